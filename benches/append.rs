@@ -0,0 +1,79 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Compares `Append::append` against constructing the concatenated `HList` directly.  The two
+//! variants in each group should report indistinguishable timings.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pl_hlist::*;
+
+fn append_small(c: &mut Criterion) {
+    let mut group = c.benchmark_group("append_4");
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            black_box(hlist!(
+                black_box(1u8),
+                black_box(2u16),
+                black_box(3u32),
+                black_box(4u64)
+            ))
+        })
+    });
+    group.bench_function("append", |b| {
+        b.iter(|| {
+            let lhs = hlist!(black_box(1u8), black_box(2u16));
+            let rhs = hlist!(black_box(3u32), black_box(4u64));
+            black_box(lhs.append(rhs))
+        })
+    });
+    group.finish();
+}
+
+fn append_medium(c: &mut Criterion) {
+    let mut group = c.benchmark_group("append_16");
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            let x = black_box(7u64);
+            black_box(hlist!(x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x))
+        })
+    });
+    group.bench_function("append", |b| {
+        b.iter(|| {
+            let x = black_box(7u64);
+            let lhs = hlist!(x, x, x, x, x, x, x, x);
+            let rhs = hlist!(x, x, x, x, x, x, x, x);
+            black_box(lhs.append(rhs))
+        })
+    });
+    group.finish();
+}
+
+fn append_wide(c: &mut Criterion) {
+    let mut group = c.benchmark_group("append_40");
+    group.bench_function("direct", |b| {
+        b.iter(|| {
+            let x = black_box(7u64);
+            black_box(hlist!(
+                x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x,
+                x, x, x, x, x, x, x, x, x, x, x, x
+            ))
+        })
+    });
+    group.bench_function("append", |b| {
+        b.iter(|| {
+            let x = black_box(7u64);
+            let lhs = hlist!(
+                x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x, x,
+                x, x, x, x, x, x, x, x
+            );
+            let rhs = hlist!(x, x, x, x);
+            black_box(lhs.append(rhs))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, append_small, append_medium, append_wide);
+criterion_main!(benches);
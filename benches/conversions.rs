@@ -0,0 +1,128 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Compares round-trips of a wide struct through its derived `HList` conversions against
+//! copying the struct field by field.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pl_hlist::*;
+
+/// A struct with 24 fields, wide enough that recursive conversions would be costly.
+#[derive(Clone, Copy, HListSupport)]
+struct Wide {
+    a0: u64,
+    a1: u32,
+    a2: u16,
+    a3: u8,
+    a4: f64,
+    a5: f32,
+    a6: i64,
+    a7: i32,
+    a8: i16,
+    a9: i8,
+    a10: bool,
+    a11: char,
+    a12: u64,
+    a13: u32,
+    a14: u16,
+    a15: u8,
+    a16: f64,
+    a17: f32,
+    a18: i64,
+    a19: i32,
+    a20: i16,
+    a21: i8,
+    a22: bool,
+    a23: char,
+}
+
+/// Copies each field of the struct by hand.
+#[inline(never)]
+fn manual_copy(w: Wide) -> Wide {
+    Wide {
+        a0: w.a0,
+        a1: w.a1,
+        a2: w.a2,
+        a3: w.a3,
+        a4: w.a4,
+        a5: w.a5,
+        a6: w.a6,
+        a7: w.a7,
+        a8: w.a8,
+        a9: w.a9,
+        a10: w.a10,
+        a11: w.a11,
+        a12: w.a12,
+        a13: w.a13,
+        a14: w.a14,
+        a15: w.a15,
+        a16: w.a16,
+        a17: w.a17,
+        a18: w.a18,
+        a19: w.a19,
+        a20: w.a20,
+        a21: w.a21,
+        a22: w.a22,
+        a23: w.a23,
+    }
+}
+
+#[inline(never)]
+fn hlist_round_trip(w: Wide) -> Wide {
+    Wide::from_hlist(w.into_hlist())
+}
+
+#[inline(never)]
+fn hlist_clone_round_trip(w: &Wide) -> Wide {
+    Wide::from_hlist(w.to_hlist())
+}
+
+fn wide() -> Wide {
+    Wide {
+        a0: 1,
+        a1: 2,
+        a2: 3,
+        a3: 4,
+        a4: 5.0,
+        a5: 6.0,
+        a6: -7,
+        a7: -8,
+        a8: -9,
+        a9: -10,
+        a10: true,
+        a11: 'x',
+        a12: 1,
+        a13: 2,
+        a14: 3,
+        a15: 4,
+        a16: 5.0,
+        a17: 6.0,
+        a18: -7,
+        a19: -8,
+        a20: -9,
+        a21: -10,
+        a22: true,
+        a23: 'x',
+    }
+}
+
+fn round_trip_24_fields(c: &mut Criterion) {
+    let w = wide();
+
+    let mut group = c.benchmark_group("round_trip_24_fields");
+    group.bench_function("manual", |bencher| {
+        bencher.iter(|| manual_copy(black_box(w)))
+    });
+    group.bench_function("into_hlist", |bencher| {
+        bencher.iter(|| hlist_round_trip(black_box(w)))
+    });
+    group.bench_function("to_hlist", |bencher| {
+        bencher.iter(|| hlist_clone_round_trip(black_box(&w)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, round_trip_24_fields);
+criterion_main!(benches);
@@ -0,0 +1,54 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Compares `Elementwise::add_elements` against element-wise addition implemented by
+//! recursing over the cons cells.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pl_hlist::*;
+use std::ops::Add;
+
+/// Element-wise addition by recursion over the cons cells.
+trait RecursiveAdd {
+    fn recursive_add(self, other: Self) -> Self;
+}
+
+impl RecursiveAdd for HNil {
+    fn recursive_add(self, _other: Self) -> Self {
+        HNil
+    }
+}
+
+impl<H: Add<Output = H>, T: HList + RecursiveAdd> RecursiveAdd for HCons<H, T> {
+    fn recursive_add(self, other: Self) -> Self {
+        HCons(self.0 + other.0, self.1.recursive_add(other.1))
+    }
+}
+
+fn add_8xf32(c: &mut Criterion) {
+    let a = hlist!(1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+    let b = hlist!(8.0f32, 7.0, 6.0, 5.0, 4.0, 3.0, 2.0, 1.0);
+
+    let mut group = c.benchmark_group("add_8xf32");
+    group.bench_function("recursive", |bencher| {
+        bencher.iter(|| black_box(a).recursive_add(black_box(b)))
+    });
+    group.bench_function("add_elements", |bencher| {
+        bencher.iter(|| black_box(a).add_elements(black_box(b)))
+    });
+    group.finish();
+}
+
+fn mul_add_4xf64(c: &mut Criterion) {
+    let a = hlist!(1.0f64, 2.0, 3.0, 4.0);
+    let b = hlist!(4.0f64, 3.0, 2.0, 1.0);
+
+    c.bench_function("mul_add_4xf64", |bencher| {
+        bencher.iter(|| black_box(a).mul_add_elements(black_box(b), black_box(a)))
+    });
+}
+
+criterion_group!(benches, add_8xf32, mul_add_4xf64);
+criterion_main!(benches);
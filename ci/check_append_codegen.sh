@@ -0,0 +1,39 @@
+#!/bin/sh
+#
+# Checks that `Append::append` compiles to the same machine code as constructing the
+# concatenated `HList` directly, by comparing the functions in examples/append_codegen.rs.
+#
+
+set -eu
+
+cd "$(dirname "$0")/.."
+# Touch the example so that the assembly is always regenerated
+touch examples/append_codegen.rs
+cargo rustc --quiet --release --example append_codegen -- --emit=asm
+asm=$(ls -t target/release/examples/append_codegen-*.s | head -n 1)
+[ -n "$asm" ] || { echo "no assembly was generated" >&2; exit 1; }
+
+# Prints the instructions of the given function, without its labels and directives
+body() {
+    awk -v name="$1" '
+        $0 == name ":" { inside = 1; next }
+        inside && /\.cfi_endproc/ { exit }
+        inside && !/^[ \t]*\./ && !/:$/ { print }
+    ' "$asm"
+}
+
+status=0
+for n in 4 16; do
+    direct="direct_$n"
+    append="append_$n"
+    # LLVM replaces a function with an alias when it is identical to another one
+    if grep -Eq "^($direct = $append|$append = $direct)$" "$asm"; then
+        echo "$append: identical to $direct (merged)"
+    elif [ -n "$(body "$direct")" ] && [ "$(body "$direct")" = "$(body "$append")" ]; then
+        echo "$append: identical to $direct"
+    else
+        echo "$append: differs from $direct" >&2
+        status=1
+    fi
+done
+exit $status
@@ -0,0 +1,45 @@
+#!/bin/sh
+#
+# Checks that the `Elementwise` operations on 4- and 8-wide `f32` lists are vectorized, by
+# looking for packed (and no scalar) SSE arithmetic in the functions in
+# examples/elementwise_codegen.rs.  The instruction names are specific to x86-64, so the
+# check is skipped on other hosts.
+#
+
+set -eu
+
+if [ "$(uname -m)" != "x86_64" ]; then
+    echo "skipping: not an x86-64 host"
+    exit 0
+fi
+
+cd "$(dirname "$0")/.."
+# Touch the example so that the assembly is always regenerated
+touch examples/elementwise_codegen.rs
+cargo rustc --quiet --release --example elementwise_codegen -- --emit=asm
+asm=$(ls -t target/release/examples/elementwise_codegen-*.s | head -n 1)
+[ -n "$asm" ] || { echo "no assembly was generated" >&2; exit 1; }
+
+# Prints the instructions of the given function, without its labels and directives
+body() {
+    awk -v name="$1" '
+        $0 == name ":" { inside = 1; next }
+        inside && /\.cfi_endproc/ { exit }
+        inside && !/^[ \t]*\./ && !/:$/ { print }
+    ' "$asm"
+}
+
+status=0
+for function in add_4xf32 mul_4xf32 add_8xf32 mul_add_8xf32; do
+    instructions=$(body "$function")
+    if ! echo "$instructions" | grep -Eq "^\s*(add|mul)ps\b"; then
+        echo "$function: no packed arithmetic" >&2
+        status=1
+    elif echo "$instructions" | grep -Eq "^\s*(add|mul)ss\b"; then
+        echo "$function: scalar arithmetic alongside packed arithmetic" >&2
+        status=1
+    else
+        echo "$function: vectorized"
+    fi
+done
+exit $status
@@ -0,0 +1,46 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Pairs of functions that build the same `HList` directly and with `Append::append`, for
+//! checking that the two compile to identical machine code.  `ci/check_append_codegen.sh`
+//! compiles this example to assembly and compares each `direct_*` function with its
+//! `append_*` counterpart.
+
+use pl_hlist::*;
+
+type Four = HList![u8, u16, u32, u64];
+type Sixteen =
+    HList![u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64, u64];
+
+#[no_mangle]
+#[inline(never)]
+pub fn direct_4(a: u8, b: u16, c: u32, d: u64) -> Four {
+    hlist!(a, b, c, d)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn append_4(a: u8, b: u16, c: u32, d: u64) -> Four {
+    hlist!(a, b).append(hlist!(c, d))
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn direct_16(x: [u64; 16]) -> Sixteen {
+    let [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p] = x;
+    hlist!(a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn append_16(x: [u64; 16]) -> Sixteen {
+    let [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p] = x;
+    hlist!(a, b, c, d, e, f, g, h).append(hlist!(i, j, k, l, m, n, o, p))
+}
+
+fn main() {
+    assert_eq!(direct_4(1, 2, 3, 4), append_4(1, 2, 3, 4));
+    assert_eq!(direct_16([7; 16]), append_16([7; 16]));
+}
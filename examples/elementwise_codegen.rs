@@ -0,0 +1,46 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Element-wise operations on 4- and 8-wide `f32` lists, for checking that they are
+//! vectorized.  `ci/check_elementwise_codegen.sh` compiles this example to assembly and
+//! checks that each function uses packed SIMD instructions.
+
+use pl_hlist::*;
+
+type F32x4 = HList![f32, f32, f32, f32];
+type F32x8 = HList![f32, f32, f32, f32, f32, f32, f32, f32];
+
+#[no_mangle]
+#[inline(never)]
+pub fn add_4xf32(a: F32x4, b: F32x4) -> F32x4 {
+    a.add_elements(b)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn mul_4xf32(a: F32x4, b: F32x4) -> F32x4 {
+    a.mul_elements(b)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn add_8xf32(a: F32x8, b: F32x8) -> F32x8 {
+    a.add_elements(b)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn mul_add_8xf32(a: F32x8, b: F32x8, c: F32x8) -> F32x8 {
+    a.mul_add_elements(b, c)
+}
+
+fn main() {
+    let a = hlist!(1.0f32, 2.0, 3.0, 4.0);
+    assert_eq!(add_4xf32(a, a), hlist!(2.0f32, 4.0, 6.0, 8.0));
+    assert_eq!(mul_4xf32(a, a), hlist!(1.0f32, 4.0, 9.0, 16.0));
+
+    let b = hlist!(1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+    assert_eq!(add_8xf32(b, b), mul_add_8xf32(b, F32x8::from([1.0; 8]), b));
+}
@@ -6,136 +6,1226 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
+use proc_macro2::Span;
 use quote::{quote, ToTokens};
-use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Field, Fields};
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DataStruct, DeriveInput, Field, Fields, Ident};
 
-#[proc_macro_derive(HListSupport)]
+#[proc_macro_derive(HListSupport, attributes(hlist))]
 pub fn hlist_support_derive(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
     let input = parse_macro_input!(input as DeriveInput);
 
-    // Check that the input type is a struct
-    let data_struct: DataStruct;
-    if let Data::Struct(s) = input.data {
-        data_struct = s
-    } else {
-        panic!("`HListSupport` may only be applied to structs")
-    }
+    // Check that the input type is a struct; tuple and unit structs get the positional
+    // conversions only, since their fields have no names to use as labels
+    let fields = struct_fields(input.data, "HListSupport");
 
-    // Check that the struct has named fields, since that's the only
-    // type we support at the moment
-    let fields: Fields;
-    if let Fields::Named(_) = data_struct.fields {
-        fields = data_struct.fields
-    } else {
-        panic!("`HListSupport` may only be applied to structs with named fields")
-    }
-
-    // Extract the struct name
+    // Extract the struct name, visibility, and generics.  The conversions that copy
+    // fields additionally require each field type to be `Clone`.
     let struct_name = &input.ident;
+    let struct_vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let clone_where_clause = where_with_bounds(
+        &input.generics,
+        hlist_fields(&fields).into_iter().map(|(_, f)| {
+            let ty = &f.ty;
+            quote!(#ty: ::core::clone::Clone)
+        }),
+    );
+
+    // Determine how the field label types are named, and which of the optional impls
+    // were requested
+    let labels = Labels::new(struct_name, &input.attrs);
+    let attrs = struct_attrs(&input.attrs);
+
+    // Determine which fields are part of the HList representation; the others are
+    // skipped, and filled in with their default values by `from_hlist`
+    let hlist_fields = hlist_fields(&fields);
+    let any_skipped = hlist_fields.len() < fields.len();
 
     // Build the HList type
-    let hlist_type = hlist_type(fields.iter());
+    let hlist_type = hlist_type(&hlist_fields);
 
     // Build the HList pattern
-    let hlist_pat = hlist_pattern(fields.iter());
+    let hlist_pat = hlist_pattern(&hlist_fields);
 
     // Build the struct initializer
-    let struct_field_init = struct_field_init(fields.iter());
+    let struct_init = struct_init(struct_name, &fields);
+
+    // Build the HList initializer for IntoHList; like that of ToHList below, it is a
+    // single nested constructor expression, so no recursion through trait impls is needed
+    let hlist_init = hlist_init(&hlist_fields);
 
-    // Build the HList initializer for ToHList
-    let hlist_cloned_init = hlist_cloned_init(fields.iter());
+    // Build the FromHList and ToHList bodies.  With `#[hlist(layout)]`, these go through
+    // `CopyConversions`, which copies the struct in one go when its layout matches that of
+    // the HList; otherwise they convert field by field directly.
+    let (from_hlist_body, to_hlist_body, layout) = if attrs.layout {
+        // Build the pairs of field and element offsets for HListLayout.  A struct with
+        // skipped fields never shares the layout of its HList, since conversions must
+        // produce the skipped values.
+        let layout_offsets = layout_offsets(&hlist_fields);
+        let hlist_clone_init = hlist_clone_init(&hlist_fields, &Ident::new("s", Span::call_site()));
+        let same_layout_check = if any_skipped {
+            quote!(false)
+        } else {
+            quote!(unsafe { ::pl_hlist::same_offsets::<Self, #hlist_type>(&[#(#layout_offsets),*]) })
+        };
+        (
+            quote! {
+                <Self as ::pl_hlist::CopyConversions<#hlist_type>>::from_hlist_with(hlist, |hlist| {
+                    match hlist {
+                        #hlist_pat => #struct_init
+                    }
+                })
+            },
+            quote! {
+                <Self as ::pl_hlist::CopyConversions<#hlist_type>>::to_hlist_with(self, |s| #hlist_clone_init)
+            },
+            quote! {
+                // Include the HListLayout impl, which compares the offset of each field
+                // with the offset of the corresponding element
+                #[allow(dead_code, unused_variables)]
+                unsafe impl #impl_generics ::pl_hlist::HListLayout<#hlist_type> for #struct_name #ty_generics #where_clause {
+                    const SAME_LAYOUT: bool = {
+                        let s = ::core::mem::MaybeUninit::<Self>::uninit();
+                        let h = ::core::mem::MaybeUninit::<#hlist_type>::uninit();
+                        let (s, h) = (s.as_ptr(), h.as_ptr());
+                        #same_layout_check
+                    };
+                }
+            },
+        )
+    } else {
+        (
+            quote! {
+                match hlist {
+                    #hlist_pat => #struct_init
+                }
+            },
+            hlist_clone_init(&hlist_fields, &Ident::new("self", Span::call_site())),
+            quote!(),
+        )
+    };
+
+    // Build the HListView and HListViewMut impls, if requested with `#[hlist(views)]`
+    let views = if attrs.views {
+        let hlist_view_type = hlist_view_type(&hlist_fields);
+        let hlist_view_init = hlist_view_init(&hlist_fields);
+        let hlist_view_mut_type = hlist_view_mut_type(&hlist_fields);
+        let hlist_view_mut_init = hlist_view_mut_init(&hlist_fields);
+        quote! {
+            // Include the HListView impl
+            #[allow(dead_code)]
+            impl #impl_generics ::pl_hlist::HListView for #struct_name #ty_generics #where_clause {
+                type View<'__hlist> = #hlist_view_type where Self: '__hlist;
+
+                #[inline]
+                fn hlist_view(&self) -> Self::View<'_> {
+                    #hlist_view_init
+                }
+            }
 
-    // Build the HList initializer for IntoHList
-    let hlist_init = hlist_init(fields.iter());
+            // Include the HListViewMut impl
+            #[allow(dead_code)]
+            impl #impl_generics ::pl_hlist::HListViewMut for #struct_name #ty_generics #where_clause {
+                type ViewMut<'__hlist> = #hlist_view_mut_type where Self: '__hlist;
+
+                #[inline]
+                fn hlist_view_mut(&mut self) -> Self::ViewMut<'_> {
+                    #hlist_view_mut_init
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    // Build the label types and the labelled HList conversions for structs with named
+    // fields.  The labelled conversions delegate to the plain ones via the generic
+    // helpers in the runtime crate, rather than being expanded field by field; adding or
+    // removing labels compiles to nothing, so only the plain conversions are expanded.
+    let labelled = match fields {
+        Fields::Named(_) => {
+            let labels_mod = labels.module_decl(struct_vis, &hlist_fields);
+            let label_impls = label_impls(&labels, &hlist_fields);
+            let labelled_hlist_type = labelled_hlist_type(&labels, &hlist_fields);
+            let lens_impls = if attrs.lenses {
+                lens_impls(&labels, &input.generics, struct_name, &hlist_fields)
+            } else {
+                quote!()
+            };
+            quote! {
+                // Include the labels module, if one is needed
+                #labels_mod
+                #label_impls
+
+                // Include the Lens impls, one per field label, if requested with
+                // `#[hlist(lenses)]`
+                #lens_impls
+
+                // Include the FromLabelledHList impl
+                #[allow(dead_code)]
+                impl #impl_generics ::pl_hlist::FromLabelledHList<#labelled_hlist_type> for #struct_name #ty_generics #where_clause {
+                    #[inline]
+                    fn from_labelled_hlist(hlist: #labelled_hlist_type) -> Self {
+                        <Self as ::pl_hlist::FromHList<#hlist_type>>::from_hlist(
+                            <#labelled_hlist_type as ::pl_hlist::StripLabels>::strip_labels(hlist),
+                        )
+                    }
+                }
+
+                // Include the ToLabelledHList impl
+                #[allow(dead_code)]
+                impl #impl_generics ::pl_hlist::ToLabelledHList<#labelled_hlist_type> for #struct_name #ty_generics #clone_where_clause {
+                    #[inline]
+                    fn to_labelled_hlist(&self) -> #labelled_hlist_type {
+                        ::pl_hlist::IntoFields::into_fields(<Self as ::pl_hlist::ToHList<#hlist_type>>::to_hlist(self))
+                    }
+                }
+
+                // Include the IntoLabelledHList impl
+                #[allow(dead_code)]
+                impl #impl_generics ::pl_hlist::IntoLabelledHList<#labelled_hlist_type> for #struct_name #ty_generics #where_clause {
+                    #[inline]
+                    fn into_labelled_hlist(self) -> #labelled_hlist_type {
+                        ::pl_hlist::IntoFields::into_fields(<Self as ::pl_hlist::IntoHList<#hlist_type>>::into_hlist(self))
+                    }
+                }
+            }
+        }
+        _ => quote!(),
+    };
+
+    // Build the tuple conversions, if requested with `#[hlist(tuple)]`.  Both go through
+    // the HList pattern and struct initializer, so skipped fields are handled as above.
+    let tuple = if attrs.tuple {
+        let tuple_types = hlist_fields.iter().map(|(_, f)| &f.ty);
+        let tuple_type = quote!((#(#tuple_types,)*));
+        let tuple_bindings: Vec<_> = hlist_fields
+            .iter()
+            .map(|(i, f)| field_binding(*i, f))
+            .collect();
+        quote! {
+            // Include the From<tuple> impl
+            #[allow(dead_code)]
+            impl #impl_generics ::core::convert::From<#tuple_type> for #struct_name #ty_generics #where_clause {
+                #[inline]
+                fn from(tuple: #tuple_type) -> Self {
+                    let (#(#tuple_bindings,)*) = tuple;
+                    <Self as ::pl_hlist::FromHList<#hlist_type>>::from_hlist(#hlist_pat)
+                }
+            }
+
+            // Include the From<struct> impl for the tuple
+            #[allow(dead_code)]
+            impl #impl_generics ::core::convert::From<#struct_name #ty_generics> for #tuple_type #where_clause {
+                #[inline]
+                fn from(s: #struct_name #ty_generics) -> Self {
+                    match <#struct_name #ty_generics as ::pl_hlist::IntoHList<#hlist_type>>::into_hlist(s) {
+                        #hlist_pat => (#(#tuple_bindings,)*),
+                    }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
 
     // Build the output
     let expanded = quote! {
         // Include the FromHList impl
         #[allow(dead_code)]
-        impl FromHList<#hlist_type> for #struct_name {
+        impl #impl_generics ::pl_hlist::FromHList<#hlist_type> for #struct_name #ty_generics #where_clause {
+            #[inline]
             fn from_hlist(hlist: #hlist_type) -> Self {
-                match hlist {
-                    #hlist_pat => #struct_name { #struct_field_init }
-                }
+                #from_hlist_body
             }
         }
 
         // Include the ToHList impl
         #[allow(dead_code)]
-        impl ToHList<#hlist_type> for #struct_name {
+        impl #impl_generics ::pl_hlist::ToHList<#hlist_type> for #struct_name #ty_generics #clone_where_clause {
+            #[inline]
             fn to_hlist(&self) -> #hlist_type {
-                #hlist_cloned_init
+                #to_hlist_body
             }
         }
 
         // Include the IntoHList impl
         #[allow(dead_code)]
-        impl IntoHList<#hlist_type> for #struct_name {
+        impl #impl_generics ::pl_hlist::IntoHList<#hlist_type> for #struct_name #ty_generics #where_clause {
+            #[inline]
             fn into_hlist(self) -> #hlist_type {
                 #hlist_init
             }
         }
+
+        #layout
+
+        #views
+
+        #labelled
+
+        #tuple
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(PartialSupport)]
+pub fn partial_support_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is a struct with named fields
+    let fields = named_fields(input.data, "PartialSupport");
+
+    // Extract the struct name and visibility
+    let struct_name = &input.ident;
+    let struct_vis = &input.vis;
+
+    // Build the names of the partial struct and of the module that holds the
+    // typestate markers for unset fields
+    let labels = Labels::new(struct_name, &input.attrs);
+    let partial_name = Ident::new(&format!("{}Partial", struct_name), Span::call_site());
+    let partial_mod = partial_mod(struct_name, &input.attrs);
+
+    // Build one typestate parameter per field; each defaults to the field's unset
+    // marker type
+    let hlist_fields = hlist_fields(&fields);
+    let field_names: Vec<_> = hlist_fields
+        .iter()
+        .map(|(_, f)| f.ident.as_ref().unwrap())
+        .collect();
+    let field_types: Vec<_> = hlist_fields.iter().map(|(_, f)| &f.ty).collect();
+    let params: Vec<_> = (0..field_names.len())
+        .map(|i| Ident::new(&format!("__S{}", i), Span::call_site()))
+        .collect();
+
+    // Build the labelled HList of optional values that backs the partial struct, and
+    // its initializer with every field unset
+    let fields_type = hlist_fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (_, f)| {
+            let label = labels.label_type(f);
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<::pl_hlist::Field<#label, ::core::option::Option<#ty>>, #tail>)
+        });
+    let fields_init = field_names.iter().fold(
+        quote!(::pl_hlist::HNil),
+        |tail, _| quote!(::pl_hlist::HCons(::pl_hlist::Field::new(::core::option::Option::None), #tail)),
+    );
+
+    // Build the generics of the partial struct: those of the struct itself, followed by
+    // the typestate parameters
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let struct_args: Vec<_> = input
+        .generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+            syn::GenericParam::Type(t) => t.ident.to_token_stream(),
+            syn::GenericParam::Const(c) => c.ident.to_token_stream(),
+        })
+        .collect();
+    let mut partial_generics = input.generics.clone();
+    partial_generics.params.extend::<Vec<syn::GenericParam>>(
+        params
+            .iter()
+            .zip(&field_names)
+            .map(|(param, name)| syn::parse_quote!(#param = #partial_mod::#name))
+            .collect(),
+    );
+    let (partial_impl_generics, partial_ty_generics, _) = partial_generics.split_for_impl();
+    let partial_params = &partial_generics.params;
+
+    // Build the setter and getter for each field
+    let accessors =
+        field_names
+            .iter()
+            .zip(&field_types)
+            .enumerate()
+            .map(|(i, (name, ty))| {
+                let setter = Ident::new(&format!("set_{}", name.unraw()), Span::call_site());
+                let set_params = params.iter().enumerate().map(|(j, param)| {
+                    if i == j {
+                        quote!(::pl_hlist::Set)
+                    } else {
+                        quote!(#param)
+                    }
+                });
+                let tails: Vec<_> = (0..i).map(|_| syn::Index::from(1)).collect();
+                let head = syn::Index::from(0);
+                quote! {
+                    /// Sets the value of this field.
+                    #struct_vis fn #setter(self, value: #ty) -> #partial_name<#(#struct_args,)* #(#set_params),*> {
+                        let mut fields = self.fields;
+                        fields #(.#tails)*.#head.value = ::core::option::Option::Some(value);
+                        #partial_name { fields, state: ::core::marker::PhantomData }
+                    }
+
+                    /// Returns the value of this field, if it has been set.
+                    #struct_vis fn #name(&self) -> ::core::option::Option<&#ty> {
+                        self.fields #(.#tails)*.#head.value.as_ref()
+                    }
+                }
+            });
+
+    // Build the output
+    let expanded = quote! {
+        // Include the typestate markers for unset fields, one per field
+        #[allow(dead_code, non_camel_case_types)]
+        #struct_vis mod #partial_mod {
+            #(pub struct #field_names;)*
+        }
+
+        // Include the partial struct, whose fields are set one at a time
+        #[allow(dead_code)]
+        #struct_vis struct #partial_name<#partial_params> #where_clause {
+            fields: #fields_type,
+            state: ::core::marker::PhantomData<(#(#params,)* fn() -> #struct_name #ty_generics)>,
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            /// Returns a partial value with no fields set.
+            #struct_vis fn partial() -> #partial_name<#(#struct_args),*> {
+                #partial_name { fields: #fields_init, state: ::core::marker::PhantomData }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #partial_impl_generics #partial_name #partial_ty_generics #where_clause {
+            #(#accessors)*
+
+            /// Builds the complete value.  This is only callable once every field has
+            /// been set.
+            #struct_vis fn finalize(self) -> #struct_name #ty_generics
+            where
+                #(#params: ::pl_hlist::IsSet),*
+            {
+                match ::pl_hlist::UnwrapFields::unwrap_fields(self.fields) {
+                    ::core::option::Option::Some(fields) => {
+                        ::pl_hlist::FromLabelledHList::from_labelled_hlist(fields)
+                    }
+                    ::core::option::Option::None => ::core::unreachable!(),
+                }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(Fixture)]
+pub fn fixture_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is a struct with named fields
+    let fields = named_fields(input.data, "Fixture");
+
+    // Build the labelled HList type
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let labelled_hlist_type = labelled_hlist_type(
+        &Labels::new(struct_name, &input.attrs),
+        &hlist_fields(&fields),
+    );
+
+    // Build the output; the methods are provided by the trait
+    let expanded = quote! {
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::Fixture for #struct_name #ty_generics #where_clause {
+            type Fields = #labelled_hlist_type;
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(Metrics)]
+pub fn metrics_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is a struct with named fields
+    let fields = named_fields(input.data, "Metrics");
+
+    // Build the labelled HList type and initializer over references to the fields that
+    // are recorded as metrics
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let labels = Labels::new(struct_name, &input.attrs);
+    let metric_fields: Vec<_> = hlist_fields(&fields)
+        .into_iter()
+        .map(|(_, f)| f)
+        .filter(|f| is_metric_type(&f.ty))
+        .collect();
+    let metrics_type = metric_fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, f| {
+            let label = labels.label_type(f);
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<::pl_hlist::Field<#label, &'__metrics #ty>, #tail>)
+        });
+    let metrics_init = metric_fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, f| {
+            let name = f.ident.as_ref().unwrap();
+            quote!(::pl_hlist::HCons(::pl_hlist::Field::new(&self.#name), #tail))
+        });
+
+    // Build the output; the recording is provided by the trait
+    let expanded = quote! {
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::EmitMetrics for #struct_name #ty_generics #where_clause {
+            type Metrics<'__metrics> = #metrics_type where Self: '__metrics;
+
+            fn metric_fields(&self) -> Self::Metrics<'_> {
+                #metrics_init
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(HListEncode)]
+pub fn hlist_encode_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is a struct, and require each field in its HList
+    // representation to be encodable
+    let fields = struct_fields(input.data, "HListEncode");
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let where_clause = where_with_bounds(
+        &input.generics,
+        hlist_fields(&fields).into_iter().map(|(_, f)| {
+            let ty = &f.ty;
+            quote!(#ty: ::pl_hlist::HListEncode)
+        }),
+    );
+
+    // Build the output; the struct is encoded as an HList of references to the fields in
+    // its HList representation, which does not rely on the optional `HListView` impl
+    let hlist_view_init = hlist_view_init(&hlist_fields(&fields));
+    let expanded = quote! {
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::HListEncode for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn encode<W: ::pl_hlist::ByteSink + ?Sized>(&self, out: &mut W) {
+                ::pl_hlist::HListEncode::encode(&#hlist_view_init, out)
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_derive(HListDecode)]
+pub fn hlist_decode_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is a struct
+    let fields = struct_fields(input.data, "HListDecode");
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+    let hlist_type = hlist_type(&hlist_fields(&fields));
+    let where_clause = where_with_bounds(
+        &input.generics,
+        ::core::iter::once(quote!(#hlist_type: ::pl_hlist::HListDecode)),
+    );
+
+    // Build the output; the struct is decoded from its HList representation
+    let expanded = quote! {
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::HListDecode for #struct_name #ty_generics #where_clause {
+            #[inline]
+            fn decode(input: &mut &[u8]) -> ::core::result::Result<Self, ::pl_hlist::DecodeError> {
+                <#hlist_type as ::pl_hlist::HListDecode>::decode(input)
+                    .map(<Self as ::pl_hlist::FromHList<#hlist_type>>::from_hlist)
+            }
+        }
     };
 
     // Hand the output tokens back to the compiler
     TokenStream::from(expanded)
 }
 
-/// Recursive function that builds up an HList type using the types from a
-/// series of Fields.
-fn hlist_type(mut fields: syn::punctuated::Iter<Field>) -> proc_macro2::TokenStream {
-    match fields.next() {
-        Some(field) => {
-            let lhs = field.ty.to_token_stream();
-            let rhs = hlist_type(fields);
-            quote!(HCons<#lhs, #rhs>)
+/// Returns true if the given field type is recorded as a metric by the `Metrics` derive,
+/// which is the case for the numeric primitives and `Monotonic` wrappers of the unsigned
+/// integers that `Metric` supports as counters.
+fn is_metric_type(ty: &syn::Type) -> bool {
+    const GAUGE_TYPES: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+    const COUNTER_TYPES: &[&str] = &["u8", "u16", "u32", "u64", "usize"];
+    let segment = match ty {
+        syn::Type::Path(path) => match path.path.segments.last() {
+            Some(segment) => segment,
+            None => return false,
+        },
+        _ => return false,
+    };
+    if segment.ident != "Monotonic" {
+        return GAUGE_TYPES.contains(&segment.ident.to_string().as_str());
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(syn::Type::Path(inner))) => inner
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| COUNTER_TYPES.contains(&ident.to_string().as_str())),
+                _ => false,
+            }
         }
-        None => quote!(HNil),
+        _ => false,
     }
 }
 
-/// Recursive function that builds up an HList pattern using the names from a
-/// series of Fields.
-fn hlist_pattern(mut fields: syn::punctuated::Iter<Field>) -> proc_macro2::TokenStream {
-    match fields.next() {
-        Some(field) => {
-            let lhs = field.ident.as_ref();
-            let rhs = hlist_pattern(fields);
-            quote!(HCons(#lhs, #rhs))
+#[proc_macro_derive(CoproductSupport)]
+pub fn coproduct_support_derive(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree
+    let input = parse_macro_input!(input as DeriveInput);
+
+    // Check that the input type is an enum
+    let variants = match input.data {
+        Data::Enum(data_enum) => data_enum.variants,
+        _ => panic!("`CoproductSupport` may only be applied to enums"),
+    };
+    let enum_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let clone_where_clause = where_with_bounds(
+        &input.generics,
+        variants.iter().flat_map(|v| v.fields.iter()).map(|f| {
+            let ty = &f.ty;
+            quote!(#ty: ::core::clone::Clone)
+        }),
+    );
+
+    // Build the coproduct type, with one variant per enum variant holding an HList of
+    // the variant's fields
+    let coproduct_type = variants
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::CNil), |tail, variant| {
+            let payload = hlist_type(&hlist_fields(&variant.fields));
+            quote!(::pl_hlist::Coproduct<#payload, #tail>)
+        });
+
+    // Build the match arms for each conversion.  Each enum variant binds its fields by
+    // name (or by position, for tuple variants) and corresponds to the coproduct value
+    // that is reached by `i` steps to the right.
+    let mut into_arms = Vec::new();
+    let mut to_arms = Vec::new();
+    let mut from_arms = Vec::new();
+    for (i, variant) in variants.iter().enumerate() {
+        let variant_name = &variant.ident;
+        let bindings: Vec<_> = variant
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(j, f)| match &f.ident {
+                Some(name) => name.clone(),
+                None => Ident::new(&format!("__v{}", j), Span::call_site()),
+            })
+            .collect();
+        let variant_pat = match &variant.fields {
+            Fields::Named(_) => quote!(#enum_name::#variant_name { #(#bindings),* }),
+            Fields::Unnamed(_) => quote!(#enum_name::#variant_name(#(#bindings),*)),
+            Fields::Unit => quote!(#enum_name::#variant_name),
+        };
+        let payload_pat = bindings.iter().rev().fold(
+            quote!(::pl_hlist::HNil),
+            |tail, b| quote!(::pl_hlist::HCons(#b, #tail)),
+        );
+        let cloned_payload = bindings.iter().rev().fold(
+            quote!(::pl_hlist::HNil),
+            |tail, b| quote!(::pl_hlist::HCons(::core::clone::Clone::clone(#b), #tail)),
+        );
+        let inject = |inner: proc_macro2::TokenStream| {
+            (0..i).fold(
+                quote!(::pl_hlist::Coproduct::Inl(#inner)),
+                |acc, _| quote!(::pl_hlist::Coproduct::Inr(#acc)),
+            )
+        };
+        let coproduct_pat = inject(payload_pat.clone());
+        let coproduct_value = inject(payload_pat);
+        let cloned_coproduct_value = inject(cloned_payload);
+        into_arms.push(quote!(#variant_pat => #coproduct_value));
+        to_arms.push(quote!(#variant_pat => #cloned_coproduct_value));
+        from_arms.push(quote!(#coproduct_pat => #variant_pat));
+    }
+    let cnil_pat = (0..variants.len()).fold(
+        quote!(cnil),
+        |acc, _| quote!(::pl_hlist::Coproduct::Inr(#acc)),
+    );
+    from_arms.push(quote!(#cnil_pat => match cnil {}));
+
+    // An empty enum has no variants to borrow, so match on the (uninhabited) value itself
+    let borrowed = if variants.is_empty() {
+        quote!(*self)
+    } else {
+        quote!(self)
+    };
+
+    // Build the output
+    let expanded = quote! {
+        // Include the FromCoproduct impl
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::FromCoproduct<#coproduct_type> for #enum_name #ty_generics #where_clause {
+            fn from_coproduct(coproduct: #coproduct_type) -> Self {
+                match coproduct {
+                    #(#from_arms),*
+                }
+            }
+        }
+
+        // Include the ToCoproduct impl
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::ToCoproduct<#coproduct_type> for #enum_name #ty_generics #clone_where_clause {
+            fn to_coproduct(&self) -> #coproduct_type {
+                match #borrowed {
+                    #(#to_arms),*
+                }
+            }
         }
-        None => quote!(HNil),
+
+        // Include the IntoCoproduct impl
+        #[allow(dead_code)]
+        impl #impl_generics ::pl_hlist::IntoCoproduct<#coproduct_type> for #enum_name #ty_generics #where_clause {
+            fn into_coproduct(self) -> #coproduct_type {
+                match self {
+                    #(#into_arms),*
+                }
+            }
+        }
+    };
+
+    // Hand the output tokens back to the compiler
+    TokenStream::from(expanded)
+}
+
+/// Builds a where clause with the predicates of the given generics followed by the
+/// given additional bounds.
+fn where_with_bounds(
+    generics: &syn::Generics,
+    bounds: impl Iterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let predicates = generics
+        .where_clause
+        .iter()
+        .flat_map(|w| w.predicates.iter())
+        .map(|p| p.to_token_stream());
+    let predicates: Vec<_> = predicates.chain(bounds).collect();
+    quote!(where #(#predicates),*)
+}
+
+/// Returns the fields of the given struct, panicking if the input is not a struct.
+fn struct_fields(data: Data, derive_name: &str) -> Fields {
+    match data {
+        Data::Struct(s) => s.fields,
+        _ => panic!("`{}` may only be applied to structs", derive_name),
+    }
+}
+
+/// Returns the named fields of the given struct, panicking if the input is not a
+/// struct with named fields (the only type we support at the moment).
+fn named_fields(data: Data, derive_name: &str) -> Fields {
+    let data_struct: DataStruct = match data {
+        Data::Struct(s) => s,
+        _ => panic!("`{}` may only be applied to structs", derive_name),
+    };
+    match data_struct.fields {
+        fields @ Fields::Named(_) => fields,
+        _ => panic!(
+            "`{}` may only be applied to structs with named fields",
+            derive_name
+        ),
     }
 }
 
-/// Recursive function that builds up an HList initializer using the names from a
-/// series of Fields.
-fn hlist_init(mut fields: syn::punctuated::Iter<Field>) -> proc_macro2::TokenStream {
-    match fields.next() {
-        Some(field) => {
-            let lhs = field.ident.as_ref();
-            let rhs = hlist_init(fields);
-            quote!(HCons(self.#lhs, #rhs))
+/// The label types of the fields of a struct.  Each label is the `Name` type that
+/// encodes the field name, so labels with the same name are the same type across
+/// structs.  Names that are too long to encode get a dedicated marker type instead,
+/// declared in the labels module.
+struct Labels {
+    /// The name of the labels module.
+    module: Ident,
+    /// Whether the labels module was requested with `#[hlist(labels)]` or
+    /// `#[hlist(labels = "name")]`.  If so, it has the struct's visibility and declares
+    /// an alias for every label; otherwise it is hidden, and only declared if a marker
+    /// type is needed.
+    public: bool,
+}
+
+impl Labels {
+    /// Returns the labels of the given struct.  A requested labels module is named by
+    /// the attribute, or else is the struct name in `snake_case` followed by `_labels`;
+    /// a hidden one is named after the struct exactly, so that it cannot collide with
+    /// that of another struct in the same scope.
+    fn new(struct_name: &Ident, attrs: &[syn::Attribute]) -> Self {
+        let (name, public) = match struct_attrs(attrs).labels {
+            Some(Some(name)) => (name, true),
+            Some(None) => (
+                format!("{}_labels", snake_case(&struct_name.to_string())),
+                true,
+            ),
+            None => (format!("__{}_labels", struct_name), false),
+        };
+        Labels {
+            module: Ident::new(&name, Span::call_site()),
+            public,
+        }
+    }
+
+    /// Returns the label type of the given field.
+    fn label_type(&self, field: &Field) -> proc_macro2::TokenStream {
+        let label_name = field.ident.as_ref().unwrap();
+        let label_str = label_name.unraw().to_string();
+        if label_str.len() <= MAX_NAME_LEN {
+            let words = encode_name(&label_str);
+            quote!(::pl_hlist::Name<#(#words),*>)
+        } else {
+            let module = &self.module;
+            quote!(#module::#label_name)
+        }
+    }
+
+    /// Builds up the declaration of the labels module for a series of Fields, or
+    /// nothing if no module is needed.
+    fn module_decl(
+        &self,
+        vis: &syn::Visibility,
+        fields: &[(usize, &Field)],
+    ) -> proc_macro2::TokenStream {
+        let decls: Vec<_> = fields
+            .iter()
+            .filter_map(|(_, f)| {
+                let label_name = f.ident.as_ref().unwrap();
+                if label_name.unraw().to_string().len() > MAX_NAME_LEN {
+                    Some(quote!(pub struct #label_name;))
+                } else if self.public {
+                    let label = self.label_type(f);
+                    Some(quote!(pub type #label_name = #label;))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let module = &self.module;
+        if self.public {
+            quote! {
+                #[allow(dead_code, non_camel_case_types)]
+                #vis mod #module {
+                    #(#decls)*
+                }
+            }
+        } else if !decls.is_empty() {
+            quote! {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types, non_snake_case)]
+                #vis mod #module {
+                    #(#decls)*
+                }
+            }
+        } else {
+            quote!()
         }
-        None => quote!(HNil),
     }
 }
 
-/// Recursive function that builds up an HList initializer using the names from a
-/// series of Fields.
-fn hlist_cloned_init(mut fields: syn::punctuated::Iter<Field>) -> proc_macro2::TokenStream {
-    match fields.next() {
-        Some(field) => {
-            let lhs = field.ident.as_ref();
-            let rhs = hlist_cloned_init(fields);
-            quote!(HCons(self.#lhs.clone(), #rhs))
+/// Returns the name of the module that holds the typestate markers of the partial
+/// struct generated for the given struct: the name of its requested labels module with
+/// `_partial` in place of `_labels`, or else the struct name in `snake_case` followed
+/// by `_partial`.
+fn partial_mod(struct_name: &Ident, attrs: &[syn::Attribute]) -> Ident {
+    let name = match struct_attrs(attrs).labels {
+        Some(Some(name)) => format!("{}_partial", name.strip_suffix("_labels").unwrap_or(&name)),
+        _ => format!("{}_partial", snake_case(&struct_name.to_string())),
+    };
+    Ident::new(&name, Span::call_site())
+}
+
+/// Builds up an HList type using the types from a series of Fields.
+fn hlist_type(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (_, f)| {
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<#ty, #tail>)
+        })
+}
+
+/// Builds up an HList pattern binding each of a series of Fields (by name, or by
+/// position for tuple structs).
+fn hlist_pattern(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (i, f)| {
+            let binding = field_binding(*i, f);
+            quote!(::pl_hlist::HCons(#binding, #tail))
+        })
+}
+
+/// Builds up an HList initializer that moves each of a series of Fields out of `self`.
+fn hlist_init(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (i, f)| {
+            let member = field_member(*i, f);
+            quote!(::pl_hlist::HCons(self.#member, #tail))
+        })
+}
+
+/// Builds up an HList initializer containing a clone of each of a series of Fields of
+/// the struct referenced by `source`.
+fn hlist_clone_init(fields: &[(usize, &Field)], source: &Ident) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (i, f)| {
+            let member = field_member(*i, f);
+            quote!(::pl_hlist::HCons(::core::clone::Clone::clone(&#source.#member), #tail))
+        })
+}
+
+/// Builds up an HList type containing a reference (with the `'__hlist` lifetime) to
+/// each of a series of Fields.
+fn hlist_view_type(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (_, f)| {
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<&'__hlist #ty, #tail>)
+        })
+}
+
+/// Builds up an HList initializer containing a reference to each of a series of
+/// Fields.
+fn hlist_view_init(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (i, f)| {
+            let member = field_member(*i, f);
+            quote!(::pl_hlist::HCons(&self.#member, #tail))
+        })
+}
+
+/// Builds up an HList type containing a mutable reference to each of a series of
+/// Fields.
+fn hlist_view_mut_type(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (_, f)| {
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<&'__hlist mut #ty, #tail>)
+        })
+}
+
+/// Builds up an HList initializer containing a mutable reference to each of a series
+/// of Fields.
+fn hlist_view_mut_init(fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (i, f)| {
+            let member = field_member(*i, f);
+            quote!(::pl_hlist::HCons(&mut self.#member, #tail))
+        })
+}
+
+/// Builds up a `(field offset, element offset)` expression for each of a series of
+/// Fields, given raw pointers `s` to the struct and `h` to its HList representation.
+fn layout_offsets(fields: &[(usize, &Field)]) -> Vec<proc_macro2::TokenStream> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(position, (i, f))| {
+            let member = field_member(*i, f);
+            let tails = (0..position).map(|_| syn::Index::from(1));
+            let head = syn::Index::from(0);
+            quote! {
+                (
+                    ::pl_hlist::field_offset(s, ::core::ptr::addr_of!((*s).#member)),
+                    ::pl_hlist::field_offset(h, ::core::ptr::addr_of!((*h)#(.#tails)*.#head)),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Builds up an expression that initializes the given struct from the bindings made
+/// by `hlist_pattern`, filling in each skipped field with its default value.
+fn struct_init(struct_name: &Ident, fields: &Fields) -> proc_macro2::TokenStream {
+    let values = fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| match field_default(f) {
+            Some(default) => default,
+            None => field_binding(i, f).to_token_stream(),
+        });
+    match fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|f| f.ident.as_ref());
+            quote!(#struct_name { #(#names: #values),* })
+        }
+        Fields::Unnamed(_) => quote!(#struct_name(#(#values),*)),
+        Fields::Unit => quote!(#struct_name),
+    }
+}
+
+/// Returns the fields of the given struct that are part of its HList representation,
+/// along with the position of each within the struct.
+fn hlist_fields(fields: &Fields) -> Vec<(usize, &Field)> {
+    fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| field_default(f).is_none())
+        .collect()
+}
+
+/// Returns the expression that produces the value of the given field if it is excluded
+/// from the HList representation by an `#[hlist(skip)]`, `#[hlist(default)]`, or
+/// `#[hlist(default = "expr")]` attribute, or `None` if it is included.
+fn field_default(field: &Field) -> Option<proc_macro2::TokenStream> {
+    let mut default = None;
+    for attr in field.attrs.iter().filter(|a| a.path.is_ident("hlist")) {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested,
+            _ => panic!("expected `#[hlist(skip)]` or `#[hlist(default = \"expr\")]`"),
+        };
+        for meta in nested {
+            match meta {
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.is_ident("skip") || path.is_ident("default") =>
+                {
+                    default.get_or_insert_with(|| quote!(::core::default::Default::default()));
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(expr),
+                    ..
+                })) if path.is_ident("default") => {
+                    let expr: syn::Expr = expr
+                        .parse()
+                        .unwrap_or_else(|_| panic!("invalid `default` expression"));
+                    default = Some(expr.to_token_stream());
+                }
+                _ => panic!("unknown `hlist` attribute; expected `skip` or `default`"),
+            }
+        }
+    }
+    default
+}
+
+/// The options given by `#[hlist(...)]` attributes on a struct.
+struct StructAttrs {
+    /// Whether to derive conversions to and from the tuple of the HList elements, as
+    /// requested with `#[hlist(tuple)]`.
+    tuple: bool,
+    /// Whether to generate a public labels module, as requested with `#[hlist(labels)]`,
+    /// and its name, if given by `#[hlist(labels = "name")]`.
+    labels: Option<Option<String>>,
+    /// Whether to implement `HListLayout` and convert through `CopyConversions`, as
+    /// requested with `#[hlist(layout)]`.
+    layout: bool,
+    /// Whether to implement `HListView` and `HListViewMut`, as requested with
+    /// `#[hlist(views)]`.
+    views: bool,
+    /// Whether to implement `Lens` for each field label, as requested with
+    /// `#[hlist(lenses)]`.
+    lenses: bool,
+}
+
+/// Parses the `#[hlist(...)]` attributes on a struct.
+fn struct_attrs(attrs: &[syn::Attribute]) -> StructAttrs {
+    let mut options = StructAttrs {
+        tuple: false,
+        labels: None,
+        layout: false,
+        views: false,
+        lenses: false,
+    };
+    for attr in attrs.iter().filter(|a| a.path.is_ident("hlist")) {
+        let nested = match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested,
+            _ => panic!(
+                "expected `#[hlist(...)]` with `tuple`, `labels`, `layout`, `views`, or `lenses`"
+            ),
+        };
+        for meta in nested {
+            match meta {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("tuple") => {
+                    options.tuple = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("layout") => {
+                    options.layout = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("views") => {
+                    options.views = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("lenses") => {
+                    options.lenses = true;
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("labels") => {
+                    options.labels.get_or_insert(None);
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(name),
+                    ..
+                })) if path.is_ident("labels") => {
+                    let name: Ident = name
+                        .parse()
+                        .unwrap_or_else(|_| panic!("invalid `labels` module name"));
+                    options.labels = Some(Some(name.to_string()));
+                }
+                _ => panic!(
+                    "unknown `hlist` attribute on struct; expected `tuple`, `labels`, `layout`, `views`, or `lenses`"
+                ),
+            }
+        }
+    }
+    options
+}
+
+/// Returns the name used to bind the given field in patterns, which is the field name
+/// for named fields and `__f<index>` for positional ones.
+fn field_binding(index: usize, field: &Field) -> Ident {
+    match &field.ident {
+        Some(name) => name.clone(),
+        None => Ident::new(&format!("__f{}", index), Span::call_site()),
+    }
+}
+
+/// Returns the member used to access the given field, which is the field name for named
+/// fields and the index for positional ones.
+fn field_member(index: usize, field: &Field) -> syn::Member {
+    match &field.ident {
+        Some(name) => syn::Member::Named(name.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(index)),
+    }
+}
+
+/// Builds up the `Label` impls for the marker label types declared in the labels
+/// module using the names from a series of Fields.
+fn label_impls(labels: &Labels, fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    let impls = fields.iter().filter_map(|(_, f)| {
+        let label_name = f.ident.as_ref().unwrap();
+        let label_str = label_name.unraw().to_string();
+        if label_str.len() <= MAX_NAME_LEN {
+            return None;
         }
-        None => quote!(HNil),
+        let label = labels.label_type(f);
+        Some(quote! {
+            impl ::pl_hlist::Label for #label {
+                const NAME: &'static str = #label_str;
+            }
+        })
+    });
+    quote!(#(#impls)*)
+}
+
+/// Builds up the `Lens` impls that focus on each of a series of Fields through its
+/// label type.
+fn lens_impls(
+    labels: &Labels,
+    generics: &syn::Generics,
+    struct_name: &Ident,
+    fields: &[(usize, &Field)],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let impls = fields.iter().map(|(_, f)| {
+        let label_name = f.ident.as_ref().unwrap();
+        let label = labels.label_type(f);
+        let ty = &f.ty;
+        quote! {
+            #[allow(dead_code)]
+            impl #impl_generics ::pl_hlist::Lens<#struct_name #ty_generics> for #label #where_clause {
+                type Target = #ty;
+
+                #[inline(always)]
+                fn get<'__lens>(source: &'__lens #struct_name #ty_generics) -> &'__lens #ty {
+                    &source.#label_name
+                }
+
+                #[inline(always)]
+                fn get_mut<'__lens>(source: &'__lens mut #struct_name #ty_generics) -> &'__lens mut #ty {
+                    &mut source.#label_name
+                }
+            }
+        }
+    });
+    quote!(#(#impls)*)
+}
+
+/// The maximum length of a name that can be encoded in a `Name` label; this must
+/// match `pl_hlist::MAX_NAME_LEN`.
+const MAX_NAME_LEN: usize = 32;
+
+/// Encodes a name as the two `u128` words of a `Name` label, matching
+/// `pl_hlist::encode_name`.
+fn encode_name(name: &str) -> [proc_macro2::Literal; 2] {
+    let mut words = [0u128; 2];
+    for (i, b) in name.bytes().enumerate() {
+        words[i / 16] |= u128::from(b) << ((i % 16) * 8);
     }
+    [
+        proc_macro2::Literal::u128_suffixed(words[0]),
+        proc_macro2::Literal::u128_suffixed(words[1]),
+    ]
+}
+
+/// Builds up a labelled HList type using the names and types from a series of
+/// Fields.
+fn labelled_hlist_type(labels: &Labels, fields: &[(usize, &Field)]) -> proc_macro2::TokenStream {
+    fields
+        .iter()
+        .rev()
+        .fold(quote!(::pl_hlist::HNil), |tail, (_, f)| {
+            let label = labels.label_type(f);
+            let ty = &f.ty;
+            quote!(::pl_hlist::HCons<::pl_hlist::Field<#label, #ty>, #tail>)
+        })
 }
 
-/// Builds up a struct initializer list using the names from a series of Fields.
-fn struct_field_init(fields: syn::punctuated::Iter<Field>) -> proc_macro2::TokenStream {
-    let field_names = fields.map(|f| f.ident.as_ref());
-    quote!(#(#field_names),*)
+/// Converts a `CamelCase` type name into `snake_case`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let next_lower = matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            if prev_lower || (i > 0 && next_lower && chars[i - 1].is_uppercase()) {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(*c);
+        }
+    }
+    result
 }
@@ -0,0 +1,117 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for calling a function with the elements of an `HList` as its arguments, in
+/// order, so that code generic over argument lists (such as handler dispatch) can be
+/// written once rather than once per arity.
+///
+/// This is implemented for every `FnOnce` taking up to 16 arguments.  Since `&F`
+/// implements `FnOnce` whenever `F` implements `Fn`, a function can be applied to several
+/// argument lists by calling `call_with` on a reference to it.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// fn describe(n: i32, name: &str, flag: bool) -> String {
+///     format!("{} {} {}", n, name, flag)
+/// }
+///
+/// assert_eq!(describe.call_with(hlist!(1, "a", true)), "1 a true");
+///
+/// let add = |a: u32, b: u32| a + b;
+/// assert_eq!((&add).call_with(hlist!(1, 2)), 3);
+/// assert_eq!(add.call_with(hlist!(3, 4)), 7);
+/// # }
+/// ```
+pub trait ApplyHList<Args: HList> {
+    /// The type returned by the function.
+    type Output;
+
+    /// Calls this function with the elements of `args` as its arguments.
+    fn call_with(self, args: Args) -> Self::Output;
+}
+
+impl<F, R> ApplyHList<HNil> for F
+where
+    F: FnOnce() -> R,
+{
+    type Output = R;
+
+    #[inline(always)]
+    fn call_with(self, _: HNil) -> R {
+        self()
+    }
+}
+
+/// Implements `ApplyHList` for functions taking the given arguments, and then for
+/// functions taking each shorter list of arguments formed by dropping arguments from
+/// the front.
+macro_rules! impl_apply_hlist {
+    () => {};
+    ($A1:ident $a1:ident $(, $A:ident $a:ident)*) => {
+        impl<F, R, $A1 $(, $A)*> ApplyHList<hlist_type!($A1 $(, $A)*)> for F
+        where
+            F: FnOnce($A1 $(, $A)*) -> R,
+        {
+            type Output = R;
+
+            #[inline(always)]
+            fn call_with(self, args: hlist_type!($A1 $(, $A)*)) -> R {
+                let hlist_pattern!($a1 $(, $a)*) = args;
+                self($a1 $(, $a)*)
+            }
+        }
+
+        impl_apply_hlist!($($A $a),*);
+    };
+}
+
+impl_apply_hlist!(
+    A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8,
+    A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14, A15 a15, A16 a16
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_with_should_pass_elements_as_arguments() {
+        assert_eq!((|| 7).call_with(HNil), 7);
+        assert_eq!((|a: u8| a * 2).call_with(hlist!(4)), 8);
+
+        let concat = |a: &str, b: String, c: char| format!("{}{}{}", a, b, c);
+        assert_eq!(
+            (&concat).call_with(hlist!("a", String::from("b"), 'c')),
+            "abc"
+        );
+
+        let sum16 = |a: u8,
+                     b: u8,
+                     c: u8,
+                     d: u8,
+                     e: u8,
+                     f: u8,
+                     g: u8,
+                     h: u8,
+                     i: u8,
+                     j: u8,
+                     k: u8,
+                     l: u8,
+                     m: u8,
+                     n: u8,
+                     o: u8,
+                     p: u8| {
+            [a, b, c, d, e, f, g, h, i, j, k, l, m, n, o, p]
+                .iter()
+                .sum::<u8>()
+        };
+        let args = hlist!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        assert_eq!(sum16.call_with(args), 136);
+    }
+}
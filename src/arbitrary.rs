@@ -0,0 +1,62 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use quickcheck::{Arbitrary, Gen};
+
+impl Arbitrary for HNil {
+    fn arbitrary(_: &mut Gen) -> Self {
+        HNil
+    }
+}
+
+/// Generates each element independently.  Shrinking shrinks one element at a
+/// time (head first, then each element of the tail) while holding the others
+/// fixed, so a failing case is reduced element by element.
+impl<H, T> Arbitrary for HCons<H, T>
+where
+    H: Arbitrary,
+    T: HList + Arbitrary,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        HCons(H::arbitrary(g), T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let tail = self.1.clone();
+        let head = self.0.clone();
+        let shrunk_heads = self.0.shrink().map(move |h| HCons(h, tail.clone()));
+        let shrunk_tails = self.1.shrink().map(move |t| HCons(head.clone(), t));
+        Box::new(shrunk_heads.chain(shrunk_tails))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::quickcheck;
+
+    #[test]
+    fn shrinking_should_shrink_each_element_independently() {
+        let hlist = hlist!(2u8, true);
+        let shrunk: Vec<_> = hlist.shrink().collect();
+        assert_eq!(
+            shrunk,
+            vec![hlist!(0u8, true), hlist!(1u8, true), hlist!(2u8, false)]
+        );
+    }
+
+    #[test]
+    fn hnil_should_not_shrink() {
+        assert_eq!(HNil.shrink().count(), 0);
+    }
+
+    quickcheck! {
+        fn append_should_preserve_elements(a: HCons<u8, HCons<String, HNil>>, b: HCons<bool, HNil>) -> bool {
+            let HCons(x, HCons(y, HNil)) = a.clone();
+            a.append(b) == HCons(x, HCons(y, b))
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::tails::*;
+use bumpalo::boxed::Box;
+use bumpalo::Bump;
+
+/// An `HCons` whose tail is allocated in a `bumpalo` arena.
+///
+/// Consing onto an arena-allocated list moves only a pointer to the existing list, rather
+/// than the whole list, so long chains can be assembled at runtime without deep stack
+/// moves or one heap allocation per element.  The tail is dropped (running any
+/// destructors) when the `HCons` is dropped, and its memory is reclaimed when the arena
+/// is reset or dropped.
+pub type ArenaHCons<'bump, H, T> = HCons<H, Box<'bump, T>>;
+
+impl<'bump, T: HList> HList for Box<'bump, T> {}
+
+/// Allows for consing onto an `HList` while moving the existing list into an arena.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// use bumpalo::Bump;
+///
+/// # fn main() {
+/// let bump = Bump::new();
+/// let hlist = HNil.cons_in(3u8, &bump).cons_in("two", &bump).cons_in(1.0f32, &bump);
+/// assert_eq!(hlist.into_inline(), hlist!(1.0f32, "two", 3u8));
+/// # }
+/// ```
+pub trait ConsIn: HList + Sized {
+    /// Creates a new `HCons` with the given `X` value in head position, moving `self` into
+    /// the given arena as its tail.
+    fn cons_in<X>(self, x: X, bump: &Bump) -> ArenaHCons<'_, X, Self> {
+        HCons(x, Box::new_in(self, bump))
+    }
+}
+
+impl<L: HList> ConsIn for L {}
+
+/// Allows for converting a plain `HList` into an arena-allocated `HList`.
+pub trait IntoArena: HList {
+    /// The arena-allocated form of this list.
+    type Output<'bump>: HList
+    where
+        Self: 'bump;
+
+    /// Converts this list into its arena-allocated form, allocating every tail in `bump`.
+    fn into_arena<'bump>(self, bump: &'bump Bump) -> Self::Output<'bump>
+    where
+        Self: 'bump;
+}
+
+impl IntoArena for HNil {
+    type Output<'bump> = HNil;
+
+    fn into_arena<'bump>(self, _bump: &'bump Bump) -> Self::Output<'bump>
+    where
+        Self: 'bump,
+    {
+        HNil
+    }
+}
+
+impl<H, T> IntoArena for HCons<H, T>
+where
+    T: IntoArena,
+{
+    type Output<'bump>
+        = ArenaHCons<'bump, H, T::Output<'bump>>
+    where
+        Self: 'bump;
+
+    fn into_arena<'bump>(self, bump: &'bump Bump) -> Self::Output<'bump>
+    where
+        Self: 'bump,
+    {
+        HCons(self.0, Box::new_in(self.1.into_arena(bump), bump))
+    }
+}
+
+impl<'bump, H, T> IntoInline for ArenaHCons<'bump, H, T>
+where
+    T: HList + IntoInline,
+{
+    type Output = HCons<H, T::Output>;
+
+    fn into_inline(self) -> Self::Output {
+        HCons(self.0, Box::into_inner(self.1).into_inline())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn into_arena_should_round_trip() {
+        let bump = Bump::new();
+        let hlist = hlist!(1u8, String::from("two"), [3u64; 64]);
+        let arena = hlist.clone().into_arena(&bump);
+        assert_eq!(arena.1 .0, "two");
+        assert_eq!(arena.into_inline(), hlist);
+    }
+
+    #[test]
+    fn long_chains_should_be_built_in_the_arena() {
+        let bump = Bump::new();
+        let hlist = HNil
+            .cons_in([0u8; 256], &bump)
+            .cons_in([1u8; 256], &bump)
+            .cons_in([2u8; 256], &bump);
+        assert!(bump.allocated_bytes() >= 2 * 256);
+        assert!(core::mem::size_of_val(&hlist) < 2 * 256);
+        assert_eq!(hlist.1 .1 .0, [0u8; 256]);
+    }
+
+    #[test]
+    fn arena_hlists_should_drop_their_elements() {
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let bump = Bump::new();
+        drop(
+            HNil.cons_in(Counted(&drops), &bump)
+                .cons_in(Counted(&drops), &bump),
+        );
+        assert_eq!(drops.get(), 2);
+    }
+}
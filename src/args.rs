@@ -0,0 +1,357 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+/// A problem with a single command line argument.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArgError {
+    /// No argument was provided for the element at the given position.
+    MissingArgument { position: usize },
+    /// No `--name` flag was provided for the named field.
+    MissingFlag { name: &'static str },
+    /// A flag was provided without a value.
+    MissingValue { flag: String },
+    /// The argument at the given position could not be parsed.
+    InvalidArgument {
+        position: usize,
+        value: String,
+        message: String,
+    },
+    /// The value of the flag for the named field could not be parsed.
+    InvalidFlag {
+        name: &'static str,
+        value: String,
+        message: String,
+    },
+    /// An argument was provided that does not correspond to any element.
+    UnexpectedArgument { value: String },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgError::MissingArgument { position } => {
+                write!(f, "missing argument at position {}", position)
+            }
+            ArgError::MissingFlag { name } => write!(f, "missing flag `--{}`", flag_name(name)),
+            ArgError::MissingValue { flag } => write!(f, "missing value for flag `{}`", flag),
+            ArgError::InvalidArgument {
+                position,
+                value,
+                message,
+            } => write!(
+                f,
+                "invalid argument `{}` at position {}: {}",
+                value, position, message
+            ),
+            ArgError::InvalidFlag {
+                name,
+                value,
+                message,
+            } => write!(
+                f,
+                "invalid value `{}` for flag `--{}`: {}",
+                value,
+                flag_name(name),
+                message
+            ),
+            ArgError::UnexpectedArgument { value } => write!(f, "unexpected argument `{}`", value),
+        }
+    }
+}
+
+/// The aggregated set of problems encountered while parsing command line arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgsError {
+    errors: Vec<ArgError>,
+}
+
+impl ArgsError {
+    /// Returns the individual problems, in the order they were encountered.
+    pub fn errors(&self) -> &[ArgError] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArgsError {}
+
+/// Allows for parsing an `HList` from positional command line arguments, one argument
+/// per element, using `FromStr` for each element.
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// let args = vec!["8080", "true"];
+/// let hlist: HCons<u16, HCons<bool, HNil>> = FromArgs::from_args(args).unwrap();
+/// assert_eq!(hlist, hlist!(8080u16, true));
+/// ```
+pub trait FromArgs: Sized {
+    /// Parses the elements of this list from `args`, starting at the given position.
+    /// Problems are recorded in `errors`, and `None` is returned if there were any.
+    fn parse_args<I>(args: &mut I, position: usize, errors: &mut Vec<ArgError>) -> Option<Self>
+    where
+        I: Iterator<Item = String>;
+
+    /// Parses this list from the given arguments, reporting every problem encountered
+    /// rather than stopping at the first one.
+    fn from_args<I, S>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut args = args.into_iter().map(Into::into);
+        let mut errors = Vec::new();
+        let parsed = Self::parse_args(&mut args, 0, &mut errors);
+        match parsed {
+            Some(hlist) if errors.is_empty() => Ok(hlist),
+            _ => Err(ArgsError { errors }),
+        }
+    }
+}
+
+impl FromArgs for HNil {
+    fn parse_args<I>(args: &mut I, _: usize, errors: &mut Vec<ArgError>) -> Option<Self>
+    where
+        I: Iterator<Item = String>,
+    {
+        errors.extend(args.map(|value| ArgError::UnexpectedArgument { value }));
+        Some(HNil)
+    }
+}
+
+impl<H, T> FromArgs for HCons<H, T>
+where
+    H: FromStr,
+    H::Err: fmt::Display,
+    T: HList + FromArgs,
+{
+    fn parse_args<I>(args: &mut I, position: usize, errors: &mut Vec<ArgError>) -> Option<Self>
+    where
+        I: Iterator<Item = String>,
+    {
+        let head = match args.next() {
+            Some(value) => match value.parse::<H>() {
+                Ok(head) => Some(head),
+                Err(err) => {
+                    errors.push(ArgError::InvalidArgument {
+                        position,
+                        value,
+                        message: err.to_string(),
+                    });
+                    None
+                }
+            },
+            None => {
+                errors.push(ArgError::MissingArgument { position });
+                None
+            }
+        };
+        let tail = T::parse_args(args, position + 1, errors);
+        Some(HCons(head?, tail?))
+    }
+}
+
+/// Allows for parsing a labelled `HList` from `--field-name value` (or
+/// `--field-name=value`) command line flags, using `FromStr` for each field.  The flag
+/// name for each field is its label with underscores replaced by dashes.
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// #[derive(HListSupport)]
+/// struct Options {
+///     listen_port: u16,
+///     verbose: bool,
+/// }
+///
+/// let args = vec!["--verbose", "true", "--listen-port=8080"];
+/// let options = Options::from_labelled_hlist(FromFlags::from_flags(args).unwrap());
+/// assert_eq!(options.listen_port, 8080);
+/// assert!(options.verbose);
+/// ```
+pub trait FromFlags: Sized {
+    /// Parses the fields of this list from the given `(flag name, value)` pairs,
+    /// removing each pair that is used.  Problems are recorded in `errors`, and `None` is
+    /// returned if there were any.
+    fn parse_flags(
+        flags: &mut Vec<Option<(String, String)>>,
+        errors: &mut Vec<ArgError>,
+    ) -> Option<Self>;
+
+    /// Parses this list from the given arguments, reporting every problem encountered
+    /// rather than stopping at the first one.
+    fn from_flags<I, S>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut errors = Vec::new();
+        let mut flags = Vec::new();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                errors.push(ArgError::UnexpectedArgument { value: arg });
+                continue;
+            }
+            let flag = match arg.find('=') {
+                Some(i) => Some((arg[2..i].to_string(), arg[i + 1..].to_string())),
+                None => args.next().map(|value| (arg[2..].to_string(), value)),
+            };
+            match flag {
+                Some(flag) => flags.push(Some(flag)),
+                None => errors.push(ArgError::MissingValue { flag: arg }),
+            }
+        }
+
+        let parsed = Self::parse_flags(&mut flags, &mut errors);
+        match parsed {
+            Some(hlist) if errors.is_empty() => Ok(hlist),
+            _ => Err(ArgsError { errors }),
+        }
+    }
+}
+
+impl FromFlags for HNil {
+    fn parse_flags(
+        flags: &mut Vec<Option<(String, String)>>,
+        errors: &mut Vec<ArgError>,
+    ) -> Option<Self> {
+        errors.extend(flags.drain(..).flatten().map(|(name, value)| {
+            ArgError::UnexpectedArgument {
+                value: ["--", &name, "=", &value].concat(),
+            }
+        }));
+        Some(HNil)
+    }
+}
+
+impl<L, V, T> FromFlags for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: FromStr,
+    V::Err: fmt::Display,
+    T: HList + FromFlags,
+{
+    fn parse_flags(
+        flags: &mut Vec<Option<(String, String)>>,
+        errors: &mut Vec<ArgError>,
+    ) -> Option<Self> {
+        let name = flag_name(L::NAME);
+        let flag = flags
+            .iter_mut()
+            .find(|flag| matches!(flag, Some((n, _)) if *n == name))
+            .and_then(Option::take);
+        let head = match flag {
+            Some((_, value)) => match value.parse::<V>() {
+                Ok(head) => Some(Field::new(head)),
+                Err(err) => {
+                    errors.push(ArgError::InvalidFlag {
+                        name: L::NAME,
+                        value,
+                        message: err.to_string(),
+                    });
+                    None
+                }
+            },
+            None => {
+                errors.push(ArgError::MissingFlag { name: L::NAME });
+                None
+            }
+        };
+        let tail = T::parse_flags(flags, errors);
+        Some(HCons(head?, tail?))
+    }
+}
+
+/// Returns the flag name (without the leading dashes) for the given field label.
+fn flag_name(label: &str) -> String {
+    label.replace('_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Options {
+        input_file: String,
+        count: u32,
+    }
+
+    type Positional = HCons<String, HCons<u32, HCons<bool, HNil>>>;
+
+    #[test]
+    fn positional_args_should_parse_each_element() {
+        let hlist = Positional::from_args(vec!["in.txt", "3", "false"]).unwrap();
+        assert_eq!(hlist, hlist!("in.txt".to_string(), 3u32, false));
+    }
+
+    #[test]
+    fn positional_args_should_report_all_errors() {
+        let err = Positional::from_args(vec!["in.txt", "three"]).unwrap_err();
+        assert_eq!(
+            err.errors(),
+            &[
+                ArgError::InvalidArgument {
+                    position: 1,
+                    value: "three".to_string(),
+                    message: "invalid digit found in string".to_string(),
+                },
+                ArgError::MissingArgument { position: 2 },
+            ]
+        );
+
+        let err = Positional::from_args(vec!["in.txt", "3", "true", "extra"]).unwrap_err();
+        assert_eq!(err.to_string(), "unexpected argument `extra`");
+    }
+
+    #[test]
+    fn flags_should_parse_each_field() {
+        let hlist = FromFlags::from_flags(vec!["--count=2", "--input-file", "in.txt"]).unwrap();
+        assert_eq!(
+            Options::from_labelled_hlist(hlist),
+            Options {
+                input_file: "in.txt".to_string(),
+                count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn flags_should_report_all_errors() {
+        let err = <HCons<
+            Field<label!(input_file), String>,
+            HCons<Field<label!(count), u32>, HNil>,
+        >>::from_flags(vec!["stray", "--count", "x", "--verbose=true"])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected argument `stray`\n\
+             missing flag `--input-file`\n\
+             invalid value `x` for flag `--count`: invalid digit found in string\n\
+             unexpected argument `--verbose=true`"
+        );
+    }
+}
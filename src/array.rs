@@ -0,0 +1,126 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::view::*;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::array;
+
+/// Allows for converting a homogeneous `HList` (one whose elements all have type `T`)
+/// into an array of its `N` elements, in order.
+///
+/// This is implemented for `HList`s of up to 16 elements; the inverse conversion is
+/// provided by `from_array` and the `From<[T; N]>` impls.  The provided methods make
+/// the elements available as an iterator or (with the `alloc` feature) a `Vec`, for use
+/// with APIs that expect slices:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let mut hlist = hlist!(1u32, 2, 3);
+/// assert_eq!(hlist.iter().sum::<u32>(), 6);
+/// hlist.iter_mut().for_each(|n| *n *= 10);
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(hlist.into_vec(), vec![10, 20, 30]);
+/// # }
+/// ```
+pub trait IntoArray<T, const N: usize> {
+    /// Converts this list into an array of its elements.
+    fn into_array(self) -> [T; N];
+
+    /// Converts this list into a `Vec` of its elements.
+    #[cfg(feature = "alloc")]
+    fn into_vec(self) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        Vec::from(self.into_array())
+    }
+
+    /// Returns an iterator over references to the elements of this list.
+    fn iter<'a>(&'a self) -> array::IntoIter<&'a T, N>
+    where
+        Self: ToRef<'a>,
+        <Self as ToRef<'a>>::Output: IntoArray<&'a T, N>,
+    {
+        IntoIterator::into_iter(self.to_ref().into_array())
+    }
+
+    /// Returns an iterator over mutable references to the elements of this list.
+    fn iter_mut<'a>(&'a mut self) -> array::IntoIter<&'a mut T, N>
+    where
+        Self: ToMut<'a>,
+        <Self as ToMut<'a>>::Output: IntoArray<&'a mut T, N>,
+    {
+        IntoIterator::into_iter(self.to_mut().into_array())
+    }
+}
+
+impl<T> IntoArray<T, 0> for HNil {
+    #[inline(always)]
+    fn into_array(self) -> [T; 0] {
+        []
+    }
+}
+
+/// Implements `IntoArray` for the homogeneous `HList` with the given number of elements,
+/// and then for each shorter `HList`.
+macro_rules! impl_into_array {
+    () => {};
+    ($h1:ident $(, $h:ident)*) => {
+        impl<T> IntoArray<T, { count!($h1 $($h)*) }> for hlist_type!(T $(, same_type!($h, T))*) {
+            #[inline(always)]
+            fn into_array(self) -> [T; count!($h1 $($h)*)] {
+                let hlist_pattern!($h1 $(, $h)*) = self;
+                [$h1, $($h),*]
+            }
+        }
+
+        impl_into_array!($($h),*);
+    };
+}
+
+impl_into_array!(a1, a2, a3, a4, a5, a6, a7, a8, a9, a10, a11, a12, a13, a14, a15, a16);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_array_should_preserve_order() {
+        assert_eq!(hlist!(1u8, 2, 3).into_array(), [1u8, 2, 3]);
+        assert_eq!(hlist!("a").into_array(), ["a"]);
+        let empty: [u8; 0] = HNil.into_array();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn iter_should_visit_elements_in_order() {
+        let mut hlist = hlist!(1u8, 2, 3);
+        assert!(hlist.iter().copied().eq([1u8, 2, 3]));
+        for (i, n) in hlist.iter_mut().enumerate() {
+            *n += i as u8;
+        }
+        assert_eq!(hlist.into_array(), [1u8, 3, 5]);
+        assert_eq!(IntoArray::<u8, 0>::iter(&HNil).count(), 0);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn into_vec_should_collect_elements_in_order() {
+        assert_eq!(hlist!(1u8, 3, 5).into_vec(), vec![1u8, 3, 5]);
+        assert_eq!(IntoArray::<u8, 0>::into_vec(HNil), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn into_array_should_round_trip_through_from_array() {
+        let hlist = hlist!(1u32, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+        let array = hlist.into_array();
+        assert_eq!(array[15], 16);
+        assert_eq!(HCons::from(array), hlist);
+    }
+}
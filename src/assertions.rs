@@ -0,0 +1,38 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Support for the compile-time assertion macros (`assert_hlist_type!` and friends).
+//! None of these functions are meant to be called directly, or at all: the macros only
+//! name them, so that the compiler checks their bounds.
+
+use crate::len::*;
+use crate::set::*;
+
+/// Implemented only by `Self` for `T == Self`, so that a type mismatch is reported with
+/// both types named.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "expected `{T}`, found `{Self}`",
+    label = "this has type `{Self}`"
+)]
+pub trait SameTypeAs<T: ?Sized> {}
+
+impl<T: ?Sized> SameTypeAs<T> for T {}
+
+#[doc(hidden)]
+pub fn assert_same_type<A: ?Sized + SameTypeAs<B>, B: ?Sized>() {}
+
+#[doc(hidden)]
+pub fn assert_type_of<T: SameTypeAs<Expected>, Expected>(_: &T) {}
+
+#[doc(hidden)]
+pub fn assert_subset<Sub, Super, Indices>()
+where
+    Sub: SubsetOf<Super, Indices>,
+{
+}
+
+#[doc(hidden)]
+pub fn assert_same_length<A: SameLength<B>, B>() {}
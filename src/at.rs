@@ -0,0 +1,129 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for borrowing the element at position `N` of an `HList`.
+///
+/// Positions start at zero and are checked at compile time: accessing a position past the
+/// end of the list fails to compile.  This is implemented for positions up to 31.
+///
+/// `HCons` also provides `at` and `at_mut` as inherent methods, so the position can be
+/// given with a turbofish:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let mut hlist = hlist!(1u8, "two", 3u32);
+/// assert_eq!(*hlist.at::<2>(), 3);
+/// *hlist.at_mut::<0>() += 1;
+/// assert_eq!(hlist, hlist!(2u8, "two", 3u32));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two");
+/// hlist.at::<2>();
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`HList` position out of bounds",
+    label = "this `HList` has fewer elements than the requested position"
+)]
+pub trait At<const N: usize> {
+    /// The type of the element at position `N`.
+    type Output;
+
+    /// Returns a reference to the element at position `N`.
+    fn at(&self) -> &Self::Output;
+
+    /// Returns a mutable reference to the element at position `N`.
+    fn at_mut(&mut self) -> &mut Self::Output;
+}
+
+impl<H, T: HList> HCons<H, T> {
+    /// Returns a reference to the element at position `N`.
+    #[inline(always)]
+    pub fn at<const N: usize>(&self) -> &<Self as At<N>>::Output
+    where
+        Self: At<N>,
+    {
+        At::<N>::at(self)
+    }
+
+    /// Returns a mutable reference to the element at position `N`.
+    #[inline(always)]
+    pub fn at_mut<const N: usize>(&mut self) -> &mut <Self as At<N>>::Output
+    where
+        Self: At<N>,
+    {
+        At::<N>::at_mut(self)
+    }
+}
+
+impl<H, T: HList> At<0> for HCons<H, T> {
+    type Output = H;
+
+    #[inline(always)]
+    fn at(&self) -> &H {
+        &self.0
+    }
+
+    #[inline(always)]
+    fn at_mut(&mut self) -> &mut H {
+        &mut self.0
+    }
+}
+
+/// Implements `At` for each of the given positions, by delegating to the tail at the
+/// preceding position.
+macro_rules! impl_at {
+    ($($prev:literal => $n:literal),*) => {
+        $(
+            impl<H, T> At<$n> for HCons<H, T>
+            where
+                T: HList + At<$prev>,
+            {
+                type Output = T::Output;
+
+                #[inline(always)]
+                fn at(&self) -> &Self::Output {
+                    At::<$prev>::at(&self.1)
+                }
+
+                #[inline(always)]
+                fn at_mut(&mut self) -> &mut Self::Output {
+                    At::<$prev>::at_mut(&mut self.1)
+                }
+            }
+        )*
+    };
+}
+
+impl_at!(
+    0 => 1, 1 => 2, 2 => 3, 3 => 4, 4 => 5, 5 => 6, 6 => 7, 7 => 8,
+    8 => 9, 9 => 10, 10 => 11, 11 => 12, 12 => 13, 13 => 14, 14 => 15, 15 => 16,
+    16 => 17, 17 => 18, 18 => 19, 19 => 20, 20 => 21, 21 => 22, 22 => 23, 23 => 24,
+    24 => 25, 25 => 26, 26 => 27, 27 => 28, 28 => 29, 29 => 30, 30 => 31
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_should_borrow_the_element_at_the_given_position() {
+        let mut hlist = hlist!(1u8, String::from("two"), 3.0f64, 'c');
+        assert_eq!(*hlist.at::<0>(), 1);
+        assert_eq!(hlist.at::<1>(), "two");
+        *hlist.at_mut::<2>() *= 2.0;
+        assert_eq!(*hlist.at::<2>(), 6.0);
+        assert_eq!(*At::<3>::at(&hlist), 'c');
+    }
+}
@@ -0,0 +1,104 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::tails::*;
+use alloc::boxed::Box;
+
+/// An `HCons` whose tail is stored on the heap.
+///
+/// A plain `HList` stores all of its elements inline, so passing a deep list of large
+/// elements by value moves all of them (and can exhaust the stack).  A boxed `HList`
+/// stores only its head and a pointer inline, trading an indirection per element for
+/// bounded stack usage.
+pub type HConsBox<H, T> = HCons<H, Box<T>>;
+
+impl<T: HList> HList for Box<T> {}
+
+/// Allows for consing onto an `HList` while moving the existing list onto the heap.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = HNil.cons_boxed([3u8; 4096]).cons_boxed("two").cons_boxed(1.0f32);
+/// assert!(core::mem::size_of_val(&hlist) < 64);
+/// assert_eq!(hlist.into_inline(), hlist!(1.0f32, "two", [3u8; 4096]));
+/// # }
+/// ```
+pub trait ConsBoxed: HList + Sized {
+    /// Creates a new `HCons` with the given `X` value in head position, moving `self` onto
+    /// the heap as its tail.
+    fn cons_boxed<X>(self, x: X) -> HConsBox<X, Self> {
+        HCons(x, Box::new(self))
+    }
+}
+
+impl<L: HList> ConsBoxed for L {}
+
+/// Allows for converting a plain `HList` into a boxed `HList`, with every tail stored on
+/// the heap.
+pub trait IntoBoxed: HList {
+    /// The boxed form of this list.
+    type Output: HList;
+
+    /// Converts this list into its boxed form.
+    fn into_boxed(self) -> Self::Output;
+}
+
+impl IntoBoxed for HNil {
+    type Output = HNil;
+
+    fn into_boxed(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<H, T> IntoBoxed for HCons<H, T>
+where
+    T: IntoBoxed,
+{
+    type Output = HConsBox<H, T::Output>;
+
+    fn into_boxed(self) -> Self::Output {
+        HCons(self.0, Box::new(self.1.into_boxed()))
+    }
+}
+
+impl<H, T> IntoInline for HConsBox<H, T>
+where
+    T: HList + IntoInline,
+{
+    type Output = HCons<H, T::Output>;
+
+    fn into_inline(self) -> Self::Output {
+        HCons(self.0, (*self.1).into_inline())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::size_of_val;
+
+    #[test]
+    fn into_boxed_should_round_trip() {
+        let hlist = hlist!(1u8, String::from("two"), [3u64; 512]);
+        let boxed = hlist.clone().into_boxed();
+        assert!(size_of_val(&boxed) < size_of_val(&hlist));
+        assert_eq!(boxed.1 .0, "two");
+        assert_eq!(boxed.into_inline(), hlist);
+    }
+
+    #[test]
+    fn cons_boxed_should_keep_large_elements_off_the_stack() {
+        let hlist = HNil
+            .cons_boxed(vec![0u8; 16])
+            .cons_boxed([1u8; 1024])
+            .cons_boxed([2u8; 1024]);
+        assert_eq!(size_of_val(&hlist), 1024 + size_of_val(&hlist.1));
+        assert_eq!(hlist.1 .1 .0, vec![0u8; 16]);
+    }
+}
@@ -0,0 +1,136 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// A builder that accumulates the elements of an `HList` one at a time, in order.
+///
+/// The type of the builder records the elements added so far, so a partially built
+/// value can be passed between functions (each adding its own elements) without
+/// resorting to `Option` fields, and `build` is only available once the accumulated
+/// elements match the target type.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(Debug, PartialEq, HListSupport)]
+/// struct Request {
+///     method: &'static str,
+///     path: String,
+///     retries: u8,
+/// }
+///
+/// fn add_target(
+///     builder: HListBuilder<HCons<&'static str, HNil>>,
+/// ) -> HListBuilder<HCons<&'static str, HCons<String, HNil>>> {
+///     builder.add(String::from("/index.html"))
+/// }
+///
+/// let builder = HListBuilder::new().add("GET");
+/// let request: Request = add_target(builder).add(3u8).build();
+/// assert_eq!(request.path, "/index.html");
+/// # }
+/// ```
+///
+/// Building before all of the elements have been added is a compile error:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(HListSupport)]
+/// struct Request {
+///     method: &'static str,
+///     retries: u8,
+/// }
+///
+/// let request: Request = HListBuilder::new().add("GET").build();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct HListBuilder<L: HList> {
+    hlist: L,
+}
+
+impl HListBuilder<HNil> {
+    /// Creates a new builder with no elements.
+    pub fn new() -> Self {
+        HListBuilder { hlist: HNil }
+    }
+}
+
+impl Default for HListBuilder<HNil> {
+    fn default() -> Self {
+        HListBuilder::new()
+    }
+}
+
+impl<L: HList> HListBuilder<L> {
+    /// Creates a new builder that starts with the elements of the given `HList`.
+    pub fn from_hlist(hlist: L) -> Self {
+        HListBuilder { hlist }
+    }
+
+    /// Adds an element after the elements added so far.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<X>(self, x: X) -> HListBuilder<<L as Append<HCons<X, HNil>>>::Output>
+    where
+        L: Append<HCons<X, HNil>>,
+    {
+        HListBuilder {
+            hlist: self.hlist.append(HCons(x, HNil)),
+        }
+    }
+
+    /// Returns a reference to the elements added so far.
+    pub fn as_hlist(&self) -> &L {
+        &self.hlist
+    }
+
+    /// Consumes this builder, returning the elements added so far.
+    pub fn into_hlist(self) -> L {
+        self.hlist
+    }
+
+    /// Consumes this builder, converting the elements added so far into an `S`.
+    pub fn build<S: FromHList<L>>(self) -> S {
+        S::from_hlist(self.hlist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    struct Connection {
+        host: String,
+        port: u16,
+        secure: bool,
+    }
+
+    #[test]
+    fn add_should_append_elements_in_order() {
+        let builder = HListBuilder::new().add(1u8).add("two").add(3.0f32);
+        assert_eq!(builder.as_hlist(), &hlist!(1u8, "two", 3.0f32));
+        assert_eq!(builder.into_hlist(), hlist!(1u8, "two", 3.0f32));
+    }
+
+    #[test]
+    fn build_should_convert_into_the_target_struct() {
+        let builder = HListBuilder::from_hlist(hlist!(String::from("localhost")));
+        let connection: Connection = builder.add(8080u16).add(true).build();
+        assert_eq!(
+            connection,
+            Connection {
+                host: String::from("localhost"),
+                port: 8080,
+                secure: true
+            }
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::convert::TryInto;
+use core::marker::PhantomData;
+
+/// The byte order used when encoding or decoding `FixedBytes` values.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endian {
+    Big,
+    Little,
+}
+
+/// A value with a fixed-size binary encoding.  This is implemented for the primitive
+/// integer and floating point types, and for any `HList` whose elements implement it,
+/// in which case the elements are encoded back to back with no padding.
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// let header = hlist!(0x0102u16, 7u8);
+/// let bytes: [u8; 3] = header.to_be_bytes();
+/// assert_eq!(bytes, [0x01, 0x02, 7]);
+/// assert_eq!(HCons::<u16, HCons<u8, HNil>>::from_be_bytes(&bytes), header);
+/// ```
+///
+/// Using a byte array of the wrong length is a compile error:
+///
+/// ```compile_fail
+/// use pl_hlist::*;
+///
+/// let bytes: [u8; 4] = hlist!(0x0102u16, 7u8).to_be_bytes();
+/// ```
+pub trait FixedBytes: Sized {
+    /// The number of bytes in the encoded representation.
+    const SIZE: usize;
+
+    /// Writes the encoded representation into the first `SIZE` bytes of `buf`.
+    ///
+    /// Panics if `buf` is shorter than `SIZE` bytes.
+    fn write_bytes(&self, buf: &mut [u8], endian: Endian);
+
+    /// Reads a value from the first `SIZE` bytes of `buf`.
+    ///
+    /// Panics if `buf` is shorter than `SIZE` bytes.
+    fn read_bytes(buf: &[u8], endian: Endian) -> Self;
+
+    /// Encodes this value as a big-endian byte array.  `N` must equal `SIZE`, which is
+    /// checked at compile time.
+    fn to_be_bytes<const N: usize>(&self) -> [u8; N] {
+        to_bytes(self, Endian::Big)
+    }
+
+    /// Encodes this value as a little-endian byte array.  `N` must equal `SIZE`, which
+    /// is checked at compile time.
+    fn to_le_bytes<const N: usize>(&self) -> [u8; N] {
+        to_bytes(self, Endian::Little)
+    }
+
+    /// Decodes a value from a big-endian byte array.  `N` must equal `SIZE`, which is
+    /// checked at compile time.
+    fn from_be_bytes<const N: usize>(bytes: &[u8; N]) -> Self {
+        let () = SizeCheck::<Self, N>::OK;
+        Self::read_bytes(bytes, Endian::Big)
+    }
+
+    /// Decodes a value from a little-endian byte array.  `N` must equal `SIZE`, which
+    /// is checked at compile time.
+    fn from_le_bytes<const N: usize>(bytes: &[u8; N]) -> Self {
+        let () = SizeCheck::<Self, N>::OK;
+        Self::read_bytes(bytes, Endian::Little)
+    }
+}
+
+/// Fails to compile (when `OK` is referenced) unless `N` equals `T::SIZE`.
+struct SizeCheck<T, const N: usize>(PhantomData<T>);
+
+impl<T: FixedBytes, const N: usize> SizeCheck<T, N> {
+    const OK: () = assert!(
+        N == T::SIZE,
+        "byte array length does not match the encoded size"
+    );
+}
+
+fn to_bytes<T: FixedBytes, const N: usize>(value: &T, endian: Endian) -> [u8; N] {
+    let () = SizeCheck::<T, N>::OK;
+    let mut bytes = [0u8; N];
+    value.write_bytes(&mut bytes, endian);
+    bytes
+}
+
+macro_rules! impl_fixed_bytes {
+    ($($t:ty),*) => {
+        $(
+            impl FixedBytes for $t {
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                fn write_bytes(&self, buf: &mut [u8], endian: Endian) {
+                    let bytes = match endian {
+                        Endian::Big => <$t>::to_be_bytes(*self),
+                        Endian::Little => <$t>::to_le_bytes(*self),
+                    };
+                    buf[..Self::SIZE].copy_from_slice(&bytes);
+                }
+
+                fn read_bytes(buf: &[u8], endian: Endian) -> Self {
+                    let bytes = buf[..Self::SIZE].try_into().unwrap();
+                    match endian {
+                        Endian::Big => <$t>::from_be_bytes(bytes),
+                        Endian::Little => <$t>::from_le_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_fixed_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl FixedBytes for HNil {
+    const SIZE: usize = 0;
+
+    fn write_bytes(&self, _: &mut [u8], _: Endian) {}
+
+    fn read_bytes(_: &[u8], _: Endian) -> Self {
+        HNil
+    }
+}
+
+impl<H, T> FixedBytes for HCons<H, T>
+where
+    H: FixedBytes,
+    T: HList + FixedBytes,
+{
+    const SIZE: usize = H::SIZE + T::SIZE;
+
+    fn write_bytes(&self, buf: &mut [u8], endian: Endian) {
+        self.0.write_bytes(buf, endian);
+        self.1.write_bytes(&mut buf[H::SIZE..], endian);
+    }
+
+    fn read_bytes(buf: &[u8], endian: Endian) -> Self {
+        HCons(
+            H::read_bytes(buf, endian),
+            T::read_bytes(&buf[H::SIZE..], endian),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct PacketHeader {
+        version: u8,
+        flags: u8,
+        length: u16,
+        sequence: u32,
+    }
+
+    type PacketHeaderHList = HCons<u8, HCons<u8, HCons<u16, HCons<u32, HNil>>>>;
+
+    #[test]
+    fn size_should_be_sum_of_element_sizes() {
+        assert_eq!(HNil::SIZE, 0);
+        assert_eq!(<HCons<u8, HCons<f64, HNil>>>::SIZE, 9);
+        assert_eq!(PacketHeaderHList::SIZE, 8);
+    }
+
+    #[test]
+    fn encoding_should_respect_endianness() {
+        let hlist = hlist!(0x0102u16, -2i32, 1.5f32);
+        let be: [u8; 10] = hlist.to_be_bytes();
+        assert_eq!(
+            be,
+            [0x01, 0x02, 0xff, 0xff, 0xff, 0xfe, 0x3f, 0xc0, 0x00, 0x00]
+        );
+        let le: [u8; 10] = hlist.to_le_bytes();
+        assert_eq!(
+            le,
+            [0x02, 0x01, 0xfe, 0xff, 0xff, 0xff, 0x00, 0x00, 0xc0, 0x3f]
+        );
+
+        type Decoded = HCons<u16, HCons<i32, HCons<f32, HNil>>>;
+        assert_eq!(Decoded::from_be_bytes(&be), hlist);
+        assert_eq!(Decoded::from_le_bytes(&le), hlist);
+    }
+
+    #[test]
+    fn struct_should_round_trip_through_bytes() {
+        let header = PacketHeader {
+            version: 1,
+            flags: 0x80,
+            length: 512,
+            sequence: 7,
+        };
+        let bytes: [u8; 8] = header.to_hlist().to_be_bytes();
+        assert_eq!(bytes, [1, 0x80, 0x02, 0x00, 0, 0, 0, 7]);
+
+        let decoded = PacketHeader::from_hlist(PacketHeaderHList::from_be_bytes(&bytes));
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn nested_hlists_should_be_encoded_inline() {
+        let hlist = hlist!(1u8, hlist!(2u8, 3u16));
+        let bytes: [u8; 4] = hlist.to_le_bytes();
+        assert_eq!(bytes, [1, 2, 3, 0]);
+    }
+}
@@ -0,0 +1,140 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::plucker::*;
+use crate::sculptor::*;
+use crate::selector::*;
+
+/// A set of capability values (typically zero-sized permission markers) stored in an
+/// `HList`.
+///
+/// Code that needs a permission takes a `Capabilities` and calls `require`, which only
+/// compiles if the capability is present in the set.  Capabilities can be added with
+/// `grant`, removed with `revoke`, and the set can be narrowed to a subset before it is
+/// passed to less-trusted code, so the permissions available to each component are
+/// visible in (and enforced by) its signature.  Each capability type should appear at
+/// most once; requiring a type that appears more than once is ambiguous and fails to
+/// compile.
+///
+/// ```
+/// # use pl_hlist::*;
+/// struct ReadFiles;
+/// struct WriteFiles;
+/// struct Network;
+///
+/// fn load<L, I>(caps: &Capabilities<L>) -> &'static str
+/// where
+///     L: HList + Selector<ReadFiles, I>,
+/// {
+///     let _: &ReadFiles = caps.require();
+///     "contents"
+/// }
+///
+/// let caps = Capabilities::new().grant(ReadFiles).grant(WriteFiles).grant(Network);
+/// let sandboxed: Capabilities<HCons<ReadFiles, HNil>> = caps.narrow();
+/// assert_eq!(load(&sandboxed), "contents");
+/// ```
+///
+/// Requiring a capability that is not in the set does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// struct ReadFiles;
+/// struct WriteFiles;
+///
+/// let caps = Capabilities::new().grant(ReadFiles);
+/// let _: &WriteFiles = caps.require();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Capabilities<L: HList> {
+    capabilities: L,
+}
+
+impl Capabilities<HNil> {
+    /// Creates an empty capability set.
+    pub fn new() -> Self {
+        Capabilities { capabilities: HNil }
+    }
+}
+
+impl<L: HList> Capabilities<L> {
+    /// Creates a capability set containing the given capabilities.
+    pub fn from_hlist(capabilities: L) -> Self {
+        Capabilities { capabilities }
+    }
+
+    /// Adds the given capability, returning the extended set.
+    pub fn grant<C>(self, capability: C) -> Capabilities<HCons<C, L>> {
+        Capabilities::from_hlist(HCons(capability, self.capabilities))
+    }
+
+    /// Returns the capability of type `C`.  This only compiles if the set contains it.
+    pub fn require<C, Index>(&self) -> &C
+    where
+        L: Selector<C, Index>,
+    {
+        self.capabilities.get()
+    }
+
+    /// Removes the capability of type `C`, returning it along with the reduced set.
+    pub fn revoke<C, Index>(self) -> (C, Capabilities<L::Remainder>)
+    where
+        L: Plucker<C, Index>,
+    {
+        let (capability, rest) = self.capabilities.pluck();
+        (capability, Capabilities::from_hlist(rest))
+    }
+
+    /// Narrows this set to the capabilities in `Subset`, which must all be present, and
+    /// discards the others.
+    pub fn narrow<Subset, Indices>(self) -> Capabilities<Subset>
+    where
+        Subset: HList,
+        L: Sculptor<Subset, Indices>,
+    {
+        Capabilities::from_hlist(self.capabilities.sculpt().0)
+    }
+
+    /// Returns the capabilities in this set.
+    pub fn as_hlist(&self) -> &L {
+        &self.capabilities
+    }
+
+    /// Consumes this set, returning its capabilities.
+    pub fn into_hlist(self) -> L {
+        self.capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Admin;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Quota(u32);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Audit;
+
+    #[test]
+    fn capabilities_should_be_granted_revoked_and_narrowed() {
+        let caps = Capabilities::new()
+            .grant(Quota(10))
+            .grant(Admin)
+            .grant(Audit);
+        assert_eq!(caps.require::<Quota, _>(), &Quota(10));
+
+        let (admin, caps) = caps.revoke::<Admin, _>();
+        assert_eq!(admin, Admin);
+        assert_eq!(caps.as_hlist(), &hlist!(Audit, Quota(10)));
+
+        let narrowed: Capabilities<HCons<Quota, HNil>> = caps.narrow();
+        assert_eq!(narrowed.into_hlist(), hlist!(Quota(10)));
+    }
+}
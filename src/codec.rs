@@ -0,0 +1,495 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+
+/// A destination for the bytes written by `HListEncode`.  This is implemented for `Vec<u8>`
+/// (with the `alloc` feature), and can be implemented for fixed buffers and other
+/// writers.
+pub trait ByteSink {
+    /// Appends the given bytes.
+    fn put_bytes(&mut self, bytes: &[u8]);
+}
+
+#[cfg(feature = "alloc")]
+impl ByteSink for Vec<u8> {
+    fn put_bytes(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes);
+    }
+}
+
+/// Error returned when a value cannot be decoded by `HListDecode`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DecodeError {
+    /// The input ended before the value was complete.
+    UnexpectedEnd,
+    /// A `bool` was encoded as a byte other than 0 or 1.
+    InvalidBool(u8),
+    /// A `char` was encoded as a value that is not a Unicode scalar value.
+    InvalidChar(u32),
+    /// An `Option` was encoded with a tag byte other than 0 or 1.
+    InvalidTag(u8),
+    /// A string was not valid UTF-8.
+    InvalidUtf8,
+    /// A `usize` or `isize` was encoded as a value that does not fit in the target's
+    /// pointer width.
+    OutOfRange(i128),
+    /// Bytes remained after decoding a complete value with `decode_from_slice`.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("unexpected end of input"),
+            DecodeError::InvalidBool(b) => write!(f, "invalid bool byte {:#04x}", b),
+            DecodeError::InvalidChar(c) => write!(f, "invalid char value {:#x}", c),
+            DecodeError::InvalidTag(t) => write!(f, "invalid option tag {:#04x}", t),
+            DecodeError::InvalidUtf8 => f.write_str("invalid UTF-8 in string"),
+            DecodeError::OutOfRange(n) => write!(f, "integer {} out of range for this target", n),
+            DecodeError::TrailingBytes(n) => write!(f, "{} trailing bytes after value", n),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+/// A value that can be written as a sequence of bytes.
+///
+/// Unlike `FixedBytes`, the encoding may vary in length: integers and floating point
+/// numbers are written in little-endian order (with `usize` and `isize` widened to 64
+/// bits), `bool`s as a single byte, `Option`s as a tag byte followed by the value,
+/// and strings and vectors as a 32-bit length followed by their contents.  `HList`s are
+/// encoded as their elements, in order, with nothing in between.  Encoding never fails,
+/// except that strings and vectors longer than `u32::MAX` cannot be encoded (see the
+/// `# Panics` sections of their impls).
+///
+/// `#[derive(HListEncode, HListDecode)]` (alongside `HListSupport`) implements the
+/// encoding for a struct as that of its `HList` representation, so structs can be nested
+/// within each other:
+///
+/// ```
+/// # use pl_hlist::*;
+/// # #[cfg(feature = "alloc")] {
+/// #[derive(Clone, Debug, PartialEq, HListSupport, HListEncode, HListDecode)]
+/// struct Point {
+///     x: i16,
+///     y: i16,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, HListSupport, HListEncode, HListDecode)]
+/// struct Shape {
+///     name: String,
+///     points: Vec<Point>,
+/// }
+///
+/// let shape = Shape { name: String::from("line"), points: vec![Point { x: 1, y: 2 }, Point { x: 3, y: 4 }] };
+/// let bytes = shape.encode_to_vec();
+/// assert_eq!(bytes.len(), 4 + 4 + 4 + 2 * 4);
+/// assert_eq!(Shape::decode_from_slice(&bytes), Ok(shape));
+/// # }
+/// ```
+pub trait HListEncode {
+    /// Writes the encoded representation of this value to `out`.
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W);
+
+    /// Returns the encoded representation of this value.
+    #[cfg(feature = "alloc")]
+    fn encode_to_vec(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+}
+
+/// A value that can be read from the byte representation written by `HListEncode`.
+pub trait HListDecode: Sized {
+    /// Reads a value from the front of `input`, advancing it past the bytes that were
+    /// read.
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError>;
+
+    /// Reads a value from `bytes`, which must contain exactly one encoded value.
+    fn decode_from_slice(mut bytes: &[u8]) -> Result<Self, DecodeError> {
+        let value = Self::decode(&mut bytes)?;
+        match bytes.len() {
+            0 => Ok(value),
+            n => Err(DecodeError::TrailingBytes(n)),
+        }
+    }
+}
+
+/// Splits the first `n` bytes off of the front of `input`.
+fn take_bytes<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8], DecodeError> {
+    if input.len() < n {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (bytes, rest) = input.split_at(n);
+    *input = rest;
+    Ok(bytes)
+}
+
+/// Writes the 32-bit length prefix of a string or vector, panicking if `len` exceeds
+/// `u32::MAX`.
+fn encode_len<W: ByteSink + ?Sized>(len: usize, out: &mut W) {
+    let len: u32 = len.try_into().expect("length exceeds u32::MAX");
+    len.encode(out);
+}
+
+macro_rules! impl_codec_for_numbers {
+    ($($t:ty),*) => {
+        $(
+            impl HListEncode for $t {
+                fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+                    out.put_bytes(&self.to_le_bytes());
+                }
+            }
+
+            impl HListDecode for $t {
+                fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+                    let bytes = take_bytes(input, core::mem::size_of::<$t>())?;
+                    Ok(<$t>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_for_numbers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl HListEncode for usize {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        (*self as u64).encode(out);
+    }
+}
+
+impl HListDecode for usize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let n = u64::decode(input)?;
+        usize::try_from(n).map_err(|_| DecodeError::OutOfRange(n.into()))
+    }
+}
+
+impl HListEncode for isize {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        (*self as i64).encode(out);
+    }
+}
+
+impl HListDecode for isize {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let n = i64::decode(input)?;
+        isize::try_from(n).map_err(|_| DecodeError::OutOfRange(n.into()))
+    }
+}
+
+impl HListEncode for bool {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        (*self as u8).encode(out);
+    }
+}
+
+impl HListDecode for bool {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(DecodeError::InvalidBool(b)),
+        }
+    }
+}
+
+impl HListEncode for char {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        (*self as u32).encode(out);
+    }
+}
+
+impl HListDecode for char {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let c = u32::decode(input)?;
+        char::from_u32(c).ok_or(DecodeError::InvalidChar(c))
+    }
+}
+
+impl<T: HListEncode> HListEncode for Option<T> {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        match self {
+            None => 0u8.encode(out),
+            Some(value) => {
+                1u8.encode(out);
+                value.encode(out);
+            }
+        }
+    }
+}
+
+impl<T: HListDecode> HListDecode for Option<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        match u8::decode(input)? {
+            0 => Ok(None),
+            1 => T::decode(input).map(Some),
+            t => Err(DecodeError::InvalidTag(t)),
+        }
+    }
+}
+
+/// Encodes the elements of the array, with no length prefix.
+impl<T: HListEncode, const N: usize> HListEncode for [T; N] {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        self.iter().for_each(|element| element.encode(out));
+    }
+}
+
+impl<T: HListDecode, const N: usize> HListDecode for [T; N] {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut error = None;
+        let elements: [Option<T>; N] = core::array::from_fn(|_| match error {
+            Some(_) => None,
+            None => T::decode(input).map_err(|e| error = Some(e)).ok(),
+        });
+        match error {
+            Some(error) => Err(error),
+            None => Ok(elements.map(|element| element.unwrap())),
+        }
+    }
+}
+
+/// Encodes the length of the slice followed by its elements.
+///
+/// # Panics
+///
+/// Panics if the length exceeds `u32::MAX`, which does not fit in the length prefix.
+impl<T: HListEncode> HListEncode for [T] {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        encode_len(self.len(), out);
+        self.iter().for_each(|element| element.encode(out));
+    }
+}
+
+/// Encodes the length of the string followed by its UTF-8 bytes.
+///
+/// # Panics
+///
+/// Panics if the length exceeds `u32::MAX`, which does not fit in the length prefix.
+impl HListEncode for str {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        encode_len(self.len(), out);
+        out.put_bytes(self.as_bytes());
+    }
+}
+
+impl<T: HListEncode + ?Sized> HListEncode for &T {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        (**self).encode(out);
+    }
+}
+
+/// Encodes the string as a `str`.
+///
+/// # Panics
+///
+/// Panics if the length exceeds `u32::MAX`, which does not fit in the length prefix.
+#[cfg(feature = "alloc")]
+impl HListEncode for String {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        self.as_str().encode(out);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl HListDecode for String {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = u32::decode(input)? as usize;
+        let bytes = take_bytes(input, len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+/// Encodes the vector as a slice.
+///
+/// # Panics
+///
+/// Panics if the length exceeds `u32::MAX`, which does not fit in the length prefix.
+#[cfg(feature = "alloc")]
+impl<T: HListEncode> HListEncode for Vec<T> {
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        self.as_slice().encode(out);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: HListDecode> HListDecode for Vec<T> {
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let len = u32::decode(input)? as usize;
+        // Don't trust the length for the allocation, since each element takes at least
+        // one byte (except for zero-sized elements, which are rare in wire formats)
+        let mut elements = Vec::with_capacity(len.min(input.len()));
+        for _ in 0..len {
+            elements.push(T::decode(input)?);
+        }
+        Ok(elements)
+    }
+}
+
+impl HListEncode for HNil {
+    fn encode<W: ByteSink + ?Sized>(&self, _: &mut W) {}
+}
+
+impl HListDecode for HNil {
+    fn decode(_: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(HNil)
+    }
+}
+
+impl<H, T> HListEncode for HCons<H, T>
+where
+    H: HListEncode,
+    T: HList + HListEncode,
+{
+    fn encode<W: ByteSink + ?Sized>(&self, out: &mut W) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+}
+
+impl<H, T> HListDecode for HCons<H, T>
+where
+    H: HListDecode,
+    T: HList + HListDecode,
+{
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let head = H::decode(input)?;
+        Ok(HCons(head, T::decode(input)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::{HListDecode, HListEncode, HListSupport};
+
+    #[derive(Clone, Debug, PartialEq, HListSupport, HListEncode, HListDecode)]
+    struct Header {
+        version: u8,
+        flags: Option<u16>,
+    }
+
+    #[cfg(feature = "alloc")]
+    #[derive(Clone, Debug, PartialEq, HListSupport, HListEncode, HListDecode)]
+    struct Message {
+        header: Header,
+        body: String,
+        tags: Vec<char>,
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hlists_should_encode_elements_in_order() {
+        let hlist = hlist!(0x0102u16, true, -1i8, Some('a'), None::<u8>, "hi");
+        assert_eq!(
+            hlist.encode_to_vec(),
+            [0x02, 0x01, 1, 0xff, 1, 0x61, 0, 0, 0, 0, 2, 0, 0, 0, b'h', b'i']
+        );
+
+        type Decoded = HCons<u16, HCons<[u8; 2], HCons<usize, HNil>>>;
+        let decoded = hlist!(7u16, [1u8, 2], 3usize);
+        assert_eq!(
+            Decoded::decode_from_slice(&decoded.encode_to_vec()),
+            Ok(decoded)
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn structs_should_round_trip() {
+        let message = Message {
+            header: Header {
+                version: 1,
+                flags: Some(0x80),
+            },
+            body: String::from("hello"),
+            tags: vec!['x', 'y'],
+        };
+        let bytes = message.encode_to_vec();
+        assert_eq!(&bytes[..4], [1, 1, 0x80, 0]);
+        assert_eq!(Message::decode_from_slice(&bytes), Ok(message));
+    }
+
+    #[derive(Clone, Debug, PartialEq, HListSupport, HListEncode, HListDecode)]
+    struct Tagged<T, const N: usize>
+    where
+        T: Copy,
+    {
+        tag: T,
+        values: [T; N],
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn generic_structs_should_round_trip() {
+        let tagged = Tagged {
+            tag: 7u16,
+            values: [1, 2],
+        };
+        let bytes = tagged.encode_to_vec();
+        assert_eq!(bytes, [7, 0, 1, 0, 2, 0]);
+        assert_eq!(Tagged::decode_from_slice(&bytes), Ok(tagged));
+    }
+
+    #[test]
+    fn invalid_input_should_be_reported() {
+        assert_eq!(
+            u32::decode_from_slice(&[1, 2]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+        assert_eq!(
+            bool::decode_from_slice(&[2]),
+            Err(DecodeError::InvalidBool(2))
+        );
+        assert_eq!(
+            u8::decode_from_slice(&[1, 2]),
+            Err(DecodeError::TrailingBytes(1))
+        );
+        assert_eq!(
+            DecodeError::InvalidTag(3).to_string(),
+            "invalid option tag 0x03"
+        );
+        assert_eq!(
+            DecodeError::OutOfRange(-1).to_string(),
+            "integer -1 out of range for this target"
+        );
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn wide_sizes_should_be_reported_on_narrow_targets() {
+        assert_eq!(
+            usize::decode_from_slice(&[0, 0, 0, 0, 1, 0, 0, 0]),
+            Err(DecodeError::OutOfRange(1 << 32))
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn invalid_strings_and_lengths_should_be_reported() {
+        assert_eq!(
+            String::decode_from_slice(&[1, 0, 0, 0, 0xff]),
+            Err(DecodeError::InvalidUtf8)
+        );
+        assert_eq!(
+            Vec::<u8>::decode_from_slice(&[0xff, 0xff, 0xff, 0xff]),
+            Err(DecodeError::UnexpectedEnd)
+        );
+    }
+}
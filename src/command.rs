@@ -0,0 +1,223 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::selector::*;
+use core::marker::PhantomData;
+
+/// An undoable operation on a resource of type `S`.
+pub trait Command<S> {
+    /// Applies this command to the given resource.
+    fn execute(&mut self, resource: &mut S);
+
+    /// Reverts the effect of `execute` on the given resource.
+    fn undo(&mut self, resource: &mut S);
+}
+
+/// A command that has been executed against the resource of type `S`, found at `Index`
+/// in a command stack's resources.
+pub struct Recorded<C, S, Index> {
+    command: C,
+    resource: PhantomData<fn(&mut S) -> Index>,
+}
+
+impl<C, S, Index> Recorded<C, S, Index> {
+    /// Returns the recorded command.
+    pub fn command(&self) -> &C {
+        &self.command
+    }
+}
+
+/// A stack of executed commands over an `HList` of resources, which can be rolled back
+/// in reverse order.
+///
+/// Each command operates on the resource of the type it implements `Command` for, and
+/// the type of the stack records every command that has been executed, so undoing
+/// always applies the right command to the right resource.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Push(char);
+/// struct Add(i32);
+///
+/// impl Command<String> for Push {
+///     fn execute(&mut self, s: &mut String) {
+///         s.push(self.0);
+///     }
+///     fn undo(&mut self, s: &mut String) {
+///         s.pop();
+///     }
+/// }
+///
+/// impl Command<i32> for Add {
+///     fn execute(&mut self, n: &mut i32) {
+///         *n += self.0;
+///     }
+///     fn undo(&mut self, n: &mut i32) {
+///         *n -= self.0;
+///     }
+/// }
+///
+/// let stack = CommandStack::new(hlist!(String::new(), 0i32))
+///     .execute(Push('a'))
+///     .execute(Add(5))
+///     .execute(Push('b'));
+/// assert_eq!(stack.resources(), &hlist!(String::from("ab"), 5));
+///
+/// let (_, stack) = stack.undo();
+/// assert_eq!(stack.resources(), &hlist!(String::from("a"), 5));
+/// assert_eq!(stack.undo_all(), hlist!(String::new(), 0));
+/// # }
+/// ```
+pub struct CommandStack<Resources, History> {
+    resources: Resources,
+    history: History,
+}
+
+impl<Resources: HList> CommandStack<Resources, HNil> {
+    /// Creates an empty command stack over the given resources.
+    pub fn new(resources: Resources) -> Self {
+        CommandStack {
+            resources,
+            history: HNil,
+        }
+    }
+}
+
+impl<Resources: HList, History: HList> CommandStack<Resources, History> {
+    /// Executes the given command against the resource of type `S`, recording it so that
+    /// it can be undone.
+    pub fn execute<C, S, Index>(
+        mut self,
+        mut command: C,
+    ) -> CommandStack<Resources, HCons<Recorded<C, S, Index>, History>>
+    where
+        C: Command<S>,
+        Resources: Selector<S, Index>,
+    {
+        command.execute(self.resources.get_mut());
+        let recorded = Recorded {
+            command,
+            resource: PhantomData,
+        };
+        CommandStack {
+            resources: self.resources,
+            history: HCons(recorded, self.history),
+        }
+    }
+
+    /// Undoes every recorded command, most recent first, returning the resources.
+    pub fn undo_all(mut self) -> Resources
+    where
+        History: UndoAll<Resources>,
+    {
+        self.history.undo_all(&mut self.resources);
+        self.resources
+    }
+
+    /// Discards the recorded commands, returning the resources in their current state.
+    pub fn commit(self) -> Resources {
+        self.resources
+    }
+
+    /// Returns the resources in their current state.
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    /// Returns the recorded commands, most recent first.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+}
+
+impl<Resources, C, S, Index, Rest> CommandStack<Resources, HCons<Recorded<C, S, Index>, Rest>>
+where
+    Resources: HList + Selector<S, Index>,
+    C: Command<S>,
+    Rest: HList,
+{
+    /// Undoes the most recent command, returning it along with the remaining stack.
+    pub fn undo(mut self) -> (C, CommandStack<Resources, Rest>) {
+        let HCons(mut recorded, rest) = self.history;
+        recorded.command.undo(self.resources.get_mut());
+        let stack = CommandStack {
+            resources: self.resources,
+            history: rest,
+        };
+        (recorded.command, stack)
+    }
+}
+
+/// Allows for undoing each recorded command in an `HList` against the given resources,
+/// in order from the head of the list.
+pub trait UndoAll<Resources> {
+    /// Undoes each recorded command.
+    fn undo_all(self, resources: &mut Resources);
+}
+
+impl<Resources> UndoAll<Resources> for HNil {
+    fn undo_all(self, _resources: &mut Resources) {}
+}
+
+impl<Resources, C, S, Index, Rest> UndoAll<Resources> for HCons<Recorded<C, S, Index>, Rest>
+where
+    Resources: Selector<S, Index>,
+    C: Command<S>,
+    Rest: HList + UndoAll<Resources>,
+{
+    fn undo_all(mut self, resources: &mut Resources) {
+        self.0.command.undo(resources.get_mut());
+        self.1.undo_all(resources)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Append(&'static str);
+
+    impl Command<Vec<&'static str>> for Append {
+        fn execute(&mut self, log: &mut Vec<&'static str>) {
+            log.push(self.0);
+        }
+
+        fn undo(&mut self, log: &mut Vec<&'static str>) {
+            assert_eq!(log.pop(), Some(self.0));
+        }
+    }
+
+    struct Scale(u32);
+
+    impl Command<u32> for Scale {
+        fn execute(&mut self, value: &mut u32) {
+            *value *= self.0;
+        }
+
+        fn undo(&mut self, value: &mut u32) {
+            *value /= self.0;
+        }
+    }
+
+    #[test]
+    fn undo_all_should_roll_back_in_reverse_order() {
+        let stack = CommandStack::new(hlist!(3u32, Vec::new()))
+            .execute(Append("a"))
+            .execute(Scale(2))
+            .execute(Append("b"))
+            .execute(Scale(5));
+        assert_eq!(stack.resources(), &hlist!(30u32, vec!["a", "b"]));
+        assert_eq!(stack.history().head().command().0, 5);
+
+        let (scale, stack) = stack.undo();
+        assert_eq!(scale.0, 5);
+        assert_eq!(stack.resources(), &hlist!(6u32, vec!["a", "b"]));
+
+        assert_eq!(stack.undo_all(), hlist!(3u32, Vec::new()));
+    }
+}
@@ -0,0 +1,186 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Inherent `const fn` versions of the core structural operations, so that `HList`s can be
+//! assembled in const contexts.  Trait methods cannot be `const`, so `const_append`,
+//! `const_reverse` and `from_array` are provided for `HList`s of up to 16 elements, and
+//! `cons` for all `HList`s.  They behave identically to their trait counterparts (which
+//! remain available for generic code), and are named differently so that method calls
+//! such as `list.append(x)` always resolve to the trait methods.
+//!
+//! Moving elements out of a generic value is not yet permitted in const contexts, so the
+//! elements are instead read out of a `ManuallyDrop` copy of the value.
+
+use crate::hlist::*;
+use core::mem::ManuallyDrop;
+use core::ptr;
+
+/// Implements the const operations for the `HList` with the given elements, and then for
+/// each shorter `HList` formed by dropping elements from the front.
+macro_rules! impl_const_ops {
+    () => {};
+    ($H1:ident $h1:ident $(, $H:ident $h:ident)*) => {
+        impl<$H1, $($H),*> hlist_type!($H1 $(, $H)*) {
+            /// Appends `rhs` to the end of this list.  Unlike `Append::append`, this can be
+            /// used in const contexts.
+            #[inline(always)]
+            pub const fn const_append<RHS: HList>(self, rhs: RHS) -> hlist_type!($H1 $(, $H)*; RHS) {
+                let this = ManuallyDrop::new(self);
+                // SAFETY: `this` is never dropped, and each element is read exactly once.
+                unsafe {
+                    let hlist_pattern!($h1 $(, $h)*) = &*(&this as *const ManuallyDrop<Self> as *const Self);
+                    hlist_with_tail!(ptr::read($h1) $(, ptr::read($h))*; rhs)
+                }
+            }
+        }
+
+        impl_const_reverse!(@reverse [$H1 $h1 $($H $h)*] [] $H1 $h1 $(, $H $h)*);
+
+        impl<T> hlist_type!(T $(, same_type!($H, T))*) {
+            /// Creates an `HList` from an array of elements, in order.
+            #[inline(always)]
+            pub const fn from_array(array: [T; count!($h1 $($h)*)]) -> Self {
+                let array = ManuallyDrop::new(array);
+                // SAFETY: `array` is never dropped, and each element is read exactly once.
+                unsafe {
+                    let [$h1, $($h),*] = &*(&array as *const ManuallyDrop<[T; count!($h1 $($h)*)]> as *const [T; count!($h1 $($h)*)]);
+                    hlist!(ptr::read($h1) $(, ptr::read($h))*)
+                }
+            }
+        }
+
+        impl<T> From<[T; count!($h1 $($h)*)]> for hlist_type!(T $(, same_type!($H, T))*) {
+            fn from(array: [T; count!($h1 $($h)*)]) -> Self {
+                Self::from_array(array)
+            }
+        }
+
+        impl_const_ops!($($H $h),*);
+    };
+}
+
+/// Implements `const_reverse` by accumulating the given elements in reverse order.
+macro_rules! impl_const_reverse {
+    (@reverse [$($H:ident $h:ident)*] [$($R:ident $r:ident)*]) => {
+        impl<$($H),*> hlist_type!($($H),*) {
+            /// Returns this list with its elements in reverse order.  This can be used in
+            /// const contexts.
+            #[inline(always)]
+            pub const fn const_reverse(self) -> hlist_type!($($R),*) {
+                let this = ManuallyDrop::new(self);
+                // SAFETY: `this` is never dropped, and each element is read exactly once.
+                unsafe {
+                    let hlist_pattern!($($h),*) = &*(&this as *const ManuallyDrop<Self> as *const Self);
+                    hlist!($(ptr::read($r)),*)
+                }
+            }
+        }
+    };
+    (@reverse [$($H:ident $h:ident)*] [$($R:ident $r:ident)*] $N:ident $n:ident $(, $T:ident $t:ident)*) => {
+        impl_const_reverse!(@reverse [$($H $h)*] [$N $n $($R $r)*] $($T $t),*);
+    };
+}
+
+impl HNil {
+    /// Creates a new `HCons` with the given `X` value in head position.  Unlike
+    /// `HList::cons`, this can be used in const contexts.
+    #[inline(always)]
+    pub const fn cons<X>(self, x: X) -> HCons<X, HNil> {
+        HCons(x, self)
+    }
+
+    /// Appends `rhs` to the end of this (empty) list.  Unlike `Append::append`, this can
+    /// be used in const contexts.
+    #[inline(always)]
+    pub const fn const_append<RHS: HList>(self, rhs: RHS) -> RHS {
+        rhs
+    }
+
+    /// Returns this (empty) list.
+    #[inline(always)]
+    pub const fn const_reverse(self) -> HNil {
+        self
+    }
+
+    /// Creates an empty `HList` from an empty array.
+    #[inline(always)]
+    pub const fn from_array<T>(_array: [T; 0]) -> Self {
+        HNil
+    }
+}
+
+impl<H, T: HList> HCons<H, T> {
+    /// Creates a new `HCons` with the given `X` value in head position.  Unlike
+    /// `HList::cons`, this can be used in const contexts.
+    #[inline(always)]
+    pub const fn cons<X>(self, x: X) -> HCons<X, Self> {
+        HCons(x, self)
+    }
+}
+
+impl<T> From<[T; 0]> for HNil {
+    fn from(_array: [T; 0]) -> Self {
+        HNil
+    }
+}
+
+impl_const_ops!(
+    A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8,
+    A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14, A15 a15, A16 a16
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: HCons<u8, HCons<&str, HNil>> = hlist!(1u8, "header");
+    const BODY: HCons<u16, HCons<char, HNil>> = hlist!(2u16, 'b');
+    type Table = HCons<u8, HCons<&'static str, HCons<u16, HCons<char, HNil>>>>;
+    type Reversed = HCons<char, HCons<u16, HCons<&'static str, HCons<u8, HNil>>>>;
+    const TABLE: Table = HEADER.const_append(BODY);
+    const REVERSED: Reversed = TABLE.const_reverse();
+    type Triple = HCons<u32, HCons<u32, HCons<u32, HNil>>>;
+    const FROM_ARRAY: Triple = Triple::from_array([1, 2, 3]);
+
+    // A static lookup table of heterogeneous config values, built without lazy
+    // initialization
+    static LIMITS: HCons<&str, HCons<u32, HCons<bool, HNil>>> =
+        HNil.cons(true).cons(64).cons("limits");
+    const MAX_CONNECTIONS: u32 = *LIMITS_CONST.tail().head();
+    const LIMITS_CONST: HCons<&str, HCons<u32, HCons<bool, HNil>>> = hlist!("limits", 64, true);
+    const NAME: &str = LIMITS_CONST.head();
+
+    #[test]
+    fn cons_head_and_tail_should_work_in_const_contexts() {
+        assert_eq!(LIMITS, LIMITS_CONST);
+        assert_eq!(MAX_CONNECTIONS, 64);
+        assert_eq!(NAME, "limits");
+        assert!(*LIMITS.tail().tail().head());
+    }
+
+    #[test]
+    fn append_should_work_in_const_contexts() {
+        assert_eq!(TABLE, hlist!(1u8, "header", 2u16, 'b'));
+        assert_eq!(HNil.const_append(BODY), BODY);
+        assert_eq!(TABLE, HEADER.append(BODY));
+    }
+
+    #[test]
+    fn reverse_should_work_in_const_contexts() {
+        assert_eq!(REVERSED, hlist!('b', 2u16, "header", 1u8));
+        assert_eq!(HNil.const_reverse(), HNil);
+        assert_eq!(hlist!(1u8).const_reverse(), hlist!(1u8));
+    }
+
+    #[test]
+    fn from_array_should_work_in_const_contexts() {
+        assert_eq!(FROM_ARRAY, hlist!(1u32, 2u32, 3u32));
+
+        let hlist: HCons<String, HCons<String, HNil>> =
+            [String::from("a"), String::from("b")].into();
+        assert_eq!(hlist, hlist!(String::from("a"), String::from("b")));
+        assert_eq!(HNil::from_array::<u8>([]), HNil);
+    }
+}
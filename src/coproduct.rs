@@ -0,0 +1,381 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+
+/// An empty coproduct used as the terminal variant.  `CNil` has no values, so a
+/// coproduct can never hold it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum CNil {}
+
+/// A heterogeneous sum type holding either a value of the head type `H` (`Inl`) or a
+/// value of one of the types in the tail coproduct `T` (`Inr`).  This is the dual of
+/// an `HList`: where an `HList` holds a value of each of its element types, a
+/// coproduct holds a value of exactly one of them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Coproduct<H, T> {
+    /// A value of the head type.
+    Inl(H),
+    /// A value of one of the types in the tail.
+    Inr(T),
+}
+
+impl<H, T> Coproduct<H, T> {
+    /// Creates a coproduct holding the given value, which must be of one of the types in
+    /// this coproduct.
+    pub fn inject<X, Index>(x: X) -> Self
+    where
+        Self: CoprodInjector<X, Index>,
+    {
+        CoprodInjector::inject(x)
+    }
+
+    /// Returns a reference to the value if it is of type `Target`.
+    pub fn get<Target, Index>(&self) -> Option<&Target>
+    where
+        Self: CoprodSelector<Target, Index>,
+    {
+        CoprodSelector::get(self)
+    }
+
+    /// Returns a mutable reference to the value if it is of type `Target`.
+    pub fn get_mut<Target, Index>(&mut self) -> Option<&mut Target>
+    where
+        Self: CoprodSelector<Target, Index>,
+    {
+        CoprodSelector::get_mut(self)
+    }
+
+    /// Returns the value if it is of type `Target`, discarding it otherwise.
+    pub fn take<Target, Index>(self) -> Option<Target>
+    where
+        Self: CoprodUninjector<Target, Index>,
+    {
+        CoprodUninjector::uninject(self).ok()
+    }
+
+    /// Returns the value if it is of type `Target`, or the coproduct of the remaining
+    /// types otherwise.
+    pub fn uninject<Target, Index>(
+        self,
+    ) -> Result<Target, <Self as CoprodUninjector<Target, Index>>::Remainder>
+    where
+        Self: CoprodUninjector<Target, Index>,
+    {
+        CoprodUninjector::uninject(self)
+    }
+
+    /// Narrows this coproduct to `Targets` (a coproduct of some of its types, in any
+    /// order) if its value is of one of those types, or to the coproduct of the remaining
+    /// types otherwise.
+    ///
+    /// ```
+    /// # use pl_hlist::*;
+    /// type Value = Coproduct<u8, Coproduct<&'static str, Coproduct<f32, CNil>>>;
+    /// type Number = Coproduct<f32, Coproduct<u8, CNil>>;
+    ///
+    /// let number: Result<Number, _> = Value::inject(1.5f32).subset();
+    /// assert_eq!(number.unwrap().take::<f32, _>(), Some(1.5));
+    ///
+    /// let text: Result<Number, Coproduct<&str, CNil>> = Value::inject("two").subset();
+    /// assert_eq!(text, Err(Coproduct::Inl("two")));
+    /// ```
+    pub fn subset<Targets, Indices>(
+        self,
+    ) -> Result<Targets, <Self as CoproductSubsetter<Targets, Indices>>::Remainder>
+    where
+        Self: CoproductSubsetter<Targets, Indices>,
+    {
+        CoproductSubsetter::subset(self)
+    }
+}
+
+/// Allows for conversion from a coproduct to an instance of the `Self` type.
+///
+/// The `CoproductSupport` derive implements this for enums, along with `ToCoproduct` and
+/// `IntoCoproduct`.  The coproduct has one variant per enum variant, in declaration
+/// order, each holding an `HList` of the variant's fields (which is `HNil` for a unit
+/// variant).
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(Debug, PartialEq, CoproductSupport)]
+/// enum Shape {
+///     Circle { radius: f32 },
+///     Rect(f32, f32),
+///     Empty,
+/// }
+///
+/// type ShapeCoproduct = Coproduct<
+///     HCons<f32, HNil>,
+///     Coproduct<HCons<f32, HCons<f32, HNil>>, Coproduct<HNil, CNil>>,
+/// >;
+///
+/// let coproduct: ShapeCoproduct = Shape::Rect(2.0, 3.0).into_coproduct();
+/// assert_eq!(coproduct.get(), Some(&hlist!(2.0f32, 3.0f32)));
+/// assert_eq!(Shape::from_coproduct(coproduct), Shape::Rect(2.0, 3.0));
+/// # }
+/// ```
+pub trait FromCoproduct<C> {
+    fn from_coproduct(coproduct: C) -> Self;
+}
+
+/// Allows for copying the contents of `Self` into a coproduct.
+pub trait ToCoproduct<C> {
+    fn to_coproduct(&self) -> C;
+}
+
+/// Allows for converting (and consuming) `Self` into a coproduct.
+pub trait IntoCoproduct<C> {
+    fn into_coproduct(self) -> C;
+}
+
+/// Allows for creating a coproduct holding a value of type `Target`.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// `Target` within the coproduct.
+pub trait CoprodInjector<Target, Index> {
+    /// Creates a coproduct holding the given value.
+    fn inject(target: Target) -> Self;
+}
+
+impl<T, Tail> CoprodInjector<T, Here> for Coproduct<T, Tail> {
+    #[inline(always)]
+    fn inject(target: T) -> Self {
+        Coproduct::Inl(target)
+    }
+}
+
+impl<H, Tail, T, TailIndex> CoprodInjector<T, There<TailIndex>> for Coproduct<H, Tail>
+where
+    Tail: CoprodInjector<T, TailIndex>,
+{
+    #[inline(always)]
+    fn inject(target: T) -> Self {
+        Coproduct::Inr(Tail::inject(target))
+    }
+}
+
+/// Allows for borrowing the value of a coproduct if it is of type `Target`.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// `Target` within the coproduct.
+pub trait CoprodSelector<Target, Index> {
+    /// Returns a reference to the value if it is of type `Target`.
+    fn get(&self) -> Option<&Target>;
+
+    /// Returns a mutable reference to the value if it is of type `Target`.
+    fn get_mut(&mut self) -> Option<&mut Target>;
+}
+
+impl<T, Tail> CoprodSelector<T, Here> for Coproduct<T, Tail> {
+    #[inline(always)]
+    fn get(&self) -> Option<&T> {
+        match self {
+            Coproduct::Inl(target) => Some(target),
+            Coproduct::Inr(_) => None,
+        }
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Coproduct::Inl(target) => Some(target),
+            Coproduct::Inr(_) => None,
+        }
+    }
+}
+
+impl<H, Tail, T, TailIndex> CoprodSelector<T, There<TailIndex>> for Coproduct<H, Tail>
+where
+    Tail: CoprodSelector<T, TailIndex>,
+{
+    #[inline(always)]
+    fn get(&self) -> Option<&T> {
+        match self {
+            Coproduct::Inl(_) => None,
+            Coproduct::Inr(tail) => tail.get(),
+        }
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Coproduct::Inl(_) => None,
+            Coproduct::Inr(tail) => tail.get_mut(),
+        }
+    }
+}
+
+/// Allows for extracting the value of a coproduct if it is of type `Target`, or
+/// otherwise narrowing the coproduct to its remaining types.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// `Target` within the coproduct.
+pub trait CoprodUninjector<Target, Index> {
+    /// The coproduct of the types other than `Target`.
+    type Remainder;
+
+    /// Returns the value if it is of type `Target`, or the narrowed coproduct otherwise.
+    fn uninject(self) -> Result<Target, Self::Remainder>;
+}
+
+impl<T, Tail> CoprodUninjector<T, Here> for Coproduct<T, Tail> {
+    type Remainder = Tail;
+
+    #[inline(always)]
+    fn uninject(self) -> Result<T, Tail> {
+        match self {
+            Coproduct::Inl(target) => Ok(target),
+            Coproduct::Inr(tail) => Err(tail),
+        }
+    }
+}
+
+impl<H, Tail, T, TailIndex> CoprodUninjector<T, There<TailIndex>> for Coproduct<H, Tail>
+where
+    Tail: CoprodUninjector<T, TailIndex>,
+{
+    type Remainder = Coproduct<H, Tail::Remainder>;
+
+    #[inline(always)]
+    fn uninject(self) -> Result<T, Self::Remainder> {
+        match self {
+            Coproduct::Inl(head) => Err(Coproduct::Inl(head)),
+            Coproduct::Inr(tail) => tail.uninject().map_err(Coproduct::Inr),
+        }
+    }
+}
+
+/// Allows for narrowing a coproduct to `Targets`, a coproduct of some of its types in
+/// any order.
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position of
+/// each type of `Targets` within the coproduct.
+pub trait CoproductSubsetter<Targets, Indices> {
+    /// The coproduct of the types that are not in `Targets`.
+    type Remainder;
+
+    /// Returns the value as a `Targets` if it is of one of those types, or as the
+    /// coproduct of the remaining types otherwise.
+    fn subset(self) -> Result<Targets, Self::Remainder>;
+}
+
+impl<Source> CoproductSubsetter<CNil, HNil> for Source {
+    type Remainder = Source;
+
+    #[inline(always)]
+    fn subset(self) -> Result<CNil, Self::Remainder> {
+        Err(self)
+    }
+}
+
+impl<Source, TargetHead, TargetTail, IndexHead, IndexTail>
+    CoproductSubsetter<Coproduct<TargetHead, TargetTail>, HCons<IndexHead, IndexTail>> for Source
+where
+    Source: CoprodUninjector<TargetHead, IndexHead>,
+    Source::Remainder: CoproductSubsetter<TargetTail, IndexTail>,
+    IndexTail: HList,
+{
+    type Remainder = <Source::Remainder as CoproductSubsetter<TargetTail, IndexTail>>::Remainder;
+
+    #[inline(always)]
+    fn subset(self) -> Result<Coproduct<TargetHead, TargetTail>, Self::Remainder> {
+        match self.uninject() {
+            Ok(head) => Ok(Coproduct::Inl(head)),
+            Err(rest) => rest.subset().map(Coproduct::Inr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::CoproductSupport;
+
+    type Event = Coproduct<u8, Coproduct<&'static str, CNil>>;
+
+    #[test]
+    fn inject_should_select_the_variant_by_type() {
+        assert_eq!(Event::inject(1u8), Coproduct::Inl(1));
+        assert_eq!(Event::inject("two"), Coproduct::Inr(Coproduct::Inl("two")));
+    }
+
+    #[test]
+    fn get_and_uninject_should_match_the_variant_type() {
+        let mut event = Event::inject("two");
+        assert_eq!(CoprodSelector::<u8, _>::get(&event), None);
+        *event.get_mut().unwrap() = "three";
+        assert_eq!(event.get(), Some(&"three"));
+
+        let remainder: Result<u8, Coproduct<&str, CNil>> = event.uninject();
+        assert_eq!(remainder, Err(Coproduct::Inl("three")));
+        let value: Result<&str, _> = event.uninject();
+        assert_eq!(value, Ok("three"));
+        assert_eq!(event.take::<&str, _>(), Some("three"));
+        assert_eq!(event.take::<u8, _>(), None);
+    }
+
+    #[test]
+    fn subset_should_narrow_to_the_target_types() {
+        type Wide = Coproduct<u8, Coproduct<&'static str, Coproduct<char, CNil>>>;
+        type Narrow = Coproduct<char, Coproduct<u8, CNil>>;
+
+        let narrow: Result<Narrow, _> = Wide::inject('c').subset();
+        assert_eq!(narrow, Ok(Narrow::inject('c')));
+        let narrow: Result<Narrow, _> = Wide::inject(1u8).subset();
+        assert_eq!(narrow, Ok(Narrow::inject(1u8)));
+
+        let rest = Wide::inject("two").subset::<Narrow, _>().unwrap_err();
+        assert_eq!(rest.get::<&str, _>(), Some(&"two"));
+    }
+
+    #[derive(Clone, Debug, PartialEq, CoproductSupport)]
+    enum Message {
+        Quit,
+        Move { x: i32, y: i32 },
+        Write(String),
+    }
+
+    type MessageCoproduct = Coproduct<
+        HNil,
+        Coproduct<HCons<i32, HCons<i32, HNil>>, Coproduct<HCons<String, HNil>, CNil>>,
+    >;
+
+    #[derive(CoproductSupport)]
+    enum Never {}
+
+    #[derive(Debug, PartialEq, CoproductSupport)]
+    enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    #[test]
+    fn enums_should_convert_to_and_from_coproducts() {
+        let message = Message::Move { x: 1, y: -1 };
+        let coproduct: MessageCoproduct = message.to_coproduct();
+        assert_eq!(coproduct.get(), Some(&hlist!(1, -1)));
+        assert_eq!(Message::from_coproduct(coproduct), message);
+
+        let coproduct: MessageCoproduct = Message::Write(String::from("hi")).into_coproduct();
+        assert_eq!(coproduct.take(), Some(hlist!(String::from("hi"))));
+
+        let quit = MessageCoproduct::inject(HNil);
+        assert_eq!(Message::from_coproduct(quit), Message::Quit);
+
+        type EitherCoproduct =
+            Coproduct<HCons<u8, HNil>, Coproduct<HCons<&'static str, HNil>, CNil>>;
+        let either: EitherCoproduct = Either::<u8, &str>::Right("r").to_coproduct();
+        assert_eq!(Either::from_coproduct(either), Either::Right("r"));
+        assert_ne!(Either::Left(1u8), Either::Right("r"));
+
+        let never: Option<Never> = None;
+        assert!(never.map(|n| -> CNil { n.into_coproduct() }).is_none());
+    }
+}
@@ -0,0 +1,225 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::any::type_name;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// Formats an `HList` as a flat list of its elements, e.g. `hlist![1, 2, "three"]`,
+/// rather than as nested `HCons` cells.
+pub struct DebugShort<'a, L>(&'a L);
+
+impl<L: DebugElements> fmt::Debug for DebugShort<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("hlist!")?;
+        let mut list = f.debug_list();
+        self.0.debug_elements(&mut list);
+        list.finish()
+    }
+}
+
+/// Formats the type of an `HList` as a flat list of its element types with module paths
+/// removed, e.g. `HList![u8, i32, &str]`, rather than as nested `HCons` types.
+pub struct ShortTypeName<L>(PhantomData<L>);
+
+impl<L: DebugElements> fmt::Display for ShortTypeName<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("HList![")?;
+        L::write_type_names(f, true)?;
+        f.write_str("]")
+    }
+}
+
+impl<L: DebugElements> fmt::Debug for ShortTypeName<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Allows for formatting an `HList` and its type compactly.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, 2i32, "three", Some(String::from("four")));
+/// assert_eq!(
+///     format!("{:?}", hlist.debug_short()),
+///     "hlist![1, 2, \"three\", Some(\"four\")]"
+/// );
+/// assert_eq!(
+///     hlist.short_type_name().to_string(),
+///     "HList![u8, i32, &str, Option<String>]"
+/// );
+/// # }
+/// ```
+pub trait DebugElements: HList {
+    /// Adds each element of this list to the given `DebugList`.
+    fn debug_elements(&self, list: &mut fmt::DebugList);
+
+    /// Writes the name of each element type of this list, separated by commas.
+    fn write_type_names(f: &mut fmt::Formatter, first: bool) -> fmt::Result;
+
+    /// Returns a wrapper whose `Debug` output is a flat list of the elements.
+    fn debug_short(&self) -> DebugShort<'_, Self>
+    where
+        Self: Sized,
+    {
+        DebugShort(self)
+    }
+
+    /// Returns a wrapper whose `Display` output is a flat list of the element types.
+    fn short_type_name(&self) -> ShortTypeName<Self>
+    where
+        Self: Sized,
+    {
+        ShortTypeName(PhantomData)
+    }
+}
+
+impl DebugElements for HNil {
+    fn debug_elements(&self, _list: &mut fmt::DebugList) {}
+
+    fn write_type_names(_f: &mut fmt::Formatter, _first: bool) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<H, T> DebugElements for HCons<H, T>
+where
+    H: fmt::Debug,
+    T: DebugElements,
+{
+    fn debug_elements(&self, list: &mut fmt::DebugList) {
+        list.entry(&self.0);
+        self.1.debug_elements(list);
+    }
+
+    fn write_type_names(f: &mut fmt::Formatter, first: bool) -> fmt::Result {
+        if !first {
+            f.write_str(", ")?;
+        }
+        write_short_type_name(f, type_name::<H>())?;
+        T::write_type_names(f, false)
+    }
+}
+
+/// Formats an `HList` as a flat list of its elements using their `Display` impls, e.g.
+/// `hlist![1, 2, three]`.
+pub struct DisplayHList<'a, L>(&'a L);
+
+impl<L: DisplayElements> fmt::Display for DisplayHList<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("hlist![")?;
+        self.0.write_elements(f, true)?;
+        f.write_str("]")
+    }
+}
+
+/// Allows for formatting an `HList` whose elements implement `Display`.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, 2.5f32, "three");
+/// assert_eq!(hlist.display().to_string(), "hlist![1, 2.5, three]");
+/// # }
+/// ```
+pub trait DisplayElements: HList {
+    /// Writes each element of this list, separated by commas.
+    fn write_elements(&self, f: &mut fmt::Formatter, first: bool) -> fmt::Result;
+
+    /// Returns a wrapper whose `Display` output is a flat list of the elements.
+    fn display(&self) -> DisplayHList<'_, Self>
+    where
+        Self: Sized,
+    {
+        DisplayHList(self)
+    }
+}
+
+impl DisplayElements for HNil {
+    fn write_elements(&self, _f: &mut fmt::Formatter, _first: bool) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<H, T> DisplayElements for HCons<H, T>
+where
+    H: fmt::Display,
+    T: DisplayElements,
+{
+    fn write_elements(&self, f: &mut fmt::Formatter, first: bool) -> fmt::Result {
+        if !first {
+            f.write_str(", ")?;
+        }
+        fmt::Display::fmt(&self.0, f)?;
+        self.1.write_elements(f, false)
+    }
+}
+
+/// Writes the given type name with the module path of each type removed, so that e.g.
+/// `core::option::Option<alloc::string::String>` becomes `Option<String>`.
+fn write_short_type_name(f: &mut fmt::Formatter, name: &str) -> fmt::Result {
+    let mut segment_start = 0;
+    let mut chars = name.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == ':' && matches!(chars.peek(), Some((_, ':'))) {
+            // Drop the path segment (and the `::`) that precedes the type name
+            chars.next();
+            segment_start = i + 2;
+        } else if !(c.is_alphanumeric() || c == '_') {
+            f.write_str(&name[segment_start..=i])?;
+            segment_start = i + c.len_utf8();
+        }
+    }
+    f.write_str(&name[segment_start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debug_short_should_flatten_elements() {
+        assert_eq!(format!("{:?}", HNil.debug_short()), "hlist![]");
+        let hlist = hlist!(1u8, "two", hlist!(3.0f32));
+        assert_eq!(
+            format!("{:?}", hlist.debug_short()),
+            "hlist![1, \"two\", HCons(3.0, HNil)]"
+        );
+        assert_eq!(
+            format!("{:#?}", hlist!(1u8, 2u8).debug_short()),
+            "hlist![\n    1,\n    2,\n]"
+        );
+    }
+
+    #[test]
+    fn display_should_flatten_elements() {
+        assert_eq!(HNil.display().to_string(), "hlist![]");
+        let hlist = hlist!(1u8, 'c', String::from("three"));
+        assert_eq!(hlist.display().to_string(), "hlist![1, c, three]");
+        assert_eq!(
+            format!("{:>3}", hlist!(1u8, 2u8).display()),
+            "hlist![  1,   2]"
+        );
+    }
+
+    #[test]
+    fn short_type_name_should_strip_module_paths() {
+        assert_eq!(HNil.short_type_name().to_string(), "HList![]");
+        let hlist = hlist!(
+            Vec::<String>::new(),
+            core::marker::PhantomData::<[u8; 2]>,
+            &(1u8, 2i64)
+        );
+        assert_eq!(
+            hlist.short_type_name().to_string(),
+            "HList![Vec<String>, PhantomData<[u8; 2]>, &(u8, i64)]"
+        );
+    }
+}
@@ -0,0 +1,37 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use defmt::{Format, Formatter};
+
+/// Formats as `HNil`, matching the `Debug` output.
+impl Format for HNil {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "HNil")
+    }
+}
+
+/// Formats as `HCons(head, tail)`, matching the `Debug` output.
+impl<H, T> Format for HCons<H, T>
+where
+    H: Format,
+    T: HList + Format,
+{
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "HCons({}, {})", self.0, self.1)
+    }
+}
+
+/// Formats as `name: value`.
+impl<L, T> Format for Field<L, T>
+where
+    L: Label,
+    T: Format,
+{
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "{=str}: {}", L::NAME, self.value)
+    }
+}
@@ -0,0 +1,317 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::plucker::*;
+use crate::selector::*;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::any::{Any, TypeId};
+
+/// An entity whose components are stored in an `HList`, so that accessing, adding, and
+/// removing components is checked at compile time.  Each component type should appear
+/// at most once.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Position(f32, f32);
+/// struct Velocity(f32, f32);
+/// struct Name(&'static str);
+///
+/// let entity = Entity::new(hlist!(Position(0.0, 0.0), Velocity(1.0, 2.0)));
+/// let mut entity = entity.insert(Name("player"));
+/// entity.get_mut::<Position, _>().0 += 1.0;
+///
+/// let HCons(pos, HCons(vel, HNil)) = entity.query::<HCons<Position, HCons<Velocity, HNil>>, _>();
+/// assert_eq!((pos.0, vel.1), (1.0, 2.0));
+///
+/// let (name, _entity) = entity.remove::<Name, _>();
+/// assert_eq!(name.0, "player");
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Entity<L: HList> {
+    components: L,
+}
+
+impl<L: HList> Entity<L> {
+    /// Creates an entity with the given components.
+    pub fn new(components: L) -> Self {
+        Entity { components }
+    }
+
+    /// Returns a reference to the component of type `C`.
+    pub fn get<C, Index>(&self) -> &C
+    where
+        L: Selector<C, Index>,
+    {
+        self.components.get()
+    }
+
+    /// Returns a mutable reference to the component of type `C`.
+    pub fn get_mut<C, Index>(&mut self) -> &mut C
+    where
+        L: Selector<C, Index>,
+    {
+        self.components.get_mut()
+    }
+
+    /// Returns references to each of the components whose types are listed in `Q`.
+    pub fn query<'a, Q, Indices>(&'a self) -> L::Refs
+    where
+        L: SelectRefs<'a, Q, Indices>,
+    {
+        self.components.select_refs()
+    }
+
+    /// Adds the given component, returning the refined entity.
+    pub fn insert<C>(self, component: C) -> Entity<HCons<C, L>> {
+        Entity::new(HCons(component, self.components))
+    }
+
+    /// Removes the component of type `C`, returning it along with the refined entity.
+    pub fn remove<C, Index>(self) -> (C, Entity<L::Remainder>)
+    where
+        L: Plucker<C, Index>,
+    {
+        let (component, rest) = self.components.pluck();
+        (component, Entity::new(rest))
+    }
+
+    /// Returns the components of this entity.
+    pub fn components(&self) -> &L {
+        &self.components
+    }
+
+    /// Consumes this entity, returning its components.
+    pub fn into_components(self) -> L {
+        self.components
+    }
+}
+
+/// Allows for borrowing each of the elements whose types are listed in `Q` from an
+/// `HList`, in the order given by `Q`.
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position of
+/// each element of `Q` within the list.
+pub trait SelectRefs<'a, Q, Indices> {
+    /// An `HList` of references to the selected elements.
+    type Refs: HList;
+
+    /// Returns references to the selected elements.
+    fn select_refs(&'a self) -> Self::Refs;
+}
+
+impl<'a, L> SelectRefs<'a, HNil, HNil> for L {
+    type Refs = HNil;
+
+    fn select_refs(&'a self) -> HNil {
+        HNil
+    }
+}
+
+impl<'a, L, Q, QRest, Index, RestIndices> SelectRefs<'a, HCons<Q, QRest>, HCons<Index, RestIndices>>
+    for L
+where
+    L: Selector<Q, Index> + SelectRefs<'a, QRest, RestIndices>,
+    Q: 'a,
+    QRest: HList,
+    RestIndices: HList,
+{
+    type Refs = HCons<&'a Q, <L as SelectRefs<'a, QRest, RestIndices>>::Refs>;
+
+    fn select_refs(&'a self) -> Self::Refs {
+        HCons(self.get(), self.select_refs())
+    }
+}
+
+/// An `HList` of components whose elements can be looked up by `TypeId`, which allows
+/// a `World` to hold entities with different sets of components.
+pub trait Components: HList {
+    /// Returns the component with the given type, if present.
+    fn component(&self, id: TypeId) -> Option<&dyn Any>;
+
+    /// Returns the component with the given type mutably, if present.
+    fn component_mut(&mut self, id: TypeId) -> Option<&mut dyn Any>;
+}
+
+impl Components for HNil {
+    fn component(&self, _id: TypeId) -> Option<&dyn Any> {
+        None
+    }
+
+    fn component_mut(&mut self, _id: TypeId) -> Option<&mut dyn Any> {
+        None
+    }
+}
+
+impl<H: Any, T: Components> Components for HCons<H, T> {
+    fn component(&self, id: TypeId) -> Option<&dyn Any> {
+        if id == TypeId::of::<H>() {
+            Some(&self.0)
+        } else {
+            self.1.component(id)
+        }
+    }
+
+    fn component_mut(&mut self, id: TypeId) -> Option<&mut dyn Any> {
+        if id == TypeId::of::<H>() {
+            Some(&mut self.0)
+        } else {
+            self.1.component_mut(id)
+        }
+    }
+}
+
+/// An `HList` of component types to look for when iterating over the entities in a
+/// `World`.
+pub trait Query {
+    /// An `HList` of references to the components.
+    type Refs<'a>: HList;
+
+    /// Returns references to the components, if every one of them is present.
+    fn fetch(components: &dyn Components) -> Option<Self::Refs<'_>>;
+}
+
+impl Query for HNil {
+    type Refs<'a> = HNil;
+
+    fn fetch(_components: &dyn Components) -> Option<HNil> {
+        Some(HNil)
+    }
+}
+
+impl<H: Any, T: Query + HList> Query for HCons<H, T> {
+    type Refs<'a> = HCons<&'a H, T::Refs<'a>>;
+
+    fn fetch(components: &dyn Components) -> Option<Self::Refs<'_>> {
+        let head = components.component(TypeId::of::<H>())?.downcast_ref()?;
+        Some(HCons(head, T::fetch(components)?))
+    }
+}
+
+/// An identifier for an entity spawned in a `World`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EntityId(usize);
+
+/// A collection of entities with different sets of components, which can be iterated
+/// over by the components they have.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Position(i32);
+/// struct Velocity(i32);
+///
+/// let mut world = World::new();
+/// world.spawn(Entity::new(hlist!(Position(0), Velocity(2))));
+/// world.spawn(Entity::new(hlist!(Position(5))));
+///
+/// let moving: Vec<i32> = world
+///     .query::<HCons<Position, HCons<Velocity, HNil>>>()
+///     .map(|(_, HCons(pos, HCons(vel, HNil)))| pos.0 + vel.0)
+///     .collect();
+/// assert_eq!(moving, [2]);
+/// # }
+/// ```
+#[derive(Default)]
+pub struct World {
+    entities: Vec<Option<Box<dyn Components>>>,
+}
+
+impl World {
+    /// Creates an empty world.
+    pub fn new() -> Self {
+        World::default()
+    }
+
+    /// Adds the given entity, returning its identifier.
+    pub fn spawn<L>(&mut self, entity: Entity<L>) -> EntityId
+    where
+        L: Components + 'static,
+    {
+        self.entities.push(Some(Box::new(entity.into_components())));
+        EntityId(self.entities.len() - 1)
+    }
+
+    /// Removes the entity with the given identifier, returning true if it existed.
+    pub fn despawn(&mut self, id: EntityId) -> bool {
+        self.entities.get_mut(id.0).and_then(Option::take).is_some()
+    }
+
+    /// Returns the component of type `C` of the given entity, if it exists and has one.
+    pub fn get<C: Any>(&self, id: EntityId) -> Option<&C> {
+        self.entities
+            .get(id.0)?
+            .as_ref()?
+            .component(TypeId::of::<C>())?
+            .downcast_ref()
+    }
+
+    /// Returns the component of type `C` of the given entity mutably, if it exists and
+    /// has one.
+    pub fn get_mut<C: Any>(&mut self, id: EntityId) -> Option<&mut C> {
+        self.entities
+            .get_mut(id.0)?
+            .as_mut()?
+            .component_mut(TypeId::of::<C>())?
+            .downcast_mut()
+    }
+
+    /// Returns an iterator over the entities that have every component listed in `Q`,
+    /// along with references to those components.
+    pub fn query<Q: Query>(&self) -> impl Iterator<Item = (EntityId, Q::Refs<'_>)> {
+        self.entities.iter().enumerate().filter_map(|(i, entity)| {
+            let components = Q::fetch(entity.as_deref()?)?;
+            Some((EntityId(i), components))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Armor(u32);
+
+    #[test]
+    fn entity_should_track_its_components_statically() {
+        let entity = Entity::new(hlist!(Health(10)));
+        let entity = entity.insert(Armor(3));
+        assert_eq!(entity.get::<Health, _>(), &Health(10));
+        let (armor, entity) = entity.remove::<Armor, _>();
+        assert_eq!(armor, Armor(3));
+        assert_eq!(entity.into_components(), hlist!(Health(10)));
+    }
+
+    #[test]
+    fn world_should_find_entities_by_component_set() {
+        let mut world = World::new();
+        let a = world.spawn(Entity::new(hlist!(Health(10), Armor(1))));
+        let b = world.spawn(Entity::new(hlist!(Health(20))));
+        let c = world.spawn(Entity::new(hlist!(Armor(2), Health(30))));
+
+        world.get_mut::<Health>(b).unwrap().0 += 1;
+        assert_eq!(world.get::<Health>(b), Some(&Health(21)));
+        assert_eq!(world.get::<Armor>(b), None);
+
+        let armored: Vec<_> = world
+            .query::<hlist_type!(Armor, Health)>()
+            .map(|(id, hlist_pattern!(armor, health))| (id, armor.0 + health.0))
+            .collect();
+        assert_eq!(armored, [(a, 11), (c, 32)]);
+
+        assert!(world.despawn(a));
+        assert!(!world.despawn(a));
+        assert_eq!(world.query::<hlist_type!(Health)>().count(), 2);
+    }
+}
@@ -0,0 +1,185 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::coproduct::*;
+use crate::hlist::*;
+use core::marker::PhantomData;
+
+/// A handler for events of type `E`.
+///
+/// This is implemented for closures, for `Ignore` (an explicit "do nothing" handler),
+/// and for non-empty `HList`s of subscribers, which notify each subscriber in turn.
+pub trait Subscriber<E> {
+    /// Handles the given event.
+    fn notify(&mut self, event: &E);
+}
+
+impl<E, F> Subscriber<E> for F
+where
+    F: FnMut(&E),
+{
+    fn notify(&mut self, event: &E) {
+        self(event)
+    }
+}
+
+/// A `Subscriber` that ignores every event, for event types that need no handling.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Ignore;
+
+impl<E> Subscriber<E> for Ignore {
+    fn notify(&mut self, _event: &E) {}
+}
+
+impl<E, H> Subscriber<E> for HCons<H, HNil>
+where
+    H: Subscriber<E>,
+{
+    fn notify(&mut self, event: &E) {
+        self.0.notify(event)
+    }
+}
+
+impl<E, H, H2, T> Subscriber<E> for HCons<H, HCons<H2, T>>
+where
+    H: Subscriber<E>,
+    T: HList,
+    HCons<H2, T>: Subscriber<E>,
+{
+    fn notify(&mut self, event: &E) {
+        self.0.notify(event);
+        self.1.notify(event)
+    }
+}
+
+/// An `HList` of subscribers that has one `Subscriber` for each variant of the coproduct
+/// `Events`, in the same order.
+pub trait Subscribers<Events> {
+    /// Notifies the subscriber for the variant held by the given event.
+    fn publish(&mut self, event: &Events);
+}
+
+impl Subscribers<CNil> for HNil {
+    fn publish(&mut self, event: &CNil) {
+        match *event {}
+    }
+}
+
+impl<E, Rest, S, Tail> Subscribers<Coproduct<E, Rest>> for HCons<S, Tail>
+where
+    S: Subscriber<E>,
+    Tail: HList + Subscribers<Rest>,
+{
+    fn publish(&mut self, event: &Coproduct<E, Rest>) {
+        match event {
+            Coproduct::Inl(event) => self.0.notify(event),
+            Coproduct::Inr(rest) => self.1.publish(rest),
+        }
+    }
+}
+
+/// An event bus whose events are the variants of the coproduct `Events`, and whose
+/// subscribers are an `HList` with one `Subscriber` per variant.
+///
+/// Creating a bus statically guarantees that every kind of event has a handler (or an
+/// explicit `Ignore`), so publishing can never silently drop an event.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Login(&'static str);
+/// struct Logout(&'static str);
+/// type Events = Coproduct<Login, Coproduct<Logout, Coproduct<u64, CNil>>>;
+///
+/// let mut log = Vec::new();
+/// {
+///     let mut bus = EventBus::<Events, _>::new(hlist!(
+///         |e: &Login| log.push(e.0),
+///         hlist!(|_: &Logout| (), |_: &Logout| ()),
+///         Ignore
+///     ));
+///     bus.publish_as(Login("alice"));
+///     bus.publish_as(42u64);
+/// }
+/// assert_eq!(log, ["alice"]);
+/// # }
+/// ```
+///
+/// A bus without a subscriber for every kind of event does not compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// type Events = Coproduct<u8, Coproduct<u16, CNil>>;
+/// let bus = EventBus::<Events, _>::new(hlist!(|_: &u8| ()));
+/// # }
+/// ```
+pub struct EventBus<Events, Subs> {
+    subscribers: Subs,
+    events: PhantomData<fn(&Events)>,
+}
+
+impl<Events, Subs> EventBus<Events, Subs>
+where
+    Subs: Subscribers<Events>,
+{
+    /// Creates an event bus with the given subscribers.
+    pub fn new(subscribers: Subs) -> Self {
+        EventBus {
+            subscribers,
+            events: PhantomData,
+        }
+    }
+
+    /// Notifies the subscriber for the given event.
+    pub fn publish(&mut self, event: &Events) {
+        self.subscribers.publish(event)
+    }
+
+    /// Injects the given value into the `Events` coproduct and publishes it.
+    pub fn publish_as<E, Index>(&mut self, event: E)
+    where
+        Events: CoprodInjector<E, Index>,
+    {
+        self.publish(&Events::inject(event))
+    }
+
+    /// Consumes this bus, returning its subscribers.
+    pub fn into_subscribers(self) -> Subs {
+        self.subscribers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(u32);
+
+    impl Subscriber<u32> for Counter {
+        fn notify(&mut self, event: &u32) {
+            self.0 += event;
+        }
+    }
+
+    type Events = Coproduct<u32, Coproduct<&'static str, CNil>>;
+
+    #[test]
+    fn publish_should_notify_each_subscriber_for_the_variant() {
+        let mut names = Vec::new();
+        let mut bus = EventBus::<Events, _>::new(hlist!(
+            hlist!(Counter(0), Counter(10)),
+            |name: &&'static str| names.push(*name)
+        ));
+        bus.publish_as(1u32);
+        bus.publish(&Events::inject("a"));
+        bus.publish_as(2u32);
+        let counters = bus.into_subscribers().0;
+        assert_eq!((counters.0 .0, counters.1 .0 .0), (3, 13));
+        assert_eq!(names, ["a"]);
+    }
+}
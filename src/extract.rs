@@ -0,0 +1,128 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use axum_core::extract::{FromRequest, FromRequestParts, Request};
+use axum_core::response::{IntoResponse, Response};
+use core::convert::Infallible;
+use core::future::Future;
+use http::request::Parts;
+
+/// An `HNil` extractor always succeeds.
+impl<S> FromRequestParts<S> for HNil
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(_: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        Ok(HNil)
+    }
+}
+
+/// An `HCons` whose elements are all `FromRequestParts` extractors runs each extractor
+/// in order, failing with the first rejection.
+impl<S, H, T> FromRequestParts<S> for HCons<H, T>
+where
+    H: FromRequestParts<S> + Send,
+    T: HList + FromRequestParts<S> + Send,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let head = H::from_request_parts(parts, state)
+            .await
+            .map_err(|err| err.into_response())?;
+        let tail = T::from_request_parts(parts, state)
+            .await
+            .map_err(|err| err.into_response())?;
+        Ok(HCons(head, tail))
+    }
+}
+
+/// An `HCons` whose last element is a `FromRequest` extractor (such as one that
+/// consumes the request body) runs the preceding `FromRequestParts` extractors in
+/// order and then hands the request to the last one.
+///
+/// Like axum's tuple impls, this is not generic over the `FromRequest` marker type
+/// so that it does not conflict with the blanket impl for `FromRequestParts` types.
+impl<S, H> FromRequest<S> for HCons<H, HNil>
+where
+    H: FromRequest<S> + Send,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let head = H::from_request(req, state)
+            .await
+            .map_err(|err| err.into_response())?;
+        Ok(HCons(head, HNil))
+    }
+}
+
+/// See the impl for `HCons<H, HNil>` above.
+impl<S, H, H2, T2> FromRequest<S> for HCons<H, HCons<H2, T2>>
+where
+    H: FromRequestParts<S> + Send,
+    T2: HList,
+    HCons<H2, T2>: FromRequest<S> + Send,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    fn from_request(
+        req: Request,
+        state: &S,
+    ) -> impl Future<Output = Result<Self, Self::Rejection>> {
+        let (mut parts, body) = req.into_parts();
+        async move {
+            let head = H::from_request_parts(&mut parts, state)
+                .await
+                .map_err(|err| err.into_response())?;
+            let req = Request::from_parts(parts, body);
+            let tail = HCons::from_request(req, state)
+                .await
+                .map_err(|err| err.into_response())?;
+            Ok(HCons(head, tail))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::{Method, Uri};
+
+    fn assert_from_request<M, T>()
+    where
+        T: FromRequest<(), M>,
+    {
+    }
+
+    fn assert_from_request_parts<T: FromRequestParts<()>>() {}
+
+    #[test]
+    fn hlist_of_parts_extractors_should_be_an_extractor() {
+        assert_from_request_parts::<HNil>();
+        assert_from_request_parts::<HCons<Method, HNil>>();
+        assert_from_request_parts::<HCons<Method, HCons<Uri, HNil>>>();
+        assert_from_request::<_, HCons<Method, HCons<Uri, HNil>>>();
+    }
+
+    #[test]
+    fn hlist_ending_in_body_extractor_should_be_an_extractor() {
+        assert_from_request::<_, HCons<String, HNil>>();
+        assert_from_request::<_, HCons<Method, HCons<String, HNil>>>();
+        assert_from_request::<_, HCons<Method, HCons<Uri, HCons<String, HNil>>>>();
+    }
+
+    #[test]
+    fn nested_hlists_should_be_extractors() {
+        assert_from_request_parts::<HCons<HCons<Method, HNil>, HCons<Uri, HNil>>>();
+        assert_from_request::<_, HCons<HCons<Method, HNil>, HCons<String, HNil>>>();
+    }
+}
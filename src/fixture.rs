@@ -0,0 +1,191 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use crate::registry::*;
+use crate::selector::*;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// A type with a representative example value, for use in test fixtures.
+///
+/// This is implemented for the primitive types, `Option`, arrays, and (with the `alloc`
+/// feature) `String` and `Vec`, as well as for `HList`s and `Field`s of types that
+/// implement it.
+pub trait Example {
+    /// Returns an example value.
+    fn example() -> Self;
+}
+
+macro_rules! impl_example {
+    ($($t:ty => $value:expr),*) => {
+        $(
+            impl Example for $t {
+                fn example() -> Self {
+                    $value
+                }
+            }
+        )*
+    };
+}
+
+impl_example!(
+    u8 => 42, u16 => 42, u32 => 42, u64 => 42, u128 => 42, usize => 42,
+    i8 => -42, i16 => -42, i32 => -42, i64 => -42, i128 => -42, isize => -42,
+    f32 => 4.2, f64 => 4.2, bool => true, char => 'x', &'static str => "example", () => ()
+);
+
+#[cfg(feature = "alloc")]
+impl_example!(String => String::from("example"));
+
+impl<T: Example> Example for Option<T> {
+    fn example() -> Self {
+        Some(T::example())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Example> Example for Vec<T> {
+    fn example() -> Self {
+        vec![T::example()]
+    }
+}
+
+impl<T: Example, const N: usize> Example for [T; N] {
+    fn example() -> Self {
+        core::array::from_fn(|_| T::example())
+    }
+}
+
+impl Example for HNil {
+    fn example() -> Self {
+        HNil
+    }
+}
+
+impl<H: Example, T: HList + Example> Example for HCons<H, T> {
+    fn example() -> Self {
+        HCons(H::example(), T::example())
+    }
+}
+
+impl<L, T: Example> Example for Field<L, T> {
+    fn example() -> Self {
+        Field::new(T::example())
+    }
+}
+
+/// Allows for building test instances of a struct from example values, with selective
+/// overrides.  This is implemented by the `Fixture` derive, which requires the struct to
+/// also derive `HListSupport`.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// #[derive(Debug, HListSupport, Fixture)]
+/// struct User {
+///     name: &'static str,
+///     age: u8,
+///     admin: bool,
+/// }
+///
+/// # fn main() {
+/// let user = User::fixture();
+/// assert_eq!((user.name, user.age, user.admin), ("example", 42, true));
+///
+/// let user = User::fixture_with(hlist!(Field::<label!(admin), _>::new(false)));
+/// assert!(!user.admin);
+///
+/// let user = User::fixture_from(&hlist!("alice", 30u8, false));
+/// assert_eq!((user.name, user.age), ("alice", 30));
+/// # }
+/// ```
+pub trait Fixture: FromLabelledHList<Self::Fields> + Sized {
+    /// The labelled `HList` representation of this type.
+    type Fields: HList + Example + StripLabels;
+
+    /// Returns an instance whose fields all hold their type's example value.
+    fn fixture() -> Self {
+        Self::from_labelled_hlist(Self::Fields::example())
+    }
+
+    /// Returns an instance whose fields hold their type's example value, except for those
+    /// given in `overrides` (a labelled `HList` of a subset of the fields, in any order).
+    fn fixture_with<Overrides, Indices>(overrides: Overrides) -> Self
+    where
+        Self::Fields: ApplyOverrides<Overrides, Indices>,
+    {
+        let mut fields = Self::Fields::example();
+        fields.apply_overrides(overrides);
+        Self::from_labelled_hlist(fields)
+    }
+
+    /// Returns an instance whose fields are copies of the element of the same type in
+    /// `providers` (an `HList` with one example value per field type, in any order).
+    fn fixture_from<Providers, Indices>(providers: &Providers) -> Self
+    where
+        Providers: ResolveAll<<Self::Fields as StripLabels>::Output, Indices>,
+        <Self::Fields as StripLabels>::Output: IntoFields<Self::Fields>,
+    {
+        Self::from_labelled_hlist(providers.resolve_all().into_fields())
+    }
+}
+
+/// Allows for replacing the fields of a labelled `HList` with those in the `Overrides`
+/// labelled `HList`, matching them by label.
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position of
+/// each override within the list.
+pub trait ApplyOverrides<Overrides, Indices> {
+    /// Replaces each field that has an override.
+    fn apply_overrides(&mut self, overrides: Overrides);
+}
+
+impl<L> ApplyOverrides<HNil, HNil> for L {
+    fn apply_overrides(&mut self, _overrides: HNil) {}
+}
+
+impl<L, O, ORest, Index, RestIndices> ApplyOverrides<HCons<O, ORest>, HCons<Index, RestIndices>>
+    for L
+where
+    L: Selector<O, Index> + ApplyOverrides<ORest, RestIndices>,
+    ORest: HList,
+    RestIndices: HList,
+{
+    fn apply_overrides(&mut self, overrides: HCons<O, ORest>) {
+        *self.get_mut() = overrides.0;
+        self.apply_overrides(overrides.1)
+    }
+}
+
+// The example values under test include `String` and `Vec`
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::{Fixture, HListSupport};
+
+    #[derive(Debug, PartialEq, HListSupport, Fixture)]
+    struct Order {
+        id: u64,
+        items: Vec<&'static str>,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn fixture_should_use_example_values_and_overrides() {
+        let order = Order::fixture();
+        assert_eq!(order.id, 42);
+        assert_eq!(order.items, ["example"]);
+        assert_eq!(order.note.as_deref(), Some("example"));
+
+        let order = Order::fixture_with(hlist!(
+            Field::<label!(note), Option<String>>::new(None),
+            Field::<label!(id), _>::new(7u64)
+        ));
+        assert_eq!((order.id, order.note), (7, None));
+    }
+}
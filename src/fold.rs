@@ -0,0 +1,259 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::map::*;
+
+/// Allows for folding an `HList` from left to right, combining an accumulator with each
+/// element in turn.  The accumulator's type may change at every step.
+///
+/// The `Folder` is either a `Poly` wrapping a function object that implements
+/// `Func<(Acc, Element)>` for each step, or an `HList` of closures `FnOnce(Acc, Element)`
+/// with one closure per element.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(2u8, "three", 4.5f32);
+/// let total = hlist.foldl(
+///     hlist!(|acc: f32, n: u8| acc + n as f32, |acc, s: &str| acc + s.len() as f32, |acc, x| acc + x),
+///     0.0,
+/// );
+/// assert_eq!(total, 11.5);
+/// # }
+/// ```
+pub trait HFoldLeft<Folder, Acc> {
+    /// The type of the final accumulator.
+    type Output;
+
+    /// Folds this list from left to right, starting from `init`.
+    fn foldl(self, folder: Folder, init: Acc) -> Self::Output;
+}
+
+impl<F, Acc> HFoldLeft<Poly<F>, Acc> for HNil {
+    type Output = Acc;
+
+    #[inline]
+    fn foldl(self, _: Poly<F>, init: Acc) -> Acc {
+        init
+    }
+}
+
+impl<F, Acc, H, T> HFoldLeft<Poly<F>, Acc> for HCons<H, T>
+where
+    F: Func<(Acc, H)>,
+    T: HList + HFoldLeft<Poly<F>, F::Output>,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn foldl(self, folder: Poly<F>, init: Acc) -> Self::Output {
+        let Poly(mut f) = folder;
+        let acc = f.call((init, self.0));
+        self.1.foldl(Poly(f), acc)
+    }
+}
+
+impl<Acc> HFoldLeft<HNil, Acc> for HNil {
+    type Output = Acc;
+
+    #[inline]
+    fn foldl(self, _: HNil, init: Acc) -> Acc {
+        init
+    }
+}
+
+impl<F, R, FTail, Acc, H, T> HFoldLeft<HCons<F, FTail>, Acc> for HCons<H, T>
+where
+    F: FnOnce(Acc, H) -> R,
+    FTail: HList,
+    T: HList + HFoldLeft<FTail, R>,
+{
+    type Output = T::Output;
+
+    #[inline]
+    fn foldl(self, folder: HCons<F, FTail>, init: Acc) -> Self::Output {
+        let acc = (folder.0)(init, self.0);
+        self.1.foldl(folder.1, acc)
+    }
+}
+
+/// Allows for folding an `HList` from right to left, combining each element with the
+/// result of folding the elements after it.  The accumulator's type may change at every
+/// step.
+///
+/// The `Folder` is either a `Poly` wrapping a function object that implements
+/// `Func<(Element, Acc)>` for each step, or an `HList` of closures `FnOnce(Element, Acc)`
+/// with one closure per element.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Render;
+///
+/// impl<T: std::fmt::Debug> Func<(T, String)> for Render {
+///     type Output = String;
+///     fn call(&mut self, (value, rest): (T, String)) -> String {
+///         format!("{:?} :: {}", value, rest)
+///     }
+/// }
+///
+/// let rendered = hlist!(1u8, "two", 3.0f32).foldr(Poly(Render), String::from("nil"));
+/// assert_eq!(rendered, "1 :: \"two\" :: 3.0 :: nil");
+/// # }
+/// ```
+pub trait HFoldRight<Folder, Init> {
+    /// The type of the final accumulator.
+    type Output;
+
+    /// Folds this list from right to left, starting from `init`.
+    fn foldr(self, folder: Folder, init: Init) -> Self::Output;
+}
+
+/// Folds an `HList` from right to left with a borrowed polymorphic function object.
+///
+/// This is an implementation detail of `HFoldRight`, which needs to pass the function
+/// object to the fold of the tail before applying it to the head.
+#[doc(hidden)]
+pub trait HFoldRightWith<F, Init> {
+    type Output;
+
+    fn foldr_with(self, f: &mut F, init: Init) -> Self::Output;
+}
+
+impl<F, Init> HFoldRightWith<F, Init> for HNil {
+    type Output = Init;
+
+    #[inline]
+    fn foldr_with(self, _: &mut F, init: Init) -> Init {
+        init
+    }
+}
+
+impl<F, Init, H, T> HFoldRightWith<F, Init> for HCons<H, T>
+where
+    T: HList + HFoldRightWith<F, Init>,
+    F: Func<(H, T::Output)>,
+{
+    type Output = F::Output;
+
+    #[inline]
+    fn foldr_with(self, f: &mut F, init: Init) -> Self::Output {
+        let acc = self.1.foldr_with(f, init);
+        f.call((self.0, acc))
+    }
+}
+
+impl<F, Init, L> HFoldRight<Poly<F>, Init> for L
+where
+    L: HList + HFoldRightWith<F, Init>,
+{
+    type Output = L::Output;
+
+    #[inline]
+    fn foldr(self, folder: Poly<F>, init: Init) -> Self::Output {
+        let Poly(mut f) = folder;
+        self.foldr_with(&mut f, init)
+    }
+}
+
+impl<Init> HFoldRight<HNil, Init> for HNil {
+    type Output = Init;
+
+    #[inline]
+    fn foldr(self, _: HNil, init: Init) -> Init {
+        init
+    }
+}
+
+impl<F, R, FTail, Init, H, T> HFoldRight<HCons<F, FTail>, Init> for HCons<H, T>
+where
+    FTail: HList,
+    T: HList + HFoldRight<FTail, Init>,
+    F: FnOnce(H, T::Output) -> R,
+{
+    type Output = R;
+
+    #[inline]
+    fn foldr(self, folder: HCons<F, FTail>, init: Init) -> R {
+        let acc = self.1.foldr(folder.1, init);
+        (folder.0)(self.0, acc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::hash::{Hash, Hasher};
+    use std::collections::hash_map::DefaultHasher;
+
+    // Feeds each element into a hasher, for folding from the left.
+    struct HashAll;
+
+    impl<T: Hash> Func<(DefaultHasher, T)> for HashAll {
+        type Output = DefaultHasher;
+
+        fn call(&mut self, (mut hasher, value): (DefaultHasher, T)) -> DefaultHasher {
+            value.hash(&mut hasher);
+            hasher
+        }
+    }
+
+    // Feeds each element into a hasher, for folding from the right.
+    struct HashAllRight;
+
+    impl<T: Hash> Func<(T, DefaultHasher)> for HashAllRight {
+        type Output = DefaultHasher;
+
+        fn call(&mut self, (value, hasher): (T, DefaultHasher)) -> DefaultHasher {
+            HashAll.call((hasher, value))
+        }
+    }
+
+    #[test]
+    fn foldl_with_poly_should_visit_elements_left_to_right() {
+        let hasher = hlist!(1u8, "two", 3u64).foldl(Poly(HashAll), DefaultHasher::new());
+
+        let mut expected = DefaultHasher::new();
+        1u8.hash(&mut expected);
+        "two".hash(&mut expected);
+        3u64.hash(&mut expected);
+        assert_eq!(hasher.finish(), expected.finish());
+    }
+
+    #[test]
+    fn foldr_with_poly_should_visit_elements_right_to_left() {
+        let hasher = hlist!(1u8, "two", 3u64).foldr(Poly(HashAllRight), DefaultHasher::new());
+
+        let mut expected = DefaultHasher::new();
+        3u64.hash(&mut expected);
+        "two".hash(&mut expected);
+        1u8.hash(&mut expected);
+        assert_eq!(hasher.finish(), expected.finish());
+    }
+
+    #[test]
+    fn folds_with_closures_should_thread_the_accumulator_type() {
+        let errors = hlist!(5u32, "").foldl(
+            hlist!(
+                |acc: Vec<&'static str>, n: u32| if n > 3 { vec!["too large"] } else { acc },
+                |mut acc: Vec<&str>, s: &str| {
+                    if s.is_empty() {
+                        acc.push("empty");
+                    }
+                    acc.len()
+                }
+            ),
+            Vec::new(),
+        );
+        assert_eq!(errors, 2);
+
+        let nested =
+            hlist!(1u8, 'b').foldr(hlist!(|n: u8, acc| (n, acc), |c: char, acc| (c, acc)), ());
+        assert_eq!(nested, (1u8, ('b', ())));
+    }
+}
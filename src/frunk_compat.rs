@@ -0,0 +1,165 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use frunk::Generic;
+
+impl From<frunk::HNil> for HNil {
+    fn from(_: frunk::HNil) -> Self {
+        HNil
+    }
+}
+
+impl From<HNil> for frunk::HNil {
+    fn from(_: HNil) -> Self {
+        frunk::HNil
+    }
+}
+
+/// Converts a frunk `HCons` into the equivalent `HCons`, element by element.
+impl<H, T, FT> From<frunk::HCons<H, FT>> for HCons<H, T>
+where
+    T: HList + From<FT>,
+{
+    fn from(hlist: frunk::HCons<H, FT>) -> Self {
+        HCons(hlist.head, hlist.tail.into())
+    }
+}
+
+/// Converts an `HCons` into the equivalent frunk `HCons`, element by element.
+impl<H, T, FT> From<HCons<H, T>> for frunk::HCons<H, FT>
+where
+    T: HList,
+    FT: From<T>,
+{
+    fn from(hlist: HCons<H, T>) -> Self {
+        frunk::HCons {
+            head: hlist.0,
+            tail: hlist.1.into(),
+        }
+    }
+}
+
+/// Adapts a type that implements frunk's `Generic` to this crate's `FromHList`,
+/// `ToHList`, and `IntoHList`, converting between its frunk representation and the
+/// equivalent `HList`.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// // As generated by `#[derive(frunk::Generic)]`
+/// impl frunk::Generic for Point {
+///     type Repr = frunk::HList!(i32, i32);
+///
+///     fn into(self) -> Self::Repr {
+///         frunk::hlist![self.x, self.y]
+///     }
+///
+///     fn from(repr: Self::Repr) -> Self {
+///         let frunk::hlist_pat![x, y] = repr;
+///         Point { x, y }
+///     }
+/// }
+///
+/// let hlist: HCons<i32, HCons<i32, HNil>> = FrunkGeneric(Point { x: 1, y: 2 }).into_hlist();
+/// assert_eq!(hlist, hlist!(1, 2));
+/// let point = FrunkGeneric::<Point>::from_hlist(hlist).into_inner();
+/// assert_eq!(point, Point { x: 1, y: 2 });
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FrunkGeneric<T>(pub T);
+
+impl<T> FrunkGeneric<T> {
+    /// Returns the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, H> FromHList<H> for FrunkGeneric<T>
+where
+    T: Generic,
+    H: HList + Into<T::Repr>,
+{
+    fn from_hlist(hlist: H) -> Self {
+        FrunkGeneric(T::from(hlist.into()))
+    }
+}
+
+impl<T, H> ToHList<H> for FrunkGeneric<T>
+where
+    T: Generic + Clone,
+    H: HList + From<T::Repr>,
+{
+    fn to_hlist(&self) -> H {
+        T::into(self.0.clone()).into()
+    }
+}
+
+impl<T, H> IntoHList<H> for FrunkGeneric<T>
+where
+    T: Generic,
+    H: HList + From<T::Repr>,
+{
+    fn into_hlist(self) -> H {
+        T::into(self.0).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Point {
+        x: i32,
+        label: &'static str,
+    }
+
+    impl Generic for Point {
+        type Repr = frunk::HList!(i32, &'static str);
+
+        fn into(self) -> Self::Repr {
+            frunk::hlist![self.x, self.label]
+        }
+
+        fn from(repr: Self::Repr) -> Self {
+            let frunk::hlist_pat![x, label] = repr;
+            Point { x, label }
+        }
+    }
+
+    #[test]
+    fn frunk_generic_types_should_round_trip() {
+        let point = Point { x: 3, label: "p" };
+        let hlist: HCons<i32, HCons<&str, HNil>> = FrunkGeneric(point.clone()).to_hlist();
+        assert_eq!(hlist, hlist!(3, "p"));
+        let into: HCons<i32, HCons<&str, HNil>> = FrunkGeneric(point.clone()).into_hlist();
+        assert_eq!(into, hlist);
+        assert_eq!(FrunkGeneric::<Point>::from_hlist(hlist).into_inner(), point);
+
+        // frunk implements `Generic` for tuples too
+        let tuple: (u8, char) = FrunkGeneric::from_hlist(hlist!(1u8, 'a')).into_inner();
+        assert_eq!(tuple, (1, 'a'));
+    }
+
+    #[test]
+    fn converting_from_frunk_should_work() {
+        let hlist: HCons<u8, HCons<&str, HNil>> = frunk::hlist![1u8, "two"].into();
+        assert_eq!(hlist, hlist!(1u8, "two"));
+        assert_eq!(HNil::from(frunk::HNil), HNil);
+    }
+
+    #[test]
+    fn converting_to_frunk_should_work() {
+        let hlist: frunk::HList!(u8, &str) = hlist!(1u8, "two").into();
+        assert_eq!(hlist, frunk::hlist![1u8, "two"]);
+    }
+}
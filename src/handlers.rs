@@ -0,0 +1,219 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+use core::marker::PhantomData;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use core::any::Any;
+
+/// A handler function for requests of type `Req`, producing responses of type `Resp`.
+pub struct HandlerFn<Req, Resp, F> {
+    f: F,
+    signature: PhantomData<fn(Req) -> Resp>,
+}
+
+impl<Req, Resp, F> HandlerFn<Req, Resp, F>
+where
+    F: Fn(Req) -> Resp,
+{
+    /// Wraps the given handler function.
+    pub fn new(f: F) -> Self {
+        HandlerFn {
+            f,
+            signature: PhantomData,
+        }
+    }
+
+    /// Invokes the handler with the given request.
+    pub fn call(&self, req: Req) -> Resp {
+        (self.f)(req)
+    }
+}
+
+/// A registry of handlers stored in an `HList`, keyed by their request types.
+///
+/// `dispatch` routes a request to the handler for its type statically, so dispatching a
+/// request type with no handler is a compile error.  With the `alloc` feature,
+/// `dispatch_any` also routes type-erased requests at runtime.  Each request type should
+/// have at most one handler.
+///
+/// ```
+/// # use pl_hlist::*;
+/// struct Ping;
+/// struct Add(u32, u32);
+///
+/// let handlers = Handlers::new()
+///     .on(|_: Ping| "pong")
+///     .on(|Add(a, b)| a + b);
+///
+/// assert_eq!(handlers.dispatch(Ping), "pong");
+/// assert_eq!(handlers.dispatch(Add(1, 2)), 3);
+/// ```
+///
+/// Dispatching a request type with no handler does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// let handlers = Handlers::new().on(|x: u8| x);
+/// handlers.dispatch(1u32);
+/// ```
+pub struct Handlers<L: HList> {
+    handlers: L,
+}
+
+impl Handlers<HNil> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Handlers { handlers: HNil }
+    }
+}
+
+impl Default for Handlers<HNil> {
+    fn default() -> Self {
+        Handlers::new()
+    }
+}
+
+impl<L: HList> Handlers<L> {
+    /// Adds a handler for requests of type `Req`, returning the refined registry.
+    pub fn on<Req, Resp, F>(self, f: F) -> Handlers<HCons<HandlerFn<Req, Resp, F>, L>>
+    where
+        F: Fn(Req) -> Resp,
+    {
+        Handlers {
+            handlers: HCons(HandlerFn::new(f), self.handlers),
+        }
+    }
+
+    /// Routes the given request to the handler for its type.
+    pub fn dispatch<Req, Index>(&self, req: Req) -> L::Response
+    where
+        L: Dispatch<Req, Index>,
+    {
+        self.handlers.dispatch(req)
+    }
+
+    /// Routes the given type-erased request to the handler for its type, returning the
+    /// type-erased response, or the request if there is no handler for its type.
+    #[cfg(feature = "alloc")]
+    pub fn dispatch_any(&self, req: Box<dyn Any>) -> Result<Box<dyn Any>, Box<dyn Any>>
+    where
+        L: DispatchAny,
+    {
+        self.handlers.dispatch_any(req)
+    }
+}
+
+/// Allows for routing a request of type `Req` to the handler for that type in an `HList`
+/// of `HandlerFn`s.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// the handler within the list.
+pub trait Dispatch<Req, Index> {
+    /// The type of response produced by the handler.
+    type Response;
+
+    /// Routes the given request to the handler for its type.
+    fn dispatch(&self, req: Req) -> Self::Response;
+}
+
+impl<Req, Resp, F, Tail> Dispatch<Req, Here> for HCons<HandlerFn<Req, Resp, F>, Tail>
+where
+    F: Fn(Req) -> Resp,
+    Tail: HList,
+{
+    type Response = Resp;
+
+    #[inline]
+    fn dispatch(&self, req: Req) -> Resp {
+        self.0.call(req)
+    }
+}
+
+impl<Req, H, Tail, TailIndex> Dispatch<Req, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + Dispatch<Req, TailIndex>,
+{
+    type Response = Tail::Response;
+
+    #[inline]
+    fn dispatch(&self, req: Req) -> Self::Response {
+        self.1.dispatch(req)
+    }
+}
+
+/// Allows for routing a type-erased request to the handler for its type in an `HList`
+/// of `HandlerFn`s, checking the type of the request at runtime.
+#[cfg(feature = "alloc")]
+pub trait DispatchAny {
+    /// Routes the given request to the handler for its type, returning the type-erased
+    /// response, or the request if there is no handler for its type.
+    fn dispatch_any(&self, req: Box<dyn Any>) -> Result<Box<dyn Any>, Box<dyn Any>>;
+}
+
+#[cfg(feature = "alloc")]
+impl DispatchAny for HNil {
+    fn dispatch_any(&self, req: Box<dyn Any>) -> Result<Box<dyn Any>, Box<dyn Any>> {
+        Err(req)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<Req, Resp, F, Tail> DispatchAny for HCons<HandlerFn<Req, Resp, F>, Tail>
+where
+    Req: 'static,
+    Resp: 'static,
+    F: Fn(Req) -> Resp,
+    Tail: HList + DispatchAny,
+{
+    fn dispatch_any(&self, req: Box<dyn Any>) -> Result<Box<dyn Any>, Box<dyn Any>> {
+        match req.downcast::<Req>() {
+            Ok(req) => Ok(Box::new(self.0.call(*req))),
+            Err(req) => self.1.dispatch_any(req),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    struct Get(&'static str);
+
+    struct Put(u32);
+
+    #[test]
+    fn dispatch_should_route_by_request_type() {
+        let puts = Cell::new(0);
+        let handlers = Handlers::new()
+            .on(|Get(key)| key.len())
+            .on(|Put(value)| puts.set(puts.get() + value));
+
+        assert_eq!(handlers.dispatch(Get("abc")), 3);
+        handlers.dispatch(Put(2));
+        handlers.dispatch(Put(3));
+        assert_eq!(puts.get(), 5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn dispatch_any_should_route_by_runtime_type() {
+        let handlers = Handlers::new().on(|Get(key)| key.len()).on(|x: u8| x * 2);
+
+        let resp = handlers.dispatch_any(Box::new(Get("abcd"))).unwrap();
+        assert_eq!(*resp.downcast::<usize>().unwrap(), 4);
+        let resp = handlers.dispatch_any(Box::new(21u8)).unwrap();
+        assert_eq!(*resp.downcast::<u8>().unwrap(), 42);
+
+        let req = handlers.dispatch_any(Box::new("unhandled")).unwrap_err();
+        assert_eq!(*req.downcast::<&str>().unwrap(), "unhandled");
+    }
+}
@@ -0,0 +1,82 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::hash::{Hash, Hasher};
+
+/// Allows for hashing each element of an `HList` individually, rather than producing
+/// a single combined hash as `Hash` does.  This is useful for detecting which elements
+/// changed between two versions of a value (e.g., for Merkle-style change detection).
+pub trait HashElements {
+    /// An `HList` of `u64`s with one hash per element.
+    type Hashes: HList;
+
+    /// Returns the hash of each element, each computed with a fresh `S` hasher.
+    fn hash_elements<S: Hasher + Default>(&self) -> Self::Hashes;
+}
+
+impl HashElements for HNil {
+    type Hashes = HNil;
+
+    fn hash_elements<S: Hasher + Default>(&self) -> Self::Hashes {
+        HNil
+    }
+}
+
+impl<H, T> HashElements for HCons<H, T>
+where
+    H: Hash,
+    T: HList + HashElements,
+{
+    type Hashes = HCons<u64, T::Hashes>;
+
+    fn hash_elements<S: Hasher + Default>(&self) -> Self::Hashes {
+        let mut hasher = S::default();
+        self.0.hash(&mut hasher);
+        HCons(hasher.finish(), self.1.hash_elements::<S>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+    use std::collections::hash_map::DefaultHasher;
+
+    #[derive(Clone, HListSupport)]
+    struct Document {
+        title: String,
+        revision: u32,
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::default();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hash_elements_should_hash_each_element_independently() {
+        let hashes = hlist!(1u8, "two").hash_elements::<DefaultHasher>();
+        assert_eq!(hashes, hlist!(hash_of(&1u8), hash_of(&"two")));
+        assert_eq!(HNil.hash_elements::<DefaultHasher>(), HNil);
+    }
+
+    #[test]
+    fn changing_a_field_should_only_change_its_hash() {
+        let doc = Document {
+            title: "Draft".to_string(),
+            revision: 1,
+        };
+        let before = doc.to_hlist().hash_elements::<DefaultHasher>();
+
+        let mut updated = doc.clone();
+        updated.revision = 2;
+        let after = updated.to_hlist().hash_elements::<DefaultHasher>();
+
+        assert_eq!(before.head(), after.head());
+        assert_ne!(before.tail(), after.tail());
+    }
+}
@@ -0,0 +1,204 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a labelled `HList` cannot be converted to or from a `HeaderMap`.
+/// Each variant names the header that caused the problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeaderFieldError {
+    /// The header map did not contain the header.
+    Missing { header: String },
+    /// The header's value could not be parsed into the field's type.
+    Invalid { header: String, message: String },
+    /// The field's value could not be rendered as a valid header name or value.
+    Unrepresentable { header: String },
+}
+
+impl HeaderFieldError {
+    /// Returns the name of the header that caused this error.
+    pub fn header(&self) -> &str {
+        match self {
+            HeaderFieldError::Missing { header }
+            | HeaderFieldError::Invalid { header, .. }
+            | HeaderFieldError::Unrepresentable { header } => header,
+        }
+    }
+}
+
+impl fmt::Display for HeaderFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeaderFieldError::Missing { header } => write!(f, "missing header `{}`", header),
+            HeaderFieldError::Invalid { header, message } => {
+                write!(f, "invalid value for header `{}`: {}", header, message)
+            }
+            HeaderFieldError::Unrepresentable { header } => {
+                write!(f, "value cannot be represented in header `{}`", header)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HeaderFieldError {}
+
+/// Returns the header name for the given field label: the label with underscores
+/// replaced by dashes (header names are case-insensitive).
+fn header_name(label: &str) -> String {
+    label.replace('_', "-")
+}
+
+/// Allows for populating a labelled `HList` from a `HeaderMap`, parsing the header
+/// matching each field with `FromStr`.  The header name for each field is its label
+/// with underscores replaced by dashes (so `content_type` reads `Content-Type`).
+///
+/// ```
+/// use http::HeaderMap;
+/// use pl_hlist::*;
+///
+/// #[derive(HListSupport)]
+/// struct RateLimit {
+///     x_ratelimit_limit: u32,
+///     x_ratelimit_remaining: u32,
+/// }
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("X-RateLimit-Limit", "100".parse().unwrap());
+/// headers.insert("X-RateLimit-Remaining", "42".parse().unwrap());
+///
+/// let limit = RateLimit::from_labelled_hlist(FromHeaders::from_headers(&headers).unwrap());
+/// assert_eq!(limit.x_ratelimit_remaining, 42);
+/// ```
+pub trait FromHeaders: Sized {
+    /// Parses this list from the headers in the given map.  Headers that do not
+    /// correspond to a field are ignored.
+    fn from_headers(headers: &HeaderMap) -> Result<Self, HeaderFieldError>;
+}
+
+impl FromHeaders for HNil {
+    fn from_headers(_: &HeaderMap) -> Result<Self, HeaderFieldError> {
+        Ok(HNil)
+    }
+}
+
+impl<L, V, T> FromHeaders for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: FromStr,
+    V::Err: fmt::Display,
+    T: HList + FromHeaders,
+{
+    fn from_headers(headers: &HeaderMap) -> Result<Self, HeaderFieldError> {
+        let header = header_name(L::NAME);
+        let value = match headers.get(header.as_str()) {
+            Some(value) => value,
+            None => return Err(HeaderFieldError::Missing { header }),
+        };
+        let parsed = value
+            .to_str()
+            .map_err(|err| err.to_string())
+            .and_then(|s| s.parse::<V>().map_err(|err| err.to_string()));
+        let head = match parsed {
+            Ok(head) => head,
+            Err(message) => return Err(HeaderFieldError::Invalid { header, message }),
+        };
+        let tail = T::from_headers(headers)?;
+        Ok(HCons(Field::new(head), tail))
+    }
+}
+
+/// Allows for rendering a labelled `HList` into a `HeaderMap`, formatting each field
+/// with `Display`.  Header names are derived from labels as for `FromHeaders`.
+pub trait ToHeaders {
+    /// Inserts one header into `headers` for each field in this list, replacing any
+    /// existing values for those headers.
+    fn insert_into_headers(&self, headers: &mut HeaderMap) -> Result<(), HeaderFieldError>;
+
+    /// Renders this list into a new `HeaderMap`.
+    fn to_headers(&self) -> Result<HeaderMap, HeaderFieldError> {
+        let mut headers = HeaderMap::new();
+        self.insert_into_headers(&mut headers)?;
+        Ok(headers)
+    }
+}
+
+impl ToHeaders for HNil {
+    fn insert_into_headers(&self, _: &mut HeaderMap) -> Result<(), HeaderFieldError> {
+        Ok(())
+    }
+}
+
+impl<L, V, T> ToHeaders for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: fmt::Display,
+    T: HList + ToHeaders,
+{
+    fn insert_into_headers(&self, headers: &mut HeaderMap) -> Result<(), HeaderFieldError> {
+        let header = header_name(L::NAME);
+        let name = HeaderName::from_bytes(header.as_bytes());
+        let value = HeaderValue::from_str(&self.0.value.to_string());
+        match (name, value) {
+            (Ok(name), Ok(value)) => {
+                headers.insert(name, value);
+            }
+            _ => return Err(HeaderFieldError::Unrepresentable { header }),
+        }
+        self.1.insert_into_headers(headers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Tracing {
+        x_request_id: String,
+        x_retry_count: u8,
+    }
+
+    type TracingHList =
+        HCons<Field<label!(x_request_id), String>, HCons<Field<label!(x_retry_count), u8>, HNil>>;
+
+    #[test]
+    fn struct_should_round_trip_through_headers() {
+        let tracing = Tracing {
+            x_request_id: "abc".to_string(),
+            x_retry_count: 2,
+        };
+        let headers = tracing.to_labelled_hlist().to_headers().unwrap();
+        assert_eq!(headers["X-Request-Id"], "abc");
+        assert_eq!(headers["x-retry-count"], "2");
+
+        let hlist = TracingHList::from_headers(&headers).unwrap();
+        assert_eq!(Tracing::from_labelled_hlist(hlist), tracing);
+    }
+
+    #[test]
+    fn errors_should_name_the_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("abc"));
+        let err = TracingHList::from_headers(&headers).unwrap_err();
+        assert_eq!(err.to_string(), "missing header `x-retry-count`");
+
+        headers.insert("x-retry-count", HeaderValue::from_static("many"));
+        let err = TracingHList::from_headers(&headers).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value for header `x-retry-count`: invalid digit found in string"
+        );
+
+        let hlist: HCons<Field<label!(x_request_id), &str>, HNil> =
+            HCons(Field::new("line\nbreak"), HNil);
+        let err = hlist.to_headers().unwrap_err();
+        assert_eq!(err.header(), "x-request-id");
+    }
+}
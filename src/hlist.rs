@@ -7,6 +7,11 @@
 //
 
 /// A heterogeneous list that can hold elements of different types.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not an `HList`",
+    label = "expected an `HList` built from `HCons` and `HNil`",
+    note = "a struct that derives `HListSupport` can be converted with `into_hlist()`"
+)]
 pub trait HList {
     /// Creates a new `HCons` with the given `X` value in head position.
     fn cons<X>(self, x: X) -> HCons<X, Self>
@@ -18,30 +23,52 @@ pub trait HList {
 }
 
 /// An empty `HList` used as the terminal element.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct HNil;
 
 impl HList for HNil {}
 
 /// The "cons" of a head element of type `H` and a tail `HList`.
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+///
+/// When an `HCons` is dropped, its head is dropped before its tail, so the elements of
+/// an `HList` are always dropped in order from first to last (like the fields of a
+/// struct or tuple).  Use `into_parts`, `into_prefix`, or `drop_reversed` to control the
+/// order explicitly.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct HCons<H, T: HList>(pub H, pub T);
 
 impl<H, T: HList> HCons<H, T> {
     /// Returns a reference to the head element of this list.
-    pub fn head(&self) -> &H {
+    pub const fn head(&self) -> &H {
         &self.0
     }
 
     /// Returns a reference to the tail of this list.
-    pub fn tail(&self) -> &T {
+    pub const fn tail(&self) -> &T {
         &self.1
     }
+
+    /// Consumes this list, returning the head and tail separately.
+    pub fn into_parts(self) -> (H, T) {
+        (self.0, self.1)
+    }
 }
 
 impl<H, T: HList> HList for HCons<H, T> {}
 
+// An `HList` whose elements are in descending order of alignment has the same layout as
+// the equivalent tuple; these guard against changes that would add overhead.
+assert_same_layout!(HNil, ());
+assert_same_layout!(HCons<u8, HNil>, (u8,));
+assert_same_layout!(hlist_type!(u32, u32, u32, u32), (u32, u32, u32, u32));
+assert_same_layout!(hlist_type!(u64, u32, u16, u8), (u64, u32, u16, u8));
+assert_same_layout!(hlist_type!(&str, usize, bool), (&str, usize, bool));
+
 /// Allows for conversion from an `HList` to an instance of the `Self` type.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be built from `{H}`",
+    note = "derive `HListSupport` for the struct, and check that the element types match its field types, in order"
+)]
 pub trait FromHList<H>
 where
     H: HList,
@@ -50,6 +77,10 @@ where
 }
 
 /// Allows for copying the contents of `Self` into an `HList`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be copied into `{H}`",
+    note = "derive `HListSupport` for the struct, and check that the element types match its field types, in order"
+)]
 pub trait ToHList<H>
 where
     H: HList,
@@ -58,6 +89,10 @@ where
 }
 
 /// Allows for converting (and consuming) `Self` into an `HList`.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be converted into `{H}`",
+    note = "derive `HListSupport` for the struct, and check that the element types match its field types, in order"
+)]
 pub trait IntoHList<H>
 where
     H: HList,
@@ -66,6 +101,12 @@ where
 }
 
 /// Trait to append two HLists together.
+///
+/// Appending is resolved entirely at compile time: each element is moved directly
+/// into its position in the output, and every impl is `#[inline(always)]`, so
+/// `hlist!(a, b).append(hlist!(c, d))` compiles down to the same code as
+/// `hlist!(a, b, c, d)`.  `ci/check_append_codegen.sh` checks this by comparing the
+/// optimized assembly of the two, and `benches/append.rs` compares their timings.
 pub trait Append<RHS> {
     type Output: HList;
     fn append(self, rhs: RHS) -> Self::Output;
@@ -74,28 +115,135 @@ pub trait Append<RHS> {
 /// Base case: Appending HNil to another HList returns the second HList.
 impl<RHS: HList> Append<RHS> for HNil {
     type Output = RHS;
+
+    #[inline(always)]
     fn append(self, rhs: RHS) -> Self::Output {
         rhs
     }
 }
 
-/// Recursive case: Appending HCons to another HList.
-impl<H, T: HList, RHS: HList> Append<RHS> for HCons<H, T>
+/// Recursive case: Appending HCons to another HList moves the head and appends the
+/// tail.
+impl<H, T, RHS> Append<RHS> for HCons<H, T>
 where
-    T: Append<RHS>,
-    T::Output: HList, // Ensure the recursive output is still an HList
+    T: HList + Append<RHS>,
+    RHS: HList,
 {
     type Output = HCons<H, T::Output>;
 
+    #[inline(always)]
     fn append(self, rhs: RHS) -> Self::Output {
         HCons(self.0, self.1.append(rhs))
     }
 }
 
+/// Trait to prepend another HList to the front of an HList; the counterpart of `Append`
+/// (so `b.prepend(a)` is `a.append(b)`).
+pub trait Prepend<LHS> {
+    type Output: HList;
+    fn prepend(self, lhs: LHS) -> Self::Output;
+}
+
+impl<LHS: Append<RHS>, RHS> Prepend<LHS> for RHS {
+    type Output = LHS::Output;
+
+    #[inline(always)]
+    fn prepend(self, lhs: LHS) -> Self::Output {
+        lhs.append(self)
+    }
+}
+
+/// Trait to reverse the order of the elements in an HList.
+///
+/// For lists of up to 16 elements, the inherent `const_reverse` method provides the same
+/// operation in const contexts.
+pub trait Reverse {
+    type Output: HList;
+    fn reverse(self) -> Self::Output;
+}
+
+impl<L: ReverseOnto<HNil>> Reverse for L {
+    type Output = L::Output;
+
+    #[inline(always)]
+    fn reverse(self) -> Self::Output {
+        self.reverse_onto(HNil)
+    }
+}
+
+/// Helper for `Reverse` that moves the elements of an HList, in reverse order, onto
+/// the front of the accumulated list `Acc`.
+#[doc(hidden)]
+pub trait ReverseOnto<Acc> {
+    type Output: HList;
+    fn reverse_onto(self, acc: Acc) -> Self::Output;
+}
+
+impl<Acc: HList> ReverseOnto<Acc> for HNil {
+    type Output = Acc;
+
+    #[inline(always)]
+    fn reverse_onto(self, acc: Acc) -> Self::Output {
+        acc
+    }
+}
+
+impl<H, T, Acc> ReverseOnto<Acc> for HCons<H, T>
+where
+    T: HList + ReverseOnto<HCons<H, Acc>>,
+    Acc: HList,
+{
+    type Output = T::Output;
+
+    #[inline(always)]
+    fn reverse_onto(self, acc: Acc) -> Self::Output {
+        let HCons(head, tail) = self;
+        tail.reverse_onto(HCons(head, acc))
+    }
+}
+
+/// Trait to flatten an HList of HLists into a single HList containing the elements of
+/// each inner list, in order (so `hlist!(hlist!(1, 2), hlist!("a")).flatten()` is
+/// `hlist!(1, 2, "a")`).
+///
+/// Each call removes one level of nesting, and every element must itself be an HList;
+/// there is no way to tell an HList element from any other element on stable Rust, so
+/// deeper nesting is flattened by calling `flatten` again.  This is useful for feeding
+/// the `HList`s of several derived structs to an element-wise consumer as one record.
+pub trait Flatten {
+    type Output: HList;
+    fn flatten(self) -> Self::Output;
+}
+
+impl Flatten for HNil {
+    type Output = HNil;
+
+    #[inline(always)]
+    fn flatten(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<H, T> Flatten for HCons<H, T>
+where
+    H: HList + Append<T::Output>,
+    T: HList + Flatten,
+{
+    type Output = H::Output;
+
+    #[inline(always)]
+    fn flatten(self) -> Self::Output {
+        let HCons(head, tail) = self;
+        head.append(tail.flatten())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::labelled::*;
+    use crate::layout::*;
+    use crate::view::*;
     use pl_hlist_derive::HListSupport;
 
     #[test]
@@ -104,6 +252,13 @@ mod tests {
         assert_eq!(*hlist.head(), 1u8);
     }
 
+    #[test]
+    fn default_should_default_each_element() {
+        let hlist: HCons<u8, HCons<String, HCons<bool, HNil>>> = Default::default();
+        assert_eq!(hlist, hlist!(0u8, String::new(), false));
+        assert_eq!(<HCons<u8, HNil>>::default().tail(), &HNil);
+    }
+
     #[test]
     fn tail_should_work() {
         let hlist = HCons(1u8, HNil);
@@ -129,6 +284,70 @@ mod tests {
             let hlist2 = hlist!(1u8, 2i32, "three");
             assert_eq!(hlist1, hlist2);
         }
+
+        {
+            let hlist_pat!() = HNil;
+            let hlist_pat!(a, b, c,) = hlist!(1u8, 2i32, "three");
+            assert_eq!((a, b, c), (1u8, 2i32, "three"));
+
+            let hlist_pat!(a, ...rest) = hlist!(1u8, 2i32, "three");
+            assert_eq!(a, 1u8);
+            assert_eq!(rest, hlist!(2i32, "three"));
+
+            let hlist_pat!(_, _, ...rest) = hlist!(1u8, 2i32);
+            assert_eq!(rest, HNil);
+        }
+
+        {
+            let hlist1: HList![] = HNil;
+            let hlist2: HList![u8, i32, &str,] = hlist!(1u8, 2i32, "three");
+            let hlist3: HList![u8, ...HList![i32, &str]] = hlist2;
+            assert_eq!(hlist1, hlist!());
+            assert_eq!(hlist3, HCons(1u8, HCons(2i32, HCons("three", HNil))));
+        }
+    }
+
+    trait Bump {
+        fn bump(&mut self, by: u32);
+        fn total(self, scale: u32) -> u32;
+    }
+
+    impl Bump for u32 {
+        fn bump(&mut self, by: u32) {
+            *self += by;
+        }
+
+        fn total(self, scale: u32) -> u32 {
+            self * scale
+        }
+    }
+
+    impl_hlist_trait! {
+        Bump {
+            fn bump(&mut self, by: u32);
+            fn total(self, scale: u32) -> u32 {
+                empty => 0,
+                combine(head, tail) => head + tail,
+            }
+        }
+    }
+
+    #[test]
+    fn impl_hlist_trait_should_recurse_through_elements() {
+        let mut hlist = hlist!(1u32, 2u32, 3u32);
+        hlist.bump(10);
+        assert_eq!(hlist, hlist!(11, 12, 13));
+        assert_eq!(hlist.total(2), 72);
+        assert_eq!(HNil.total(2), 0);
+    }
+
+    #[test]
+    fn prepend_should_be_the_counterpart_of_append() {
+        assert_eq!(
+            hlist!(3u8, 4u16).prepend(hlist!(1u32, 2u64)),
+            hlist!(1u32, 2u64, 3u8, 4u16)
+        );
+        assert_eq!(HNil.prepend(hlist!(1u8)), hlist!(1u8));
     }
 
     #[derive(Debug, PartialEq, Eq, Clone, HListSupport)]
@@ -167,7 +386,244 @@ mod tests {
         }
     }
 
-    
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    #[hlist(views)]
+    struct TestTupleStruct(u8, &'static str);
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    struct TestUnitStruct;
+
+    #[test]
+    fn converting_tuple_and_unit_structs_to_from_hlist_should_work() {
+        let s = TestTupleStruct::from_hlist(hlist!(1u8, "two"));
+        assert_eq!(s, TestTupleStruct(1, "two"));
+        assert_eq!(s.to_hlist(), hlist!(1u8, "two"));
+        assert_eq!(s.hlist_view(), hlist!(&1u8, &"two"));
+        assert_eq!(s.into_hlist(), hlist!(1u8, "two"));
+
+        assert_eq!(TestUnitStruct::from_hlist(HNil), TestUnitStruct);
+        assert_eq!(TestUnitStruct.to_hlist(), HNil);
+        assert_eq!(TestUnitStruct.into_hlist(), HNil);
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    struct TestGenericStruct<'a, A, B: Copy>
+    where
+        A: PartialEq,
+    {
+        a: A,
+        b: B,
+        name: &'a str,
+    }
+
+    #[test]
+    fn converting_generic_structs_to_from_hlist_should_work() {
+        let s = TestGenericStruct::from_hlist(hlist!(vec![1u8], 2i32, "three"));
+        assert_eq!(s.a, vec![1u8]);
+        assert_eq!(s.to_hlist(), hlist!(vec![1u8], 2i32, "three"));
+
+        let labelled = s.to_labelled_hlist();
+        assert_eq!(labelled.tail().head().name(), "b");
+        assert_eq!(TestGenericStruct::from_labelled_hlist(labelled), s);
+        assert_eq!(s.into_hlist(), hlist!(vec![1u8], 2i32, "three"));
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    #[hlist(layout, views)]
+    struct TestSkippingStruct {
+        id: u32,
+        #[hlist(skip)]
+        cache: Option<u32>,
+        name: &'static str,
+        #[hlist(default = "16")]
+        capacity: usize,
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    struct TestSkippingTupleStruct(u8, #[hlist(default)] Vec<u8>, bool);
+
+    #[test]
+    fn skipped_fields_should_be_excluded_from_hlist() {
+        let s = TestSkippingStruct::from_hlist(hlist!(1u32, "one"));
+        assert_eq!(
+            s,
+            TestSkippingStruct {
+                id: 1,
+                cache: None,
+                name: "one",
+                capacity: 16,
+            }
+        );
+        assert_eq!(s.hlist_view(), hlist!(&1u32, &"one"));
+
+        let labelled = s.to_labelled_hlist();
+        assert_eq!(labelled.tail().head().name(), "name");
+        let same_layouts = [TestSkippingStruct::SAME_LAYOUT];
+        assert_eq!(same_layouts, [false]);
+
+        let s = TestSkippingStruct {
+            cache: Some(7),
+            capacity: 32,
+            ..s
+        };
+        assert_eq!(s.to_hlist(), hlist!(1u32, "one"));
+        assert_eq!(
+            TestSkippingStruct::from_labelled_hlist(labelled).capacity,
+            16
+        );
+        assert_eq!(s.into_hlist(), hlist!(1u32, "one"));
+
+        let t = TestSkippingTupleStruct::from_hlist(hlist!(2u8, true));
+        assert_eq!(t, TestSkippingTupleStruct(2, vec![], true));
+        assert_eq!(
+            TestSkippingTupleStruct(2, vec![3], true).into_hlist(),
+            hlist!(2u8, true)
+        );
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    #[hlist(labels)]
+    struct HTTPServer {
+        port: u16,
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    #[hlist(labels = "camel_http_server_labels")]
+    struct HttpServer {
+        host: &'static str,
+    }
+
+    #[test]
+    fn labels_module_should_be_renamable() {
+        let labelled: HList![Field<http_server_labels::port, u16>] =
+            HTTPServer { port: 80 }.into_labelled_hlist();
+        assert_eq!(labelled.head().name(), "port");
+        let labelled: HList![Field<camel_http_server_labels::host, &str>] =
+            HttpServer { host: "localhost" }.into_labelled_hlist();
+        assert_eq!(labelled.head().name(), "host");
+    }
+
+    // Without `#[hlist(labels)]`, the marker types for long names go in a hidden module
+    // named after the exact struct name, so these don't collide
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    struct HTTPClient {
+        a_field_name_longer_than_thirty_two_bytes: u8,
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    struct HttpClient {
+        a_field_name_longer_than_thirty_two_bytes: u8,
+    }
+
+    #[test]
+    fn hidden_labels_modules_should_not_collide() {
+        let labelled = HTTPClient {
+            a_field_name_longer_than_thirty_two_bytes: 1,
+        }
+        .into_labelled_hlist();
+        assert_eq!(
+            labelled.head().name(),
+            "a_field_name_longer_than_thirty_two_bytes"
+        );
+        let labelled = HttpClient {
+            a_field_name_longer_than_thirty_two_bytes: 2,
+        }
+        .into_labelled_hlist();
+        assert_eq!(labelled.head().value, 2);
+    }
+
+    #[derive(Debug, PartialEq, Clone, HListSupport)]
+    #[hlist(tuple)]
+    struct TestTupleConvertingStruct<T> {
+        id: T,
+        #[hlist(skip)]
+        cache: Option<u32>,
+        name: &'static str,
+    }
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    #[hlist(tuple)]
+    struct TestTupleConvertingUnitStruct;
+
+    #[test]
+    fn tuple_conversions_should_be_derived_on_request() {
+        let s = TestTupleConvertingStruct::from((1u8, "one"));
+        assert_eq!(
+            s,
+            TestTupleConvertingStruct {
+                id: 1u8,
+                cache: None,
+                name: "one",
+            }
+        );
+        let tuple: (u8, &str) = TestTupleConvertingStruct {
+            cache: Some(3),
+            ..s
+        }
+        .into();
+        assert_eq!(tuple, (1u8, "one"));
+
+        let unit: TestTupleConvertingUnitStruct = ().into();
+        let () = unit.into();
+    }
+
+    fn reversed<L: Reverse>(hlist: L) -> L::Output {
+        hlist.reverse()
+    }
+
+    #[test]
+    fn reverse_should_reverse_elements() {
+        assert_eq!(reversed(HNil), HNil);
+        assert_eq!(reversed(hlist!(1u8)), hlist!(1u8));
+        assert_eq!(reversed(hlist!(1, "a", 3.0)), hlist!(3.0, "a", 1));
+
+        // Building a list by repeated `cons` yields the elements in reverse order
+        let built = HNil.cons(1u8).cons("two").cons(3.0f32);
+        assert_eq!(Reverse::reverse(built), hlist!(1u8, "two", 3.0f32));
+
+        let wide = hlist!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17u8);
+        assert_eq!(
+            wide.reverse(),
+            hlist!(17u8, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1)
+        );
+    }
+
+    #[test]
+    fn flatten_should_concatenate_inner_lists() {
+        assert_eq!(HNil.flatten(), HNil);
+        assert_eq!(hlist!(HNil, HNil).flatten(), HNil);
+        assert_eq!(
+            hlist!(hlist!(1, 2), HNil, hlist!("a")).flatten(),
+            hlist!(1, 2, "a")
+        );
+
+        // Deeper nesting is removed one level at a time
+        let nested = hlist!(hlist!(hlist!(1u8), hlist!('b')), hlist!(hlist!("c", 4.0)));
+        assert_eq!(nested.flatten().flatten(), hlist!(1u8, 'b', "c", 4.0));
+
+        #[derive(Clone, HListSupport)]
+        struct Inner {
+            a: u8,
+            b: &'static str,
+        }
+
+        #[derive(Clone, HListSupport)]
+        struct Outer {
+            inner: Inner,
+            c: bool,
+        }
+
+        let outer = Outer {
+            inner: Inner { a: 1, b: "two" },
+            c: true,
+        };
+        let HCons(inner, rest) = outer.into_hlist();
+        assert_eq!(
+            hlist!(inner.into_hlist(), rest).flatten(),
+            hlist!(1u8, "two", true)
+        );
+    }
+
     #[test]
     fn append_hnil_to_hnil() {
         let list1 = HNil;
@@ -184,7 +640,6 @@ mod tests {
         let list2 = HNil;
         let result = list1.append(list2);
 
-      
         assert_eq!(format!("{:?}", result), "HCons(42, HNil)");
     }
 
@@ -194,7 +649,6 @@ mod tests {
         let list2 = HCons("hello", HNil);
         let result = list1.append(list2);
 
-     
         assert_eq!(format!("{:?}", result), "HCons(42, HCons(\"hello\", HNil))");
     }
 
@@ -204,11 +658,29 @@ mod tests {
         let list2 = HCons(3, HCons(4, HNil));
         let result = list1.append(list2);
 
-      
         assert_eq!(
             format!("{:?}", result),
             "HCons(1, HCons(2, HCons(3, HCons(4, HNil))))"
         );
-}
+    }
 
+    fn append_to_generic_tail<H, T, R>(hlist: HCons<H, T>, rhs: R) -> HCons<H, T::Output>
+    where
+        T: HList + Append<R>,
+        R: HList,
+    {
+        hlist.append(rhs)
+    }
+
+    #[test]
+    fn append_should_work_through_a_generic_tail() {
+        assert_eq!(
+            append_to_generic_tail(hlist!(1u8), hlist!("a")),
+            hlist!(1u8, "a")
+        );
+        assert_eq!(
+            append_to_generic_tail(hlist!(1u8, 2u16, 3u32), HNil),
+            hlist!(1u8, 2u16, 3u32)
+        );
+    }
 }
@@ -0,0 +1,232 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+use crate::labelled::*;
+
+/// A heterogeneous map whose entries are keyed by type, stored as a labelled `HList` of
+/// `Field<Key, Value>` entries.
+///
+/// Keys are usually zero-sized marker types or `label!` names.  Inserting an entry
+/// changes the type of the map, so `get` and `remove` only compile if the key is
+/// present, and the value they return has the type that was inserted under that key.
+/// This makes an `HMap` a convenient way to thread typed context through a stack of
+/// middleware, where each layer adds the entries that later layers rely on.  Each key
+/// should appear at most once; looking up a key that appears more than once is
+/// ambiguous and fails to compile.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// struct RequestId;
+/// struct User;
+///
+/// fn authenticate<E: HList>(ctx: HMap<E>) -> HMap<HCons<Field<User, &'static str>, E>> {
+///     ctx.insert::<User, _>("alice")
+/// }
+///
+/// fn handle<E, I, J>(ctx: &HMap<E>) -> String
+/// where
+///     E: HList + KeySelector<RequestId, I, Value = u64> + KeySelector<User, J, Value = &'static str>,
+/// {
+///     format!("request {} from {}", ctx.get::<RequestId, _>(), ctx.get::<User, _>())
+/// }
+///
+/// # fn main() {
+/// let ctx = authenticate(HMap::new().insert::<RequestId, _>(7u64));
+/// assert_eq!(handle(&ctx), "request 7 from alice");
+///
+/// let (user, ctx) = ctx.remove::<User, _>();
+/// assert_eq!(user, "alice");
+/// assert_eq!(*ctx.get::<RequestId, _>(), 7);
+/// # }
+/// ```
+///
+/// Looking up a key that is not in the map does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// struct RequestId;
+/// struct User;
+///
+/// let ctx = HMap::new().insert::<RequestId, _>(7u64);
+/// ctx.get::<User, _>();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct HMap<E: HList> {
+    entries: E,
+}
+
+impl HMap<HNil> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        HMap { entries: HNil }
+    }
+}
+
+impl<E: HList> HMap<E> {
+    /// Creates a map from a labelled `HList` of entries.
+    pub fn from_hlist(entries: E) -> Self {
+        HMap { entries }
+    }
+
+    /// Adds an entry with key `K`, returning the extended map.
+    pub fn insert<K, V>(self, value: V) -> HMap<HCons<Field<K, V>, E>> {
+        HMap::from_hlist(HCons(Field::new(value), self.entries))
+    }
+
+    /// Returns a reference to the value stored under key `K`.  This only compiles if
+    /// the map contains the key.
+    pub fn get<K, Index>(&self) -> &E::Value
+    where
+        E: KeySelector<K, Index>,
+    {
+        self.entries.get_key()
+    }
+
+    /// Returns a mutable reference to the value stored under key `K`.
+    pub fn get_mut<K, Index>(&mut self) -> &mut E::Value
+    where
+        E: KeySelector<K, Index>,
+    {
+        self.entries.get_key_mut()
+    }
+
+    /// Removes the entry with key `K`, returning its value along with the reduced map.
+    pub fn remove<K, Index>(self) -> (E::Value, HMap<E::Remainder>)
+    where
+        E: KeyPlucker<K, Index>,
+    {
+        let (value, rest) = self.entries.pluck_key();
+        (value, HMap::from_hlist(rest))
+    }
+
+    /// Returns the entries in this map.
+    pub fn as_hlist(&self) -> &E {
+        &self.entries
+    }
+
+    /// Consumes this map, returning its entries.
+    pub fn into_hlist(self) -> E {
+        self.entries
+    }
+}
+
+/// Looks up the value stored under key `K` in a labelled `HList`.
+#[diagnostic::on_unimplemented(
+    message = "key `{K}` is not in this map",
+    label = "no entry with key `{K}`",
+    note = "the full entry list type is named by the outermost `required for` note below"
+)]
+pub trait KeySelector<K, Index> {
+    /// The type of the value stored under `K`.
+    type Value;
+
+    /// Returns a reference to the value stored under `K`.
+    fn get_key(&self) -> &Self::Value;
+
+    /// Returns a mutable reference to the value stored under `K`.
+    fn get_key_mut(&mut self) -> &mut Self::Value;
+}
+
+impl<K, V, Tail> KeySelector<K, Here> for HCons<Field<K, V>, Tail>
+where
+    Tail: HList,
+{
+    type Value = V;
+
+    #[inline(always)]
+    fn get_key(&self) -> &V {
+        &self.0.value
+    }
+
+    #[inline(always)]
+    fn get_key_mut(&mut self) -> &mut V {
+        &mut self.0.value
+    }
+}
+
+impl<H, Tail, K, TailIndex> KeySelector<K, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + KeySelector<K, TailIndex>,
+{
+    type Value = Tail::Value;
+
+    #[inline(always)]
+    fn get_key(&self) -> &Self::Value {
+        self.1.get_key()
+    }
+
+    #[inline(always)]
+    fn get_key_mut(&mut self) -> &mut Self::Value {
+        self.1.get_key_mut()
+    }
+}
+
+/// Removes the entry with key `K` from a labelled `HList`.
+#[diagnostic::on_unimplemented(
+    message = "key `{K}` is not in this map",
+    label = "no entry with key `{K}`",
+    note = "the full entry list type is named by the outermost `required for` note below"
+)]
+pub trait KeyPlucker<K, Index>: KeySelector<K, Index> {
+    /// The entries that remain after removing `K`.
+    type Remainder: HList;
+
+    /// Removes the entry with key `K`, returning its value and the remaining entries.
+    fn pluck_key(self) -> (Self::Value, Self::Remainder);
+}
+
+impl<K, V, Tail> KeyPlucker<K, Here> for HCons<Field<K, V>, Tail>
+where
+    Tail: HList,
+{
+    type Remainder = Tail;
+
+    #[inline(always)]
+    fn pluck_key(self) -> (V, Tail) {
+        (self.0.value, self.1)
+    }
+}
+
+impl<H, Tail, K, TailIndex> KeyPlucker<K, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + KeyPlucker<K, TailIndex>,
+{
+    type Remainder = HCons<H, Tail::Remainder>;
+
+    #[inline(always)]
+    fn pluck_key(self) -> (Self::Value, Self::Remainder) {
+        let (value, rest) = self.1.pluck_key();
+        (value, HCons(self.0, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Retries;
+    struct Timeout;
+
+    #[test]
+    fn hmap_should_insert_get_and_remove_entries() {
+        let mut map = HMap::new()
+            .insert::<Retries, _>(3u8)
+            .insert::<Timeout, _>(1.5f32)
+            .insert::<label!(path), _>("/index");
+        assert_eq!(*map.get::<Retries, _>(), 3);
+        assert_eq!(*map.get::<label!(path), _>(), "/index");
+
+        *map.get_mut::<Timeout, _>() *= 2.0;
+        let (timeout, map) = map.remove::<Timeout, _>();
+        assert_eq!(timeout, 3.0);
+
+        let (path, map) = map.remove::<label!(path), _>();
+        assert_eq!(path, "/index");
+        assert_eq!(map.into_hlist().strip_labels(), hlist!(3u8));
+    }
+}
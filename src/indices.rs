@@ -0,0 +1,20 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use core::marker::PhantomData;
+
+/// Type-level index referring to the head of an `HList`.
+///
+/// Index types are never constructed; they exist only so that the compiler can infer
+/// *where* in an `HList` a given element type lives (see `Plucker` and `Sculptor`).
+pub struct Here {
+    _priv: (),
+}
+
+/// Type-level index referring to a position in the tail of an `HList`, where `T` is the
+/// index within the tail.
+pub struct There<T> {
+    _marker: PhantomData<T>,
+}
@@ -0,0 +1,397 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::sculptor::*;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+/// A type-level name for an element of a labelled `HList`.
+///
+/// Labels are normally `Name` types, written with the `label!` macro.  With
+/// `#[hlist(labels)]`, the `HListSupport` derive also emits an alias for the `Name` of
+/// each struct field.
+pub trait Label {
+    /// The name of the labelled element.
+    const NAME: &'static str;
+}
+
+/// A `Label` whose name is encoded in its const generic parameters, so that every label
+/// with the same name is the same type.
+///
+/// The name is stored as UTF-8 bytes packed little-endian into two `u128` words, which
+/// allows names of up to 32 bytes.  Use the `label!` macro rather than writing the
+/// words by hand.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// assert_eq!(<label!(user_id)>::NAME, "user_id");
+/// # }
+/// ```
+pub struct Name<const A: u128, const B: u128 = 0>;
+
+impl<const A: u128, const B: u128> Name<A, B> {
+    const BYTES: [u8; 32] = decode_name(A, B);
+}
+
+impl<const A: u128, const B: u128> Label for Name<A, B> {
+    const NAME: &'static str = name_str(&Self::BYTES);
+}
+
+/// The maximum length in bytes of the name of a `Name` label.
+pub const MAX_NAME_LEN: usize = 32;
+
+/// Encodes word `word` of the given label name, for use as a `Name` parameter.
+#[doc(hidden)]
+pub const fn encode_name(name: &str, word: usize) -> u128 {
+    // `label!(r#type)` names the field `type`
+    let bytes = match name.as_bytes() {
+        [b'r', b'#', rest @ ..] => rest,
+        bytes => bytes,
+    };
+    assert!(
+        bytes.len() <= MAX_NAME_LEN,
+        "label names are limited to 32 bytes"
+    );
+    let mut encoded = 0u128;
+    let mut i = 0;
+    while i < 16 && word * 16 + i < bytes.len() {
+        encoded |= (bytes[word * 16 + i] as u128) << (i * 8);
+        i += 1;
+    }
+    encoded
+}
+
+const fn decode_name(a: u128, b: u128) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    let mut i = 0;
+    while i < 16 {
+        bytes[i] = (a >> (i * 8)) as u8;
+        bytes[i + 16] = (b >> (i * 8)) as u8;
+        i += 1;
+    }
+    bytes
+}
+
+const fn name_str(bytes: &'static [u8; 32]) -> &'static str {
+    let mut len = 0;
+    while len < bytes.len() && bytes[len] != 0 {
+        len += 1;
+    }
+    match core::str::from_utf8(bytes.split_at(len).0) {
+        Ok(name) => name,
+        Err(_) => panic!("label name is not valid UTF-8"),
+    }
+}
+
+/// A value tagged with a type-level `Label`.  A labelled `HList` is an `HList` whose
+/// elements are all `Field`s.
+pub struct Field<L, T> {
+    pub value: T,
+    label: PhantomData<L>,
+}
+
+// The label is a zero-sized marker, so a `Field` has the same layout as its value.
+assert_same_layout!(Field<(), u8>, u8);
+assert_same_layout!(Field<(), [u64; 3]>, [u64; 3]);
+
+impl<L, T> Field<L, T> {
+    /// Creates a new `Field` holding the given value.
+    pub fn new(value: T) -> Self {
+        Field {
+            value,
+            label: PhantomData,
+        }
+    }
+
+    /// Consumes this `Field`, returning the underlying value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<L: Label, T> Field<L, T> {
+    /// Returns the name of this field's label.
+    pub fn name(&self) -> &'static str {
+        L::NAME
+    }
+}
+
+impl<L, T: Clone> Clone for Field<L, T> {
+    fn clone(&self) -> Self {
+        Field::new(self.value.clone())
+    }
+}
+
+impl<L, T: Copy> Copy for Field<L, T> {}
+
+impl<L: Label, T: fmt::Debug> fmt::Debug for Field<L, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("name", &L::NAME)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<L, T: PartialEq> PartialEq for Field<L, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<L, T: Eq> Eq for Field<L, T> {}
+
+impl<L, T: PartialOrd> PartialOrd for Field<L, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<L, T: Ord> Ord for Field<L, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<L, T: Hash> Hash for Field<L, T> {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.value.hash(state)
+    }
+}
+
+/// Allows for conversion from a labelled `HList` to an instance of the `Self` type.
+pub trait FromLabelledHList<H>
+where
+    H: HList,
+{
+    fn from_labelled_hlist(hlist: H) -> Self;
+}
+
+/// Allows for copying the contents of `Self` into a labelled `HList`.
+pub trait ToLabelledHList<H>
+where
+    H: HList,
+{
+    fn to_labelled_hlist(&self) -> H;
+}
+
+/// Allows for converting (and consuming) `Self` into a labelled `HList`.
+pub trait IntoLabelledHList<H>
+where
+    H: HList,
+{
+    fn into_labelled_hlist(self) -> H;
+}
+
+/// Allows for wrapping each element of an `HList` in a `Field`, producing a labelled
+/// `HList`.  The labels are determined by the `Output` type.
+pub trait IntoFields<Output> {
+    fn into_fields(self) -> Output;
+}
+
+impl IntoFields<HNil> for HNil {
+    #[inline]
+    fn into_fields(self) -> HNil {
+        HNil
+    }
+}
+
+impl<H, T, L, Rest> IntoFields<HCons<Field<L, H>, Rest>> for HCons<H, T>
+where
+    T: HList + IntoFields<Rest>,
+    Rest: HList,
+{
+    #[inline]
+    fn into_fields(self) -> HCons<Field<L, H>, Rest> {
+        HCons(Field::new(self.0), self.1.into_fields())
+    }
+}
+
+/// Allows for unwrapping each `Field` of a labelled `HList`, producing an `HList` of the
+/// underlying values.
+pub trait StripLabels {
+    type Output: HList;
+    fn strip_labels(self) -> Self::Output;
+}
+
+impl StripLabels for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn strip_labels(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<L, H, T> StripLabels for HCons<Field<L, H>, T>
+where
+    T: HList + StripLabels,
+{
+    type Output = HCons<H, T::Output>;
+
+    #[inline]
+    fn strip_labels(self) -> Self::Output {
+        HCons(self.0.value, self.1.strip_labels())
+    }
+}
+
+/// Converts `source` into a `Target` whose fields are a subset of the source's fields,
+/// matching them by name rather than by position.  Source fields that the target lacks
+/// are dropped.
+///
+/// Each target field must have a source field with the same name and type; otherwise the
+/// conversion fails to compile.  See `Transmogrifier` for conversions whose field types
+/// differ structurally.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(HListSupport)]
+/// struct UserDto {
+///     email: String,
+///     id: u64,
+///     etag: String,
+/// }
+///
+/// #[derive(HListSupport)]
+/// struct User {
+///     id: u64,
+///     email: String,
+/// }
+///
+/// let dto = UserDto { email: "kim@example.com".to_string(), id: 7, etag: "abc".to_string() };
+/// let user: User = transform_from(dto);
+/// assert_eq!((user.id, user.email.as_str()), (7, "kim@example.com"));
+/// ```
+#[inline]
+pub fn transform_from<Target, Source, SourceFields, TargetFields, Indices>(source: Source) -> Target
+where
+    Source: IntoLabelledHList<SourceFields>,
+    Target: FromLabelledHList<TargetFields>,
+    SourceFields: HList + Sculptor<TargetFields, Indices>,
+    TargetFields: HList,
+{
+    Target::from_labelled_hlist(source.into_labelled_hlist().sculpt().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Debug, PartialEq, Eq, Clone, HListSupport)]
+    #[hlist(labels)]
+    struct TestStruct {
+        byte_field: u8,
+        str_field: &'static str,
+    }
+
+    #[test]
+    fn field_should_expose_label_and_value() {
+        let field: Field<test_struct_labels::byte_field, u8> = Field::new(7);
+        assert_eq!(field.name(), "byte_field");
+        assert_eq!(field.value, 7);
+        assert_eq!(field.into_value(), 7);
+    }
+
+    #[derive(Debug, PartialEq, Eq, Clone, HListSupport)]
+    #[hlist(labels)]
+    struct TestRawStruct {
+        r#type: u8,
+        r#a_raw_field_name_longer_than_thirty_two: u8,
+    }
+
+    #[test]
+    fn raw_field_names_should_be_unprefixed() {
+        let labelled = TestRawStruct {
+            r#type: 1,
+            r#a_raw_field_name_longer_than_thirty_two: 2,
+        }
+        .into_labelled_hlist();
+        assert_eq!(labelled.head().name(), "type");
+        assert_eq!(
+            labelled.tail().head().name(),
+            "a_raw_field_name_longer_than_thirty_two"
+        );
+        assert_eq!(<label!(r#type)>::NAME, "type");
+        assert_same_type!(label!(r#type), test_raw_struct_labels::r#type);
+    }
+
+    #[test]
+    fn field_debug_should_include_name() {
+        let field: Field<test_struct_labels::str_field, _> = Field::new("x");
+        assert_eq!(
+            format!("{:?}", field),
+            "Field { name: \"str_field\", value: \"x\" }"
+        );
+    }
+
+    #[test]
+    fn converting_struct_to_from_labelled_hlist_should_work() {
+        let s = TestStruct {
+            byte_field: 1,
+            str_field: "two",
+        };
+
+        let hlist = s.to_labelled_hlist();
+        assert_eq!(hlist.head().name(), "byte_field");
+        assert_eq!(hlist.head().value, 1);
+        assert_eq!(hlist.tail().head().name(), "str_field");
+        assert_eq!(hlist.tail().head().value, "two");
+        assert_eq!(hlist, s.clone().into_labelled_hlist());
+
+        assert_eq!(TestStruct::from_labelled_hlist(hlist), s);
+    }
+
+    #[test]
+    fn labels_with_the_same_name_should_be_the_same_type() {
+        let field: Field<label!(byte_field), u8> = Field::new(7);
+        let field: Field<test_struct_labels::byte_field, u8> = field;
+        assert_eq!(field.name(), "byte_field");
+        assert_eq!(<label!(a)>::NAME, "a");
+        assert_eq!(
+            <label!(thirty_two_bytes_long_label_name)>::NAME,
+            "thirty_two_bytes_long_label_name"
+        );
+        assert_eq!(<Name<0>>::NAME, "");
+    }
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    struct ReorderedStruct {
+        str_field: &'static str,
+        byte_field: u8,
+    }
+
+    #[test]
+    fn transform_from_should_match_fields_by_name() {
+        let s = TestStruct {
+            byte_field: 1,
+            str_field: "two",
+        };
+        let reordered: ReorderedStruct = transform_from(s.clone());
+        assert_eq!(
+            reordered,
+            ReorderedStruct {
+                str_field: "two",
+                byte_field: 1
+            }
+        );
+        assert_eq!(transform_from::<TestStruct, _, _, _, _>(reordered), s);
+    }
+
+    #[test]
+    fn into_fields_and_strip_labels_should_round_trip() {
+        use test_struct_labels::*;
+
+        type Labelled = HCons<Field<byte_field, u8>, HCons<Field<str_field, &'static str>, HNil>>;
+        let labelled: Labelled = hlist!(1u8, "two").into_fields();
+        assert_eq!(labelled.tail().head().name(), "str_field");
+        assert_eq!(labelled.strip_labels(), hlist!(1u8, "two"));
+    }
+}
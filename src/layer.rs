@@ -0,0 +1,49 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use tower_layer::Layer;
+
+/// An empty `HList` is the identity layer.
+impl<S> Layer<S> for HNil {
+    type Service = S;
+
+    fn layer(&self, inner: S) -> S {
+        inner
+    }
+}
+
+/// An `HList` of layers wraps the service in each layer in turn, with the first element
+/// as the outermost layer.
+impl<S, H, T> Layer<S> for HCons<H, T>
+where
+    T: HList + Layer<S>,
+    H: Layer<T::Service>,
+{
+    type Service = H::Service;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.0.layer(self.1.layer(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower_layer::{layer_fn, Identity};
+
+    #[derive(Debug, PartialEq)]
+    struct Named<S>(&'static str, S);
+
+    #[test]
+    fn hlist_of_layers_should_compose_outermost_first() {
+        let stack = hlist!(
+            layer_fn(|s| Named("outer", s)),
+            Identity::new(),
+            layer_fn(|s| Named("inner", s))
+        );
+        assert_eq!(stack.layer(()), Named("outer", Named("inner", ())));
+    }
+}
@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::mem::size_of;
+
+/// Describes whether `Self` has the same layout as the `HList` type `H`.  This is
+/// implemented by the `HListSupport` derive for structs marked with `#[hlist(layout)]`,
+/// and allows `Copy` structs to be converted to and from their `HList` representation
+/// with a single memory copy.
+///
+/// The fast path is only available with a nightly compiler, by building with
+/// `RUSTFLAGS="--cfg pl_hlist_specialization"`; otherwise (and whenever the layouts
+/// differ) conversions are always performed field by field.
+///
+/// # Safety
+///
+/// `SAME_LAYOUT` may only be `true` if `Self` and `H` have the same size, and each
+/// element of `H` has the same type and offset as the corresponding field of `Self`.
+pub unsafe trait HListLayout<H: HList> {
+    /// Whether `Self` and `H` have the same layout.
+    const SAME_LAYOUT: bool;
+}
+
+/// Returns the offset in bytes of `field` from `base`, for use in `HListLayout` impls.
+///
+/// # Safety
+///
+/// `field` must point within the allocation that `base` points to.
+#[doc(hidden)]
+pub const unsafe fn field_offset<T, F>(base: *const T, field: *const F) -> usize {
+    (field as *const u8).offset_from(base as *const u8) as usize
+}
+
+/// Returns true if `S` and `H` have the same size and each of the given pairs of
+/// offsets is equal, for use in `HListLayout` impls.
+#[doc(hidden)]
+pub const fn same_offsets<S, H>(offsets: &[(usize, usize)]) -> bool {
+    if size_of::<S>() != size_of::<H>() {
+        return false;
+    }
+    let mut i = 0;
+    while i < offsets.len() {
+        if offsets[i].0 != offsets[i].1 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// The conversions used by the `HListSupport` derive, which accept the field-by-field
+/// conversion as a fallback.  With specialization enabled, `Copy` structs whose layout
+/// matches their `HList` representation are copied directly instead.
+#[doc(hidden)]
+pub trait CopyConversions<H: HList>: HListLayout<H> + Sized {
+    fn to_hlist_with(&self, fields: impl FnOnce(&Self) -> H) -> H;
+    fn from_hlist_with(hlist: H, fields: impl FnOnce(H) -> Self) -> Self;
+}
+
+#[cfg(not(pl_hlist_specialization))]
+impl<S: HListLayout<H>, H: HList> CopyConversions<H> for S {
+    #[inline(always)]
+    fn to_hlist_with(&self, fields: impl FnOnce(&Self) -> H) -> H {
+        fields(self)
+    }
+
+    #[inline(always)]
+    fn from_hlist_with(hlist: H, fields: impl FnOnce(H) -> Self) -> Self {
+        fields(hlist)
+    }
+}
+
+#[cfg(pl_hlist_specialization)]
+mod specialized;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::offset_of;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Copy, Debug, PartialEq, HListSupport)]
+    #[hlist(layout)]
+    struct Sample {
+        a: u64,
+        b: u32,
+        c: u16,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, HListSupport)]
+    #[hlist(layout)]
+    struct Padded {
+        a: u8,
+        b: u16,
+        c: u8,
+    }
+
+    /// Returns the offsets of the three elements of `HCons<A, HCons<B, HCons<C, HNil>>>`.
+    fn hlist_offsets<A, B, C>() -> [usize; 3] {
+        type Tail<B, C> = HCons<B, HCons<C, HNil>>;
+        let tail = offset_of!(HCons<A, Tail<B, C>>, 1);
+        [
+            offset_of!(HCons<A, Tail<B, C>>, 0),
+            tail + offset_of!(Tail<B, C>, 0),
+            tail + offset_of!(Tail<B, C>, 1) + offset_of!(HCons<C, HNil>, 0),
+        ]
+    }
+
+    #[test]
+    fn same_layout_should_imply_matching_offsets() {
+        // Field order under `repr(Rust)` is unspecified, so only check that a layout
+        // reported as the same really is
+        type SampleHList = HCons<u64, HCons<u32, HCons<u16, HNil>>>;
+        if <Sample as HListLayout<SampleHList>>::SAME_LAYOUT {
+            assert_eq!(size_of::<Sample>(), size_of::<SampleHList>());
+            assert_eq!(
+                [
+                    offset_of!(Sample, a),
+                    offset_of!(Sample, b),
+                    offset_of!(Sample, c)
+                ],
+                hlist_offsets::<u64, u32, u16>()
+            );
+        }
+
+        type PaddedHList = HCons<u8, HCons<u16, HCons<u8, HNil>>>;
+        if <Padded as HListLayout<PaddedHList>>::SAME_LAYOUT {
+            assert_eq!(size_of::<Padded>(), size_of::<PaddedHList>());
+            assert_eq!(
+                [
+                    offset_of!(Padded, a),
+                    offset_of!(Padded, b),
+                    offset_of!(Padded, c)
+                ],
+                hlist_offsets::<u8, u16, u8>()
+            );
+        }
+    }
+
+    #[test]
+    fn copy_conversions_should_preserve_fields() {
+        let s = Sample { a: 1, b: 2, c: 3 };
+        assert_eq!(s.to_hlist(), hlist!(1u64, 2u32, 3u16));
+        assert_eq!(Sample::from_hlist(hlist!(1u64, 2u32, 3u16)), s);
+
+        let p = Padded { a: 1, b: 2, c: 3 };
+        assert_eq!(p.to_hlist(), hlist!(1u8, 2u16, 3u8));
+        assert_eq!(Padded::from_hlist(hlist!(1u8, 2u16, 3u8)), p);
+    }
+}
@@ -0,0 +1,44 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use super::*;
+use core::mem::transmute_copy;
+
+impl<S: HListLayout<H>, H: HList> CopyConversions<H> for S {
+    #[inline(always)]
+    default fn to_hlist_with(&self, fields: impl FnOnce(&Self) -> H) -> H {
+        fields(self)
+    }
+
+    #[inline(always)]
+    default fn from_hlist_with(hlist: H, fields: impl FnOnce(H) -> Self) -> Self {
+        fields(hlist)
+    }
+}
+
+// Since every element of `H` has the same type as a field of `S`, `H` is trivially
+// copyable whenever `S` is.
+impl<S: HListLayout<H> + Copy, H: HList> CopyConversions<H> for S {
+    #[inline(always)]
+    fn to_hlist_with(&self, fields: impl FnOnce(&Self) -> H) -> H {
+        if S::SAME_LAYOUT {
+            // SAFETY: `SAME_LAYOUT` guarantees that every element of `H` has the same type
+            // and offset as the corresponding field, and the fields are all `Copy`.
+            unsafe { transmute_copy(self) }
+        } else {
+            fields(self)
+        }
+    }
+
+    #[inline(always)]
+    fn from_hlist_with(hlist: H, fields: impl FnOnce(H) -> Self) -> Self {
+        if S::SAME_LAYOUT {
+            // SAFETY: As above; `hlist` has no drop glue since its elements are all `Copy`.
+            unsafe { transmute_copy(&hlist) }
+        } else {
+            fields(hlist)
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::cell::{Cell, OnceCell};
+use core::fmt;
+
+/// A value that is computed by the closure `F` the first time it is accessed.
+pub struct Lazy<T, F = fn() -> T> {
+    value: OnceCell<T>,
+    init: Cell<Option<F>>,
+}
+
+impl<T, F: FnOnce() -> T> Lazy<T, F> {
+    /// Creates a new `Lazy` whose value will be computed by `init`.
+    pub const fn new(init: F) -> Self {
+        Lazy {
+            value: OnceCell::new(),
+            init: Cell::new(Some(init)),
+        }
+    }
+
+    /// Returns a reference to the value, computing it first if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the closure previously panicked, or if it (recursively) accesses this value.
+    pub fn get(&self) -> &T {
+        self.value.get_or_init(|| match self.init.take() {
+            Some(init) => init(),
+            None => panic!("`Lazy` instance was poisoned by a previous panic"),
+        })
+    }
+
+    /// Returns a mutable reference to the value, computing it first if necessary.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.get();
+        self.value.get_mut().expect("value was just initialized")
+    }
+
+    /// Consumes this `Lazy`, returning the value (computing it first if necessary).
+    pub fn into_value(self) -> T {
+        match self.value.into_inner() {
+            Some(value) => value,
+            None => match self.init.into_inner() {
+                Some(init) => init(),
+                None => panic!("`Lazy` instance was poisoned by a previous panic"),
+            },
+        }
+    }
+
+    /// Returns `true` if the value has already been computed.
+    pub fn is_forced(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.write_str("Lazy(<unforced>)"),
+        }
+    }
+}
+
+/// An `HCons` whose head is computed on first access.  A lazy `HList` describes a set of
+/// (possibly expensive) values structurally, and computes only the ones that are
+/// actually used.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let lazy = hlist!(Lazy::new(|| 1u8), Lazy::new(|| String::from("two")));
+/// assert!(!lazy.head().is_forced());
+/// assert_eq!(*lazy.head().get(), 1u8);
+/// assert!(lazy.head().is_forced());
+/// assert_eq!(lazy.force(), hlist!(1u8, String::from("two")));
+/// # }
+/// ```
+pub type LazyHCons<H, F, T> = HCons<Lazy<H, F>, T>;
+
+/// Allows for computing every element of a lazy `HList`, producing the equivalent strict
+/// `HList`.
+pub trait Force {
+    /// The strict form of this list.
+    type Output: HList;
+
+    /// Computes every element that has not already been computed, in order, and returns
+    /// the values.
+    fn force(self) -> Self::Output;
+}
+
+impl Force for HNil {
+    type Output = HNil;
+
+    fn force(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<H, F, T> Force for LazyHCons<H, F, T>
+where
+    F: FnOnce() -> H,
+    T: HList + Force,
+{
+    type Output = HCons<H, T::Output>;
+
+    fn force(self) -> Self::Output {
+        let head = self.0.into_value();
+        HCons(head, self.1.force())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn lazy_elements_should_only_be_computed_when_accessed() {
+        let computed = Cell::new(0);
+        let compute = |value: u32| {
+            computed.set(computed.get() + 1);
+            value
+        };
+
+        let lazy = hlist!(
+            Lazy::new(|| compute(1)),
+            Lazy::new(|| compute(2)),
+            Lazy::new(|| compute(3))
+        );
+        assert_eq!(computed.get(), 0);
+
+        assert_eq!(*lazy.tail().head().get(), 2);
+        assert_eq!(*lazy.tail().head().get(), 2);
+        assert_eq!(computed.get(), 1);
+        assert_eq!(format!("{:?}", lazy.head()), "Lazy(<unforced>)");
+        assert_eq!(format!("{:?}", lazy.tail().head()), "Lazy(2)");
+
+        assert_eq!(lazy.force(), hlist!(1, 2, 3));
+        assert_eq!(computed.get(), 3);
+    }
+
+    #[test]
+    fn get_mut_should_compute_the_value() {
+        let mut lazy: Lazy<Vec<u8>> = Lazy::new(Vec::new);
+        lazy.get_mut().push(1);
+        assert!(lazy.is_forced());
+        assert_eq!(lazy.into_value(), vec![1]);
+    }
+}
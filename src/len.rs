@@ -0,0 +1,156 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::marker::PhantomData;
+
+/// Type-level natural number zero.
+///
+/// Like the index types, natural number types are never constructed; they exist only
+/// so that lengths can be computed and compared at compile time.
+pub struct Zero {
+    _priv: (),
+}
+
+/// Type-level natural number one greater than `N`.
+pub struct Succ<N> {
+    _marker: PhantomData<N>,
+}
+
+/// A type-level natural number built from `Zero` and `Succ`.
+pub trait Nat {
+    /// The value of this number.
+    const VALUE: usize;
+}
+
+impl Nat for Zero {
+    const VALUE: usize = 0;
+}
+
+impl<N: Nat> Nat for Succ<N> {
+    const VALUE: usize = N::VALUE + 1;
+}
+
+/// Provides the number of elements in an `HList`, both as a type-level natural number
+/// and as a value.
+pub trait Len: HList {
+    /// The number of elements in this list, as a type-level natural number.
+    type Length: Nat;
+
+    /// The number of elements in this list.
+    const LEN: usize = <Self::Length as Nat>::VALUE;
+
+    /// Returns the number of elements in this list.
+    #[inline(always)]
+    fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    /// Returns `true` if this list has no elements.
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        Self::LEN == 0
+    }
+}
+
+impl Len for HNil {
+    type Length = Zero;
+}
+
+impl<H, T: Len> Len for HCons<H, T> {
+    type Length = Succ<T::Length>;
+}
+
+/// Marker trait for `HList`s with the same number of elements as `Other`, so that
+/// generic code can require two lists to have the same shape.  A mismatch is reported
+/// as an error naming both list types.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// fn pair_up<A, B>(values: A, units: B) -> A::Zipped
+/// where
+///     A: SameLength<B> + HZippable<B>,
+/// {
+///     values.zip(units)
+/// }
+///
+/// # fn main() {
+/// assert_eq!(pair_up(hlist!(1, 2.0), hlist!("m", "s")), hlist!((1, "m"), (2.0, "s")));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// fn same_length<A: SameLength<B>, B>() {}
+///
+/// # fn main() {
+/// // error[E0277]: `HCons<u8, HNil>` and `HNil` have different lengths
+/// same_length::<HCons<u8, HCons<u8, HNil>>, HCons<u8, HNil>>();
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` and `{Other}` have different lengths",
+    label = "expected an `HList` with as many elements as `{Other}`"
+)]
+pub trait SameLength<Other>: Len {}
+
+impl SameLength<HNil> for HNil {}
+
+impl<H1, T1, H2, T2> SameLength<HCons<H2, T2>> for HCons<H1, T1>
+where
+    T1: SameLength<T2>,
+    T2: HList,
+{
+}
+
+impl HNil {
+    /// The number of elements in this list (always zero).
+    pub const LEN: usize = 0;
+}
+
+impl<H, T: Len> HCons<H, T> {
+    /// The number of elements in this list.
+    pub const LEN: usize = <Self as Len>::LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Three = HCons<u8, HCons<&'static str, HCons<f32, HNil>>>;
+
+    #[test]
+    fn len_should_count_elements() {
+        assert_eq!(HNil.len(), 0);
+        assert!(HNil.is_empty());
+        assert_eq!(hlist!(1u8, "two", 3.0f32).len(), 3);
+        assert!(!hlist!(1u8).is_empty());
+
+        const LENS: [usize; 3] = [HNil::LEN, Three::LEN, <Three as Len>::LEN];
+        assert_eq!(LENS, [0, 3, 3]);
+        assert_eq!(<<Three as Len>::Length as Nat>::VALUE, 3);
+    }
+
+    fn same_length<A: SameLength<B>, B>() -> usize {
+        A::LEN
+    }
+
+    #[test]
+    fn same_length_should_accept_lists_of_equal_length() {
+        assert_eq!(same_length::<HNil, HNil>(), 0);
+        assert_eq!(
+            same_length::<Three, HCons<(), HCons<(), HCons<(), HNil>>>>(),
+            3
+        );
+    }
+
+    #[test]
+    fn len_should_size_arrays() {
+        let buffer = [0u8; Three::LEN];
+        assert_eq!(buffer.len(), 3);
+    }
+}
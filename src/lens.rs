@@ -0,0 +1,210 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// A type-level reference to a part of an `S`, through which that part can be read and
+/// updated.
+///
+/// With `#[hlist(lenses)]`, `HListSupport` implements `Lens<Struct>` for the label type
+/// of each field of a struct with named fields, so `label!(field)` (or, with
+/// `#[hlist(labels)]`, the alias in the struct's labels module) focuses on that field.
+/// An `HList` of lenses is a path that focuses on each in turn, which composes the
+/// field lenses of nested structs; `path!` writes one from the field names.  Paths are
+/// checked at compile time: naming a field that the struct does not have, or that is
+/// not part of its `HList` representation, fails to compile.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// #[derive(Clone, Debug, PartialEq, HListSupport)]
+/// #[hlist(lenses)]
+/// struct Address {
+///     city: String,
+///     zip: u32,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, HListSupport)]
+/// #[hlist(lenses)]
+/// struct User {
+///     name: String,
+///     address: Address,
+/// }
+///
+/// # fn main() {
+/// let mut user = User {
+///     name: "alice".to_string(),
+///     address: Address { city: "Springfield".to_string(), zip: 12345 },
+/// };
+/// assert_eq!(user.get_path::<path!(address, zip)>(), &12345);
+///
+/// user.set_path::<path!(address, city)>("Shelbyville".to_string());
+/// user.modify_path::<path!(address, zip), _>(|zip| *zip += 1);
+/// assert_eq!(
+///     user.address,
+///     Address { city: "Shelbyville".to_string(), zip: 12346 }
+/// );
+/// assert_eq!(<label!(name) as Lens<User>>::get(&user), "alice");
+/// # }
+/// ```
+///
+/// A path through a field that does not exist does not compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// #[derive(Clone, HListSupport)]
+/// #[hlist(lenses)]
+/// struct Address {
+///     zip: u32,
+/// }
+///
+/// # fn main() {
+/// let address = Address { zip: 12345 };
+/// address.get_path::<path!(city)>();
+/// # }
+/// ```
+pub trait Lens<S> {
+    /// The type of the part of `S` that this lens focuses on.
+    type Target;
+
+    /// Returns a reference to the focused part of `source`.
+    fn get(source: &S) -> &Self::Target;
+
+    /// Returns a mutable reference to the focused part of `source`.
+    fn get_mut(source: &mut S) -> &mut Self::Target;
+
+    /// Replaces the focused part of `source` with `value`.
+    #[inline]
+    fn set(source: &mut S, value: Self::Target) {
+        *Self::get_mut(source) = value;
+    }
+
+    /// Updates the focused part of `source` in place with `f`.
+    #[inline]
+    fn modify<F>(source: &mut S, f: F)
+    where
+        F: FnOnce(&mut Self::Target),
+    {
+        f(Self::get_mut(source))
+    }
+}
+
+/// The empty path focuses on the whole value.
+impl<S> Lens<S> for HNil {
+    type Target = S;
+
+    #[inline(always)]
+    fn get(source: &S) -> &S {
+        source
+    }
+
+    #[inline(always)]
+    fn get_mut(source: &mut S) -> &mut S {
+        source
+    }
+}
+
+/// A non-empty path focuses with its first lens, then with the rest of the path.  Lenses
+/// are type-level markers, which are `'static`; this lets the compiler see that the
+/// part focused on by the first lens lives as long as the source.
+impl<S, H, T> Lens<S> for HCons<H, T>
+where
+    H: Lens<S> + 'static,
+    T: HList + Lens<H::Target> + 'static,
+{
+    type Target = T::Target;
+
+    #[inline(always)]
+    fn get(source: &S) -> &Self::Target {
+        T::get(H::get(source))
+    }
+
+    #[inline(always)]
+    fn get_mut(source: &mut S) -> &mut Self::Target {
+        T::get_mut(H::get_mut(source))
+    }
+}
+
+/// Provides the operations of `Lens` as methods on the value being focused on.
+pub trait PathAccess: Sized {
+    /// Returns a reference to the part of this value focused on by `P`.
+    #[inline(always)]
+    fn get_path<P: Lens<Self>>(&self) -> &P::Target {
+        P::get(self)
+    }
+
+    /// Returns a mutable reference to the part of this value focused on by `P`.
+    #[inline(always)]
+    fn get_path_mut<P: Lens<Self>>(&mut self) -> &mut P::Target {
+        P::get_mut(self)
+    }
+
+    /// Replaces the part of this value focused on by `P` with `value`.
+    #[inline(always)]
+    fn set_path<P: Lens<Self>>(&mut self, value: P::Target) {
+        P::set(self, value)
+    }
+
+    /// Updates the part of this value focused on by `P` in place with `f`.
+    #[inline(always)]
+    fn modify_path<P: Lens<Self>, F: FnOnce(&mut P::Target)>(&mut self, f: F) {
+        P::modify(self, f)
+    }
+}
+
+impl<S> PathAccess for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    #[hlist(lenses)]
+    struct Inner<T> {
+        value: T,
+        #[hlist(skip)]
+        hits: u32,
+    }
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    #[hlist(labels, lenses)]
+    struct Outer {
+        id: u8,
+        inner: Inner<&'static str>,
+        a_field_name_longer_than_thirty_two_bytes: bool,
+    }
+
+    #[test]
+    fn lenses_should_focus_on_nested_fields() {
+        let mut outer = Outer {
+            id: 1,
+            inner: Inner {
+                value: "one",
+                hits: 0,
+            },
+            a_field_name_longer_than_thirty_two_bytes: false,
+        };
+        assert_eq!(outer.get_path::<outer_labels::id>(), &1);
+        assert_eq!(outer.get_path::<path!(inner, value)>(), &"one");
+        assert_eq!(outer.get_path::<path!()>(), &outer.clone());
+
+        outer.set_path::<path!(inner, value)>("two");
+        outer.modify_path::<label!(id), _>(|id| *id += 1);
+        *outer.get_path_mut::<outer_labels::a_field_name_longer_than_thirty_two_bytes>() = true;
+        assert_eq!(
+            outer,
+            Outer {
+                id: 2,
+                inner: Inner {
+                    value: "two",
+                    hits: 0,
+                },
+                a_field_name_longer_than_thirty_two_bytes: true,
+            }
+        );
+    }
+}
@@ -3,13 +3,262 @@
 // All rights reserved.
 //
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(pl_hlist_specialization, feature(specialization))]
+#![cfg_attr(pl_hlist_specialization, allow(incomplete_features))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+// Allows code generated by the derive to refer to this crate by name, even within it.
+extern crate self as pl_hlist;
+
 // Re-export the pl-hlist-derive crate
 pub use pl_hlist_derive::*;
 
 // The following is necessary to make exported macros visible.
 #[macro_use]
 mod macros;
+#[allow(unused_imports)]
 pub use self::macros::*;
 
 mod hlist;
 pub use self::hlist::*;
+
+pub mod prelude;
+
+mod const_ops;
+
+mod len;
+pub use self::len::*;
+
+mod assertions;
+pub use self::assertions::*;
+
+mod layout;
+pub use self::layout::*;
+
+mod labelled;
+pub use self::labelled::*;
+
+mod tuple;
+pub use self::tuple::*;
+
+mod map;
+pub use self::map::*;
+
+mod apply;
+pub use self::apply::*;
+
+mod fold;
+pub use self::fold::*;
+
+mod zip;
+pub use self::zip::*;
+
+mod monoid;
+pub use self::monoid::*;
+
+mod sequence;
+pub use self::sequence::*;
+
+mod array;
+pub use self::array::*;
+
+mod elementwise;
+pub use self::elementwise::*;
+
+mod parts;
+pub use self::parts::*;
+
+mod indices;
+pub use self::indices::*;
+
+mod selector;
+pub use self::selector::*;
+
+mod at;
+pub use self::at::*;
+
+mod set;
+pub use self::set::*;
+
+mod split;
+pub use self::split::*;
+
+mod plucker;
+pub use self::plucker::*;
+
+mod sculptor;
+pub use self::sculptor::*;
+
+mod view;
+pub use self::view::*;
+
+mod lens;
+pub use self::lens::*;
+
+mod coproduct;
+pub use self::coproduct::*;
+
+mod patch;
+pub use self::patch::*;
+
+mod partial;
+pub use self::partial::*;
+
+mod builder;
+pub use self::builder::*;
+
+mod debug_short;
+pub use self::debug_short::*;
+
+mod tails;
+pub use self::tails::*;
+
+mod transmogrifier;
+pub use self::transmogrifier::*;
+
+mod query;
+pub use self::query::*;
+
+mod stack;
+pub use self::stack::*;
+
+mod lazy;
+pub use self::lazy::*;
+
+mod registry;
+pub use self::registry::*;
+
+mod handlers;
+pub use self::handlers::*;
+
+mod capabilities;
+pub use self::capabilities::*;
+
+mod hmap;
+pub use self::hmap::*;
+
+mod command;
+pub use self::command::*;
+
+mod event_bus;
+pub use self::event_bus::*;
+
+mod state_machine;
+pub use self::state_machine::*;
+
+mod middleware;
+pub use self::middleware::*;
+
+mod fixture;
+pub use self::fixture::*;
+
+mod packed;
+pub use self::packed::*;
+
+mod bytes;
+pub use self::bytes::*;
+
+mod codec;
+pub use self::codec::*;
+
+mod hash;
+pub use self::hash::*;
+
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "alloc")]
+pub use self::boxed::*;
+
+#[cfg(feature = "alloc")]
+mod ecs;
+#[cfg(feature = "alloc")]
+pub use self::ecs::*;
+
+#[cfg(feature = "alloc")]
+mod validation;
+#[cfg(feature = "alloc")]
+pub use self::validation::*;
+
+#[cfg(feature = "alloc")]
+mod args;
+#[cfg(feature = "alloc")]
+pub use self::args::*;
+
+// Optional integrations with other crates
+#[cfg(feature = "bumpalo")]
+mod arena;
+#[cfg(feature = "bumpalo")]
+pub use self::arena::*;
+
+#[cfg(feature = "quickcheck")]
+mod arbitrary;
+
+#[cfg(feature = "http")]
+mod headers;
+#[cfg(feature = "http")]
+pub use self::headers::*;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use self::metrics::*;
+
+#[cfg(feature = "proptest")]
+mod strategy;
+#[cfg(feature = "proptest")]
+pub use self::strategy::*;
+
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "pyo3")]
+pub use self::python::*;
+
+#[cfg(feature = "rand")]
+mod random;
+#[cfg(feature = "rand")]
+pub use self::random::*;
+
+#[cfg(feature = "axum")]
+mod extract;
+
+#[cfg(feature = "defmt")]
+mod defmt_format;
+
+#[cfg(feature = "diesel")]
+mod queryable;
+
+#[cfg(feature = "frunk")]
+mod frunk_compat;
+#[cfg(feature = "frunk")]
+pub use self::frunk_compat::*;
+
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "schemars")]
+pub use self::schema::*;
+
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "serde")]
+pub use self::serialization::*;
+
+#[cfg(feature = "toml")]
+mod toml_table;
+#[cfg(feature = "toml")]
+pub use self::toml_table::*;
+
+#[cfg(feature = "tower")]
+mod layer;
+
+#[cfg(feature = "visitor")]
+mod visitor;
+#[cfg(feature = "visitor")]
+pub use self::visitor::*;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "wasm")]
+pub use self::wasm::*;
@@ -6,7 +6,8 @@
 //   https://github.com/epsilonz/shoggoth.rs
 //
 
-/// Shorthand for building an `HList` from the given elements.
+/// Shorthand for building an `HList` from the given elements.  The expansion is a plain
+/// constructor expression, so it can be used to initialize `const` and `static` items.
 ///
 /// # Examples
 ///
@@ -15,6 +16,9 @@
 ///
 /// # fn main() {
 /// let x: HCons<u8, HCons<u32, HNil>> = hlist!(1u8, 666u32);
+///
+/// static CONFIG: HCons<&str, HCons<u32, HNil>> = hlist!("server", 8080);
+/// assert_eq!(*CONFIG.tail().head(), 8080);
 /// # }
 /// ```
 #[macro_export]
@@ -30,5 +34,510 @@ macro_rules! hlist {
     };
 }
 
+/// Shorthand for writing the `HList` type with the given element types.  A final
+/// `...Tail` replaces the terminal `HNil` with the given type.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// fn split(hlist: HList![u8, String, ...HList![bool]]) -> (u8, HList![String, bool]) {
+///     let HCons(byte, rest) = hlist;
+///     (byte, rest)
+/// }
+///
+/// # fn main() {
+/// let x: HList![u8, String, bool] = hlist!(1u8, String::from("two"), true);
+/// let y: HCons<u8, HCons<String, HCons<bool, HNil>>> = x.clone();
+/// assert_eq!(split(x).1, hlist!(String::from("two"), true));
+/// # }
+/// ```
+#[macro_export]
+#[allow(non_snake_case)]
+macro_rules! HList {
+    {} => {
+        $crate::HNil
+    };
+    { ...$tail:ty } => {
+        $tail
+    };
+    { $head:ty } => {
+        $crate::HCons<$head, $crate::HNil>
+    };
+    { $head:ty, $($rest:tt)* } => {
+        $crate::HCons<$head, $crate::HList!($($rest)*)>
+    };
+}
 
+/// Shorthand for writing a pattern that destructures an `HList` into the given
+/// patterns, for use in `let` and `match`.  A final `...rest` binds the remainder of the
+/// list (so that only a prefix is matched) instead of matching the terminal `HNil`.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// # fn main() {
+/// let hlist_pat!(a, b, c) = hlist!(1u8, "two", 3.0f32);
+/// assert_eq!((a, b, c), (1u8, "two", 3.0f32));
+///
+/// let hlist_pat!(first, _, ...rest) = hlist!(1u8, "two", 3.0f32, 'd');
+/// assert_eq!(first, 1u8);
+/// assert_eq!(rest, hlist!(3.0f32, 'd'));
+///
+/// match hlist!(0u8, "zero") {
+///     hlist_pat!(0, name) => assert_eq!(name, "zero"),
+///     hlist_pat!(_, _) => unreachable!(),
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! hlist_pat {
+    {} => {
+        $crate::HNil
+    };
+    { ...$rest:pat } => {
+        $rest
+    };
+    { $head:pat } => {
+        $crate::HCons($head, $crate::HNil)
+    };
+    { $head:pat, $($tail:tt)* } => {
+        $crate::HCons($head, $crate::hlist_pat!($($tail)*))
+    };
+}
 
+/// Shorthand for writing the `Name` label type with the given name.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// # fn main() {
+/// let field: Field<label!(id), u32> = Field::new(7);
+/// assert_eq!(field.name(), "id");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! label {
+    ($name:ident) => {
+        $crate::Name<
+            { $crate::encode_name(stringify!($name), 0) },
+            { $crate::encode_name(stringify!($name), 1) },
+        >
+    };
+}
+
+/// Returns the type of the `Lens` path that focuses on each of the named fields in
+/// turn, an `HList` of their `label!` types.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// assert_same_type!(path!(address, zip), HList![label!(address), label!(zip)]);
+/// assert_same_type!(path!(), HNil);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! path {
+    ($($name:ident),* $(,)?) => {
+        $crate::HList![$($crate::label!($name)),*]
+    };
+}
+
+/// Implements a trait for `HNil` and `HCons` from the signatures of its methods, so that
+/// an `HList` implements the trait whenever each of its elements does.
+///
+/// Each method takes `&self`, `&mut self`, or `self`, and any number of arguments, which
+/// are passed on to every element (so they must be `Copy`, or references).  A method
+/// without a return type visits the elements in order.  A method with a return type
+/// gives the result for an empty list, and combines the result for the head with the
+/// result for the tail:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// trait Describe {
+///     fn describe(&self, out: &mut String);
+///     fn size(&self) -> usize;
+/// }
+///
+/// impl Describe for u8 {
+///     fn describe(&self, out: &mut String) {
+///         out.push_str(&format!("u8 {};", self));
+///     }
+///     fn size(&self) -> usize {
+///         1
+///     }
+/// }
+///
+/// impl Describe for &str {
+///     fn describe(&self, out: &mut String) {
+///         out.push_str(&format!("str {:?};", self));
+///     }
+///     fn size(&self) -> usize {
+///         self.len()
+///     }
+/// }
+///
+/// impl_hlist_trait! {
+///     Describe {
+///         fn describe(&self, out: &mut String);
+///         fn size(&self) -> usize {
+///             empty => 0,
+///             combine(head, tail) => head + tail,
+///         }
+///     }
+/// }
+///
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two", 3u8);
+/// let mut out = String::new();
+/// hlist.describe(&mut out);
+/// assert_eq!(out, "u8 1;str \"two\";u8 3;");
+/// assert_eq!(hlist.size(), 5);
+/// # }
+/// ```
+///
+/// The trait must be named by a single identifier (so import it first), and must not
+/// have generic parameters or generic methods.
+#[macro_export]
+macro_rules! impl_hlist_trait {
+    ($Trait:ident { $($methods:tt)* }) => {
+        $crate::impl_hlist_trait!(@munch $Trait [] [] $($methods)*);
+    };
+    (@munch $Trait:ident [$($nil:tt)*] [$($cons:tt)*]) => {
+        impl $Trait for $crate::HNil {
+            $($nil)*
+        }
+
+        impl<H: $Trait, T: $crate::HList + $Trait> $Trait for $crate::HCons<H, T> {
+            $($cons)*
+        }
+    };
+    (@munch $Trait:ident $nil:tt $cons:tt fn $method:ident ($($params:tt)*) $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@receiver $Trait $nil $cons $method [$($params)*] $($rest)*);
+    };
+    (@receiver $Trait:ident $nil:tt $cons:tt $method:ident
+        [&self $(, $arg:ident: $argty:ty)* $(,)?] $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@body $Trait $nil $cons $method
+            [&self] [&self.0] [&self.1] [$($arg: $argty),*] $($rest)*);
+    };
+    (@receiver $Trait:ident $nil:tt $cons:tt $method:ident
+        [&mut self $(, $arg:ident: $argty:ty)* $(,)?] $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@body $Trait $nil $cons $method
+            [&mut self] [&mut self.0] [&mut self.1] [$($arg: $argty),*] $($rest)*);
+    };
+    (@receiver $Trait:ident $nil:tt $cons:tt $method:ident
+        [self $(, $arg:ident: $argty:ty)* $(,)?] $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@body $Trait $nil $cons $method
+            [self] [self.0] [self.1] [$($arg: $argty),*] $($rest)*);
+    };
+    (@body $Trait:ident [$($nil:tt)*] [$($cons:tt)*] $method:ident
+        [$($recv:tt)*] [$($head:tt)*] [$($tail:tt)*] [$($arg:ident: $argty:ty),*]
+        ; $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@munch $Trait
+            [
+                $($nil)*
+                #[allow(unused_variables)]
+                #[inline]
+                fn $method($($recv)* $(, $arg: $argty)*) {}
+            ]
+            [
+                $($cons)*
+                #[inline]
+                fn $method($($recv)* $(, $arg: $argty)*) {
+                    $Trait::$method($($head)* $(, $arg)*);
+                    $Trait::$method($($tail)* $(, $arg)*);
+                }
+            ]
+            $($rest)*);
+    };
+    (@body $Trait:ident [$($nil:tt)*] [$($cons:tt)*] $method:ident
+        [$($recv:tt)*] [$($head:tt)*] [$($tail:tt)*] [$($arg:ident: $argty:ty),*]
+        -> $ret:ty {
+            empty => $empty:expr,
+            combine($h:ident, $t:ident) => $combine:expr $(,)?
+        } $(;)? $($rest:tt)*) => {
+        $crate::impl_hlist_trait!(@munch $Trait
+            [
+                $($nil)*
+                #[allow(unused_variables)]
+                #[inline]
+                fn $method($($recv)* $(, $arg: $argty)*) -> $ret {
+                    $empty
+                }
+            ]
+            [
+                $($cons)*
+                #[inline]
+                fn $method($($recv)* $(, $arg: $argty)*) -> $ret {
+                    let $h = $Trait::$method($($head)* $(, $arg)*);
+                    let $t = $Trait::$method($($tail)* $(, $arg)*);
+                    $combine
+                }
+            ]
+            $($rest)*);
+    };
+}
+
+/// Shorthand for writing the `HList` type with the given element types.  An optional
+/// `; Tail` suffix replaces the terminal `HNil` with the given type.
+#[allow(unused_macros)]
+macro_rules! hlist_type {
+    () => {
+        HNil
+    };
+    (; $tail:ty) => {
+        $tail
+    };
+    ($head:ty $(, $rest:ty)*) => {
+        HCons<$head, hlist_type!($($rest),*)>
+    };
+    ($head:ty $(, $rest:ty)*; $tail:ty) => {
+        HCons<$head, hlist_type!($($rest),*; $tail)>
+    };
+}
+
+/// Shorthand for writing a pattern that destructures an `HList` into the given
+/// bindings.  An optional `; tail` suffix binds the remainder of the list instead of
+/// matching the terminal `HNil`.
+#[allow(unused_macros)]
+macro_rules! hlist_pattern {
+    () => {
+        HNil
+    };
+    (; $tail:ident) => {
+        $tail
+    };
+    ($head:ident $(, $rest:ident)*) => {
+        HCons($head, hlist_pattern!($($rest),*))
+    };
+    ($head:ident $(, $rest:ident)*; $tail:ident) => {
+        HCons($head, hlist_pattern!($($rest),*; $tail))
+    };
+}
+
+/// Like `hlist!`, but with an optional `; tail` suffix that replaces the terminal
+/// `HNil` with the given expression.
+#[allow(unused_macros)]
+macro_rules! hlist_with_tail {
+    (; $tail:expr) => {
+        $tail
+    };
+    ($head:expr $(, $rest:expr)*; $tail:expr) => {
+        HCons($head, hlist_with_tail!($($rest),*; $tail))
+    };
+}
+
+/// Expands to the second type, ignoring the first.
+#[allow(unused_macros)]
+macro_rules! same_type {
+    ($ignored:ident, $ty:ty) => {
+        $ty
+    };
+}
+
+/// Expands to the number of identifiers given.
+#[allow(unused_macros)]
+macro_rules! count {
+    () => {
+        0
+    };
+    ($head:ident $($rest:ident)*) => {
+        1 + count!($($rest)*)
+    };
+}
+
+/// Asserts at compile time that two types have the same size and alignment.
+///
+/// This is useful for checking that an `HList` has no overhead compared to the struct
+/// or tuple it stands in for, so that a future change to either type that regresses
+/// the layout is caught as a compile error.
+///
+/// # Examples
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// struct Point {
+///     x: f64,
+///     y: f64,
+///     id: u32,
+/// }
+///
+/// assert_same_layout!(HCons<f64, HCons<f64, HCons<u32, HNil>>>, Point);
+/// assert_same_layout!(HCons<f64, HCons<f64, HCons<u32, HNil>>>, (f64, f64, u32));
+/// # fn main() {}
+/// ```
+///
+/// Because each `HCons` cell is laid out separately, an `HList` whose elements are not
+/// in descending order of alignment can be larger than the equivalent struct (see
+/// `PackedHList`):
+///
+/// ```compile_fail
+/// use pl_hlist::*;
+///
+/// assert_same_layout!(HCons<u8, HCons<u64, HCons<u8, HNil>>>, (u8, u64, u8));
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_same_layout {
+    ($a:ty, $b:ty $(,)?) => {
+        const _: () = {
+            assert!(
+                ::core::mem::size_of::<$a>() == ::core::mem::size_of::<$b>(),
+                concat!(
+                    "`",
+                    stringify!($a),
+                    "` and `",
+                    stringify!($b),
+                    "` have different sizes"
+                )
+            );
+            assert!(
+                ::core::mem::align_of::<$a>() == ::core::mem::align_of::<$b>(),
+                concat!(
+                    "`",
+                    stringify!($a),
+                    "` and `",
+                    stringify!($b),
+                    "` have different alignments"
+                )
+            );
+        };
+    };
+}
+
+/// Asserts at compile time that an expression has the given type, without evaluating
+/// the expression.
+///
+/// This is useful for pinning down the `HList` type of a protocol message or record, so
+/// that a change to its layout fails the build with an error naming both types.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// # fn main() {
+/// let header = hlist!(1u8, 0x0200u16);
+/// assert_hlist_type!(header, HList![u8, u16]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// use pl_hlist::*;
+///
+/// # fn main() {
+/// // error[E0277]: expected `HCons<u8, HCons<u32, HNil>>`, found `HCons<u8, HCons<u16, HNil>>`
+/// let header = hlist!(1u8, 0x0200u16);
+/// assert_hlist_type!(header, HList![u8, u32]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_hlist_type {
+    ($expr:expr, $ty:ty $(,)?) => {
+        let _ = || $crate::assert_type_of::<_, $ty>(&$expr);
+    };
+}
+
+/// Asserts at compile time that two types are the same.
+///
+/// # Examples
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// type Header = HList![u8, u16];
+/// assert_same_type!(Header, HCons<u8, HCons<u16, HNil>>);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_same_type {
+    ($a:ty, $b:ty $(,)?) => {
+        const _: fn() = $crate::assert_same_type::<$a, $b>;
+    };
+}
+
+/// Asserts at compile time that every element type of the `HList` type `$sub` appears in
+/// the `HList` type `$super` (in any order); a missing type is named in the error.
+///
+/// # Examples
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// struct Db;
+/// struct Logger;
+/// struct Cache;
+///
+/// assert_hlist_subset!(HList![Logger, Db], HList![Db, Cache, Logger]);
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// use pl_hlist::*;
+///
+/// struct Db;
+/// struct Logger;
+///
+/// // error[E0277]: type `Logger` does not appear in this `HList`
+/// assert_hlist_subset!(HList![Logger], HList![Db]);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_hlist_subset {
+    ($sub:ty, $super:ty $(,)?) => {
+        const _: fn() = || $crate::assert_subset::<$sub, $super, _>();
+    };
+}
+
+/// Asserts at compile time that two `HList` types have the same number of elements, or
+/// that an `HList` type has the given number of elements.
+///
+/// # Examples
+///
+/// ```
+/// use pl_hlist::*;
+///
+/// assert_hlist_len!(HList![u8, u16, u32], HList![bool, char, f32]);
+/// assert_hlist_len!(HList![u8, u16, u32], 3);
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// use pl_hlist::*;
+///
+/// // error[E0277]: `HCons<u16, HNil>` and `HNil` have different lengths
+/// assert_hlist_len!(HList![u8, u16], HList![bool]);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_hlist_len {
+    ($a:ty, $len:literal $(,)?) => {
+        const _: () = assert!(
+            <$a as $crate::Len>::LEN == $len,
+            concat!(
+                "`",
+                stringify!($a),
+                "` does not have ",
+                stringify!($len),
+                " elements"
+            )
+        );
+    };
+    ($a:ty, $b:ty $(,)?) => {
+        const _: fn() = $crate::assert_same_length::<$a, $b>;
+    };
+}
@@ -0,0 +1,227 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// A function object that accepts arguments of type `Input`.  A single type can
+/// implement `Func` for several input types, each with its own output type, making it a
+/// polymorphic function that can be mapped over a heterogeneous list with `Poly`.
+pub trait Func<Input> {
+    /// The type returned for an argument of type `Input`.
+    type Output;
+
+    /// Calls this function with the given argument.
+    fn call(&mut self, input: Input) -> Self::Output;
+}
+
+/// Wraps a polymorphic function object (one that implements `Func` for each element
+/// type of a list) so that it can be passed to `HMappable::map`.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Poly<F>(pub F);
+
+/// Allows for transforming each element of an `HList`, producing an `HList` of the
+/// results (in order, with the first element transformed first).
+///
+/// The `Mapper` is either a `Poly` wrapping a polymorphic function object, which is
+/// applied to every element, or an `HList` of closures with one closure per element.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Describe;
+///
+/// impl Func<u32> for Describe {
+///     type Output = String;
+///     fn call(&mut self, n: u32) -> String { format!("u32 {}", n) }
+/// }
+///
+/// impl Func<&str> for Describe {
+///     type Output = usize;
+///     fn call(&mut self, s: &str) -> usize { s.len() }
+/// }
+///
+/// assert_eq!(hlist!(7u32, "four").map(Poly(Describe)), hlist!(String::from("u32 7"), 4usize));
+///
+/// let mapped = hlist!(7u32, "four").map(hlist!(|n: u32| n * 2, |s: &str| s.to_uppercase()));
+/// assert_eq!(mapped, hlist!(14u32, String::from("FOUR")));
+/// # }
+/// ```
+pub trait HMappable<Mapper> {
+    /// The `HList` of results.
+    type Output: HList;
+
+    /// Transforms each element of this list with the given mapper.
+    fn map(self, mapper: Mapper) -> Self::Output;
+}
+
+impl<F> HMappable<Poly<F>> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn map(self, _: Poly<F>) -> HNil {
+        HNil
+    }
+}
+
+impl<F, H, T> HMappable<Poly<F>> for HCons<H, T>
+where
+    F: Func<H>,
+    T: HList + HMappable<Poly<F>>,
+{
+    type Output = HCons<F::Output, T::Output>;
+
+    #[inline]
+    fn map(self, mapper: Poly<F>) -> Self::Output {
+        let Poly(mut f) = mapper;
+        let head = f.call(self.0);
+        HCons(head, self.1.map(Poly(f)))
+    }
+}
+
+impl HMappable<HNil> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn map(self, _: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl<F, R, FTail, H, T> HMappable<HCons<F, FTail>> for HCons<H, T>
+where
+    F: FnOnce(H) -> R,
+    FTail: HList,
+    T: HList + HMappable<FTail>,
+{
+    type Output = HCons<R, T::Output>;
+
+    #[inline]
+    fn map(self, mapper: HCons<F, FTail>) -> Self::Output {
+        let head = (mapper.0)(self.0);
+        HCons(head, self.1.map(mapper.1))
+    }
+}
+
+/// Allows for transforming each field of a value (typically a struct that derives
+/// `HListSupport`) by converting it into an `HList`, mapping over the elements, and
+/// converting the results back into the same type or into another type.
+///
+/// This is implemented for every type that implements `IntoHList`, so a field-wise
+/// transformation written once as a polymorphic function can be reused across structs:
+///
+/// ```
+/// # use pl_hlist::*;
+/// struct Clean;
+///
+/// impl Func<String> for Clean {
+///     type Output = String;
+///     fn call(&mut self, s: String) -> String { s.trim().to_string() }
+/// }
+///
+/// impl Func<i32> for Clean {
+///     type Output = i32;
+///     fn call(&mut self, n: i32) -> i32 { n.clamp(0, 100) }
+/// }
+///
+/// #[derive(Debug, PartialEq, HListSupport)]
+/// struct Form {
+///     name: String,
+///     score: i32,
+/// }
+///
+/// let form = Form { name: String::from("  Ada "), score: 140 };
+/// assert_eq!(form.map_hlist(Poly(Clean)), Form { name: String::from("Ada"), score: 100 });
+/// ```
+pub trait MapHList<H: HList>: IntoHList<H> + Sized {
+    /// Transforms each field of this value with the given mapper, producing a value of
+    /// the same type.
+    fn map_hlist<Mapper>(self, mapper: Mapper) -> Self
+    where
+        H: HMappable<Mapper>,
+        Self: FromHList<H::Output>,
+    {
+        Self::from_hlist(self.into_hlist().map(mapper))
+    }
+
+    /// Transforms each field of this value with the given mapper, producing a value of
+    /// the target type.
+    fn map_hlist_into<Target, Mapper>(self, mapper: Mapper) -> Target
+    where
+        H: HMappable<Mapper>,
+        Target: FromHList<H::Output>,
+    {
+        Target::from_hlist(self.into_hlist().map(mapper))
+    }
+}
+
+impl<T: IntoHList<H>, H: HList> MapHList<H> for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    // Counts the elements it visits, and wraps each in `Some`.
+    struct Counter(usize);
+
+    impl<T> Func<T> for Counter {
+        type Output = (usize, Option<T>);
+
+        fn call(&mut self, input: T) -> Self::Output {
+            self.0 += 1;
+            (self.0, Some(input))
+        }
+    }
+
+    #[test]
+    fn map_with_poly_should_apply_the_function_to_each_element_in_order() {
+        let mapped = hlist!(1u8, "two", 3.0f32).map(Poly(Counter(0)));
+        assert_eq!(
+            mapped,
+            hlist!((1, Some(1u8)), (2, Some("two")), (3, Some(3.0f32)))
+        );
+        assert_eq!(HNil.map(Poly(Counter(0))), HNil);
+    }
+
+    #[test]
+    fn map_with_closures_should_apply_each_closure_to_its_element() {
+        let suffix = String::from("!");
+        let mapped = hlist!(2i32, String::from("hi"))
+            .map(hlist!(|n: i32| n - 5, move |s: String| s + &suffix));
+        assert_eq!(mapped, hlist!(-3i32, String::from("hi!")));
+    }
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    struct Reading {
+        sensor: u8,
+        value: f32,
+    }
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    struct Tagged {
+        sensor: (usize, Option<u8>),
+        value: (usize, Option<f32>),
+    }
+
+    #[test]
+    fn map_hlist_should_map_each_field() {
+        let reading = Reading {
+            sensor: 1,
+            value: 2.5,
+        };
+        let reading = reading.map_hlist(hlist!(|s: u8| s + 1, |v: f32| v * 2.0));
+        assert_eq!(
+            reading,
+            Reading {
+                sensor: 2,
+                value: 5.0
+            }
+        );
+
+        let tagged: Tagged = reading.map_hlist_into(Poly(Counter(0)));
+        assert_eq!(tagged.value, (2, Some(5.0)));
+    }
+}
@@ -0,0 +1,236 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use metrics::{Key, Level, Metadata, Recorder};
+
+// The metadata attached to every metric recorded by this module.
+static METADATA: Metadata<'static> =
+    Metadata::new(module_path!(), Level::INFO, Some(module_path!()));
+
+/// A value that can be recorded as a metric with a given name.
+///
+/// Numeric primitives are recorded as gauges, and `Monotonic` values as counters.
+pub trait Metric {
+    /// Records this value as the metric with the given name.
+    fn record<R: Recorder + ?Sized>(&self, name: &'static str, recorder: &R);
+}
+
+/// A monotonically increasing value, such as a running total, which is recorded as a
+/// counter rather than a gauge.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Monotonic<T>(pub T);
+
+macro_rules! impl_gauge_metric {
+    ($($t:ty),*) => {
+        $(
+            impl Metric for $t {
+                fn record<R: Recorder + ?Sized>(&self, name: &'static str, recorder: &R) {
+                    recorder
+                        .register_gauge(&Key::from_static_name(name), &METADATA)
+                        .set(*self as f64);
+                }
+            }
+        )*
+    };
+}
+
+impl_gauge_metric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+macro_rules! impl_counter_metric {
+    ($($t:ty),*) => {
+        $(
+            impl Metric for Monotonic<$t> {
+                fn record<R: Recorder + ?Sized>(&self, name: &'static str, recorder: &R) {
+                    recorder
+                        .register_counter(&Key::from_static_name(name), &METADATA)
+                        .absolute(self.0 as u64);
+                }
+            }
+        )*
+    };
+}
+
+impl_counter_metric!(u8, u16, u32, u64, usize);
+
+impl<T: Metric + ?Sized> Metric for &T {
+    fn record<R: Recorder + ?Sized>(&self, name: &'static str, recorder: &R) {
+        (**self).record(name, recorder)
+    }
+}
+
+/// Allows for recording each field of a labelled `HList` as a metric named after the
+/// field's label.
+pub trait RecordFields {
+    /// Records each field of this list with the given recorder.
+    fn record_fields<R: Recorder + ?Sized>(&self, recorder: &R);
+}
+
+impl RecordFields for HNil {
+    fn record_fields<R: Recorder + ?Sized>(&self, _: &R) {}
+}
+
+impl<L, V, T> RecordFields for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: Metric,
+    T: HList + RecordFields,
+{
+    fn record_fields<R: Recorder + ?Sized>(&self, recorder: &R) {
+        self.0.value.record(L::NAME, recorder);
+        self.1.record_fields(recorder);
+    }
+}
+
+/// Allows for recording the numeric fields of a struct as metrics named after the
+/// fields.
+///
+/// The `Metrics` derive (which relies on the labels declared by `HListSupport`)
+/// implements this by borrowing each field whose type is a numeric primitive or the
+/// `Monotonic` of an unsigned integer; other fields are skipped.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(HListSupport, Metrics)]
+/// struct PoolStats {
+///     name: String,
+///     active: u32,
+///     utilization: f64,
+///     requests: Monotonic<u64>,
+/// }
+///
+/// let stats = PoolStats {
+///     name: "db".to_string(),
+///     active: 3,
+///     utilization: 0.75,
+///     requests: Monotonic(1200),
+/// };
+/// stats.emit_metrics(&metrics::NoopRecorder);
+/// ```
+pub trait EmitMetrics {
+    /// A labelled `HList` with a reference to each field that is recorded as a metric.
+    type Metrics<'a>: RecordFields
+    where
+        Self: 'a;
+
+    /// Returns a labelled `HList` with a reference to each field that is recorded as a
+    /// metric.
+    fn metric_fields(&self) -> Self::Metrics<'_>;
+
+    /// Records each metric field with the given recorder.
+    fn emit_metrics<R: Recorder + ?Sized>(&self, recorder: &R) {
+        self.metric_fields().record_fields(recorder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics::{Counter, CounterFn, Gauge, GaugeFn, Histogram, KeyName, SharedString, Unit};
+    use pl_hlist_derive::{HListSupport, Metrics};
+    use std::sync::{Arc, Mutex};
+
+    type Log = Arc<Mutex<Vec<(String, &'static str, f64)>>>;
+
+    struct Handle(String, Log);
+
+    impl GaugeFn for Handle {
+        fn increment(&self, _: f64) {}
+        fn decrement(&self, _: f64) {}
+        fn set(&self, value: f64) {
+            self.1
+                .lock()
+                .unwrap()
+                .push((self.0.clone(), "gauge", value));
+        }
+    }
+
+    impl CounterFn for Handle {
+        fn increment(&self, _: u64) {}
+        fn absolute(&self, value: u64) {
+            self.1
+                .lock()
+                .unwrap()
+                .push((self.0.clone(), "counter", value as f64));
+        }
+    }
+
+    #[derive(Default)]
+    struct TestRecorder(Log);
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            Counter::from_arc(Arc::new(Handle(key.name().to_string(), self.0.clone())))
+        }
+
+        fn register_gauge(&self, key: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::from_arc(Arc::new(Handle(key.name().to_string(), self.0.clone())))
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[derive(HListSupport, Metrics)]
+    struct CacheStats {
+        region: &'static str,
+        entries: usize,
+        hit_ratio: f32,
+        evictions: Monotonic<u64>,
+    }
+
+    #[derive(HListSupport, Metrics)]
+    struct BorrowedStats<'a> {
+        name: &'a str,
+        active: u32,
+        drift: Monotonic<i64>,
+    }
+
+    #[test]
+    fn emit_metrics_should_skip_unsupported_fields_of_borrowing_structs() {
+        let name = String::from("pool");
+        let stats = BorrowedStats {
+            name: &name,
+            active: 2,
+            drift: Monotonic(-1),
+        };
+        assert_eq!((stats.name, stats.drift.0), ("pool", -1));
+
+        let recorder = TestRecorder::default();
+        stats.emit_metrics(&recorder);
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![("active".to_string(), "gauge", 2.0)]
+        );
+    }
+
+    #[test]
+    fn emit_metrics_should_record_each_numeric_field() {
+        let stats = CacheStats {
+            region: "eu",
+            entries: 12,
+            hit_ratio: 0.5,
+            evictions: Monotonic(3),
+        };
+        assert_eq!(stats.region, "eu");
+
+        let recorder = TestRecorder::default();
+        stats.emit_metrics(&recorder);
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                ("entries".to_string(), "gauge", 12.0),
+                ("hit_ratio".to_string(), "gauge", 0.5),
+                ("evictions".to_string(), "counter", 3.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// A layer that wraps a service of type `S` in another service, e.g. to add logging,
+/// timeouts, or authentication.
+///
+/// This is implemented for closures, and for `HList`s of middleware, which wrap the
+/// service in each layer in turn: the first element is the outermost layer, as with
+/// tower's `ServiceBuilder`.  The composed service has a fully concrete type, so no
+/// boxing or dynamic dispatch is involved.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Logged<S>(S);
+/// struct Retried<S>(S, u8);
+///
+/// let stack = hlist!(|s| Logged(s), |s| Retried(s, 3));
+/// let service: Logged<Retried<&str>> = stack.wrap("inner");
+/// assert_eq!((service.0).1, 3);
+/// # }
+/// ```
+///
+/// With the `tower` feature, an `HList` of `tower_layer::Layer`s is itself a `Layer`
+/// that composes its elements in the same order.
+pub trait Middleware<S> {
+    /// The type of the wrapped service.
+    type Service;
+
+    /// Wraps the given service.
+    fn wrap(&self, inner: S) -> Self::Service;
+}
+
+impl<S, F, Out> Middleware<S> for F
+where
+    F: Fn(S) -> Out,
+{
+    type Service = Out;
+
+    #[inline]
+    fn wrap(&self, inner: S) -> Out {
+        self(inner)
+    }
+}
+
+impl<S> Middleware<S> for HNil {
+    type Service = S;
+
+    #[inline]
+    fn wrap(&self, inner: S) -> S {
+        inner
+    }
+}
+
+impl<S, H, T> Middleware<S> for HCons<H, T>
+where
+    T: HList + Middleware<S>,
+    H: Middleware<T::Service>,
+{
+    type Service = H::Service;
+
+    #[inline]
+    fn wrap(&self, inner: S) -> Self::Service {
+        self.0.wrap(self.1.wrap(inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Prefix<S>(&'static str, S);
+
+    impl<S: Fn(u32) -> String> Prefix<S> {
+        fn call(&self, x: u32) -> String {
+            [self.0, &(self.1)(x)].concat()
+        }
+    }
+
+    #[test]
+    fn wrap_should_compose_layers_outermost_first() {
+        let stack = hlist!(
+            |s| Prefix("a:", s),
+            |s: fn(u32) -> String| move |x| s(x * 2)
+        );
+        let service = stack.wrap((|x: u32| x.to_string()) as fn(u32) -> String);
+        assert_eq!(service.call(21), "a:42");
+        assert_eq!(HNil.wrap(7), 7);
+    }
+}
@@ -0,0 +1,190 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A type whose values can be combined with an associative operation, such as addition
+/// for numbers or concatenation for strings.
+///
+/// `HList`s whose elements are all `Semigroup`s are themselves `Semigroup`s, combining
+/// element-wise, so a single `HList` can accumulate several aggregates at once:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// # #[cfg(feature = "alloc")] {
+/// let a = hlist!(1, String::from("a"), Some(2u8));
+/// let b = hlist!(2, String::from("b"), None);
+/// assert_eq!(a.combine(b), hlist!(3, String::from("ab"), Some(2u8)));
+/// # }
+/// # }
+/// ```
+pub trait Semigroup {
+    /// Combines this value with `other`.
+    fn combine(self, other: Self) -> Self;
+}
+
+/// A `Semigroup` with an identity value, which leaves any value unchanged when combined
+/// with it.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let rows = vec![hlist!(1u32, 2.5f64), hlist!(2, 0.5), hlist!(3, 1.0)];
+/// assert_eq!(Monoid::combine_all(rows), hlist!(6u32, 4.0f64));
+/// # }
+/// ```
+pub trait Monoid: Semigroup + Sized {
+    /// Returns the identity value.
+    fn empty() -> Self;
+
+    /// Combines all of the given values in order, starting from the identity value.
+    fn combine_all<I: IntoIterator<Item = Self>>(values: I) -> Self {
+        values.into_iter().fold(Self::empty(), Semigroup::combine)
+    }
+}
+
+/// Implements `Semigroup` (as addition) and `Monoid` (with zero as the identity) for
+/// each of the given numeric types.
+macro_rules! impl_numeric_monoid {
+    ($($ty:ty => $zero:expr),*) => {
+        $(
+            impl Semigroup for $ty {
+                #[inline]
+                fn combine(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+
+            impl Monoid for $ty {
+                #[inline]
+                fn empty() -> Self {
+                    $zero
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_monoid!(
+    u8 => 0, u16 => 0, u32 => 0, u64 => 0, u128 => 0, usize => 0,
+    i8 => 0, i16 => 0, i32 => 0, i64 => 0, i128 => 0, isize => 0,
+    f32 => 0.0, f64 => 0.0
+);
+
+/// Combines the contents of two `Some` values, or keeps whichever value is `Some`.
+impl<T: Semigroup> Semigroup for Option<T> {
+    fn combine(self, other: Self) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.combine(b)),
+            (a, None) => a,
+            (None, b) => b,
+        }
+    }
+}
+
+impl<T: Semigroup> Monoid for Option<T> {
+    fn empty() -> Self {
+        None
+    }
+}
+
+/// Concatenates the two strings.
+#[cfg(feature = "alloc")]
+impl Semigroup for String {
+    fn combine(mut self, other: Self) -> Self {
+        self.push_str(&other);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Monoid for String {
+    fn empty() -> Self {
+        String::new()
+    }
+}
+
+/// Concatenates the two vectors.
+#[cfg(feature = "alloc")]
+impl<T> Semigroup for Vec<T> {
+    fn combine(mut self, mut other: Self) -> Self {
+        self.append(&mut other);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Monoid for Vec<T> {
+    fn empty() -> Self {
+        Vec::new()
+    }
+}
+
+impl Semigroup for HNil {
+    #[inline]
+    fn combine(self, _: Self) -> Self {
+        HNil
+    }
+}
+
+impl Monoid for HNil {
+    #[inline]
+    fn empty() -> Self {
+        HNil
+    }
+}
+
+impl<H: Semigroup, T: HList + Semigroup> Semigroup for HCons<H, T> {
+    #[inline]
+    fn combine(self, other: Self) -> Self {
+        HCons(self.0.combine(other.0), self.1.combine(other.1))
+    }
+}
+
+impl<H: Monoid, T: HList + Monoid> Monoid for HCons<H, T> {
+    #[inline]
+    fn empty() -> Self {
+        HCons(H::empty(), T::empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combine_should_combine_elements_pairwise() {
+        assert_eq!(HNil.combine(HNil), HNil);
+        let a = hlist!(1, 2.5f32, None, Some(1u8));
+        let b = hlist!(2, 0.5f32, Some(3i64), None);
+        assert_eq!(a.combine(b), hlist!(3, 3.0f32, Some(3i64), Some(1u8)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn combine_should_concatenate_strings_and_vecs() {
+        let a = hlist!(1, String::from("a"), vec![1u8], None);
+        let b = hlist!(2, String::from("b"), vec![2u8], Some(3i64));
+        assert_eq!(
+            a.combine(b),
+            hlist!(3, String::from("ab"), vec![1u8, 2], Some(3i64))
+        );
+    }
+
+    #[test]
+    fn combine_all_should_start_from_empty() {
+        type Totals = HCons<u64, HCons<Option<f32>, HNil>>;
+        assert_eq!(Totals::combine_all(None), hlist!(0u64, None));
+        let rows = (1..=4).map(|n| hlist!(n, Some(n as f32 / 2.0)));
+        assert_eq!(Totals::combine_all(rows), hlist!(10u64, Some(5.0f32)));
+    }
+}
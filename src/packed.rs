@@ -0,0 +1,360 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// An `HList` that can be stored in a flat, padding-minimizing representation.
+///
+/// Because each `HCons` is its own struct, the compiler lays out a nested `HList` one
+/// cons cell at a time and cannot move small elements into the padding of larger ones.
+/// For example, `HCons<u8, HCons<u64, HCons<u8, HNil>>>` occupies 24 bytes, while the
+/// equivalent flat tuple `(u8, u64, u8)` occupies 16 bytes because the compiler is free
+/// to reorder its fields.
+///
+/// This trait is implemented for `HList`s of up to 32 elements, using a tuple as the
+/// flat representation.
+pub trait Packable: HList + Sized {
+    /// The flat representation of this `HList`.
+    type Packed;
+
+    /// An `HList` of shared references to each element, in logical order.
+    type Refs<'a>: HList
+    where
+        Self: 'a;
+
+    /// An `HList` of mutable references to each element, in logical order.
+    type Muts<'a>: HList
+    where
+        Self: 'a;
+
+    /// Converts this `HList` into its flat representation.
+    fn pack(self) -> Self::Packed;
+
+    /// Converts the flat representation back into an `HList`.
+    fn unpack(packed: Self::Packed) -> Self;
+
+    /// Returns shared references to each element of the flat representation.
+    fn packed_refs(packed: &Self::Packed) -> Self::Refs<'_>;
+
+    /// Returns mutable references to each element of the flat representation.
+    fn packed_muts(packed: &mut Self::Packed) -> Self::Muts<'_>;
+}
+
+impl Packable for HNil {
+    type Packed = ();
+    type Refs<'a> = HNil;
+    type Muts<'a> = HNil;
+
+    fn pack(self) -> Self::Packed {}
+
+    fn unpack(_packed: Self::Packed) -> Self {
+        HNil
+    }
+
+    fn packed_refs(_packed: &Self::Packed) -> Self::Refs<'_> {
+        HNil
+    }
+
+    fn packed_muts(_packed: &mut Self::Packed) -> Self::Muts<'_> {
+        HNil
+    }
+}
+
+/// Implements `Packable` for the `HList` with the given elements, and then for each
+/// shorter `HList` formed by dropping elements from the front.
+macro_rules! impl_packable {
+    () => {};
+    ($H1:ident $h1:ident $(, $H:ident $h:ident)*) => {
+        impl<$H1, $($H),*> Packable for hlist_type!($H1 $(, $H)*) {
+            type Packed = ($H1, $($H,)*);
+            type Refs<'a> = hlist_type!(&'a $H1 $(, &'a $H)*) where Self: 'a;
+            type Muts<'a> = hlist_type!(&'a mut $H1 $(, &'a mut $H)*) where Self: 'a;
+
+            #[inline]
+            fn pack(self) -> Self::Packed {
+                let hlist_pattern!($h1 $(, $h)*) = self;
+                ($h1, $($h,)*)
+            }
+
+            #[inline]
+            fn unpack(packed: Self::Packed) -> Self {
+                let ($h1, $($h,)*) = packed;
+                hlist!($h1 $(, $h)*)
+            }
+
+            #[inline]
+            fn packed_refs(packed: &Self::Packed) -> Self::Refs<'_> {
+                let ($h1, $($h,)*) = packed;
+                hlist!($h1 $(, $h)*)
+            }
+
+            #[inline]
+            fn packed_muts(packed: &mut Self::Packed) -> Self::Muts<'_> {
+                let ($h1, $($h,)*) = packed;
+                hlist!($h1 $(, $h)*)
+            }
+        }
+
+        impl_packable!($($H $h),*);
+    };
+}
+
+impl_packable!(
+    A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8,
+    A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14, A15 a15, A16 a16,
+    A17 a17, A18 a18, A19 a19, A20 a20, A21 a21, A22 a22, A23 a23, A24 a24,
+    A25 a25, A26 a26, A27 a27, A28 a28, A29 a29, A30 a30, A31 a31, A32 a32
+);
+
+/// Allows for constant-time access to the element at index `N` of the flat
+/// representation of a `Packable` `HList`.
+///
+/// Accessing the `N`th element of a nested `HList` walks `N` cons cells, which the
+/// optimizer must flatten; the flat representation instead exposes each element as a
+/// direct field access.
+pub trait PackedIndex<const N: usize>: Packable {
+    /// The type of the element at index `N`.
+    type Output;
+
+    /// Returns a reference to the element at index `N`.
+    fn packed_get(packed: &Self::Packed) -> &Self::Output;
+
+    /// Returns a mutable reference to the element at index `N`.
+    fn packed_get_mut(packed: &mut Self::Packed) -> &mut Self::Output;
+}
+
+/// Implements `PackedIndex` for each prefix of the given `index Type` pairs.
+macro_rules! impl_packed_index {
+    (@prefixes [$($idx:tt $H:ident)*];) => {};
+    (@prefixes [$($idx:tt $H:ident)*]; $nidx:tt $N:ident $(, $ridx:tt $R:ident)*) => {
+        impl_packed_index!(@arity [$($H)* $N] $($idx $H)* $nidx $N);
+        impl_packed_index!(@prefixes [$($idx $H)* $nidx $N]; $($ridx $R),*);
+    };
+    (@arity $all:tt $($idx:tt $H:ident)*) => {
+        $(impl_packed_index!(@index $all $idx $H);)*
+    };
+    (@index [$($All:ident)*] $idx:tt $H:ident) => {
+        impl<$($All),*> PackedIndex<$idx> for hlist_type!($($All),*) {
+            type Output = $H;
+
+            #[inline(always)]
+            fn packed_get(packed: &Self::Packed) -> &Self::Output {
+                &packed.$idx
+            }
+
+            #[inline(always)]
+            fn packed_get_mut(packed: &mut Self::Packed) -> &mut Self::Output {
+                &mut packed.$idx
+            }
+        }
+    };
+}
+
+impl_packed_index!(@prefixes [];
+    0 A1, 1 A2, 2 A3, 3 A4, 4 A5, 5 A6, 6 A7, 7 A8,
+    8 A9, 9 A10, 10 A11, 11 A12, 12 A13, 13 A14, 14 A15, 15 A16,
+    16 A17, 17 A18, 18 A19, 19 A20, 20 A21, 21 A22, 22 A23, 23 A24,
+    24 A25, 25 A26, 26 A27, 27 A28, 28 A29, 29 A30, 30 A31, 31 A32
+);
+
+/// Storage for an `HList` that uses its flat `Packable` representation, which places
+/// the elements in whatever physical order minimizes padding and allows constant-time
+/// access to each element via `get`.  The logical order of the elements is preserved by
+/// all accessors and conversions.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # use core::mem::size_of;
+/// # fn main() {
+/// type Nested = HCons<u8, HCons<u64, HCons<u8, HNil>>>;
+/// assert!(size_of::<PackedHList<Nested>>() < size_of::<Nested>());
+///
+/// let mut packed = PackedHList::new(hlist!(1u8, 2u64, 3u8));
+/// *packed.get_mut::<1>() += 40;
+/// assert_eq!(*packed.get::<2>(), 3u8);
+/// assert_eq!(packed.as_hlist(), hlist!(&1u8, &42u64, &3u8));
+/// assert_eq!(packed.into_hlist(), hlist!(1u8, 42u64, 3u8));
+/// # }
+/// ```
+pub struct PackedHList<H: Packable> {
+    packed: H::Packed,
+}
+
+assert_same_layout!(PackedHList<hlist_type!(u8, u64, u8)>, (u8, u64, u8));
+
+impl<H: Packable> PackedHList<H> {
+    /// Creates a new `PackedHList` holding the elements of the given `HList`.
+    pub fn new(hlist: H) -> Self {
+        PackedHList {
+            packed: hlist.pack(),
+        }
+    }
+
+    /// Consumes this `PackedHList`, returning the elements as a plain `HList`.
+    pub fn into_hlist(self) -> H {
+        H::unpack(self.packed)
+    }
+
+    /// Returns an `HList` of shared references to the elements, in logical order.
+    pub fn as_hlist(&self) -> H::Refs<'_> {
+        H::packed_refs(&self.packed)
+    }
+
+    /// Returns an `HList` of mutable references to the elements, in logical order.
+    pub fn as_hlist_mut(&mut self) -> H::Muts<'_> {
+        H::packed_muts(&mut self.packed)
+    }
+
+    /// Returns a reference to the element at (logical) index `N`, in constant time.
+    pub fn get<const N: usize>(&self) -> &<H as PackedIndex<N>>::Output
+    where
+        H: PackedIndex<N>,
+    {
+        H::packed_get(&self.packed)
+    }
+
+    /// Returns a mutable reference to the element at (logical) index `N`, in constant time.
+    pub fn get_mut<const N: usize>(&mut self) -> &mut <H as PackedIndex<N>>::Output
+    where
+        H: PackedIndex<N>,
+    {
+        H::packed_get_mut(&mut self.packed)
+    }
+}
+
+impl<H: Packable> From<H> for PackedHList<H> {
+    fn from(hlist: H) -> Self {
+        PackedHList::new(hlist)
+    }
+}
+
+impl<H: Packable> FromHList<H> for PackedHList<H> {
+    fn from_hlist(hlist: H) -> Self {
+        PackedHList::new(hlist)
+    }
+}
+
+impl<H: Packable> IntoHList<H> for PackedHList<H> {
+    fn into_hlist(self) -> H {
+        H::unpack(self.packed)
+    }
+}
+
+impl<H: Packable> ToHList<H> for PackedHList<H>
+where
+    H::Packed: Clone,
+{
+    fn to_hlist(&self) -> H {
+        H::unpack(self.packed.clone())
+    }
+}
+
+impl<H: Packable> Clone for PackedHList<H>
+where
+    H::Packed: Clone,
+{
+    fn clone(&self) -> Self {
+        PackedHList {
+            packed: self.packed.clone(),
+        }
+    }
+}
+
+impl<H: Packable> Copy for PackedHList<H> where H::Packed: Copy {}
+
+impl<H: Packable> fmt::Debug for PackedHList<H>
+where
+    H::Packed: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PackedHList").field(&self.packed).finish()
+    }
+}
+
+impl<H: Packable> PartialEq for PackedHList<H>
+where
+    H::Packed: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.packed == other.packed
+    }
+}
+
+impl<H: Packable> Eq for PackedHList<H> where H::Packed: Eq {}
+
+impl<H: Packable> Hash for PackedHList<H>
+where
+    H::Packed: Hash,
+{
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.packed.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem::{align_of, size_of, size_of_val};
+
+    #[test]
+    fn packed_hlist_should_be_no_larger_than_the_nested_hlist() {
+        type Mixed = HCons<u8, HCons<u64, HCons<u16, HCons<u8, HCons<u32, HNil>>>>>;
+        assert_eq!(size_of::<Mixed>(), 32);
+        assert_eq!(size_of::<PackedHList<Mixed>>(), 16);
+        assert_eq!(align_of::<PackedHList<Mixed>>(), align_of::<Mixed>());
+
+        type Uniform = HCons<u32, HCons<u32, HNil>>;
+        assert_eq!(size_of::<PackedHList<Uniform>>(), size_of::<Uniform>());
+        assert_eq!(size_of::<PackedHList<HNil>>(), 0);
+    }
+
+    #[test]
+    fn packed_hlist_should_preserve_logical_order() {
+        let hlist = hlist!(1u8, "two", 3u64, 'f');
+        let mut packed = PackedHList::new(hlist);
+        assert_eq!(packed.as_hlist(), hlist!(&1u8, &"two", &3u64, &'f'));
+
+        {
+            let HCons(a, HCons(_, HCons(c, _))) = packed.as_hlist_mut();
+            *a += 10;
+            *c *= 2;
+        }
+        assert_eq!(packed.to_hlist(), hlist!(11u8, "two", 6u64, 'f'));
+        assert_eq!(packed.into_hlist(), hlist!(11u8, "two", 6u64, 'f'));
+    }
+
+    #[test]
+    fn packed_hlist_should_support_sixteen_elements() {
+        let hlist = hlist!(
+            0u8, 1u64, 2u8, 3u64, 4u8, 5u64, 6u8, 7u64, 8u8, 9u64, 10u8, 11u64, 12u8, 13u64, 14u8,
+            15u64
+        );
+        let packed = PackedHList::from_hlist(hlist);
+        assert_eq!(size_of_val(&packed), 72);
+        assert_eq!(size_of_val(&hlist), 128);
+        assert_eq!(packed.into_hlist(), hlist);
+    }
+
+    #[test]
+    fn packed_hlist_should_support_positional_access_to_thirty_two_elements() {
+        let mut packed = PackedHList::new(hlist!(
+            0u8, 1u16, 2u32, 3u64, 4u8, 5u16, 6u32, 7u64, 8u8, 9u16, 10u32, 11u64, 12u8, 13u16,
+            14u32, 15u64, 16u8, 17u16, 18u32, 19u64, 20u8, 21u16, 22u32, 23u64, 24u8, 25u16, 26u32,
+            27u64, 28u8, 29u16, 30u32, 'z'
+        ));
+        assert_eq!(*packed.get::<0>(), 0u8);
+        assert_eq!(*packed.get::<15>(), 15u64);
+        assert_eq!(*packed.get::<31>(), 'z');
+
+        *packed.get_mut::<30>() += 100;
+        let hlist = packed.into_hlist();
+        let HCons(_, HCons(second, _)) = hlist;
+        assert_eq!(second, 1u16);
+        assert_eq!(PackedHList::new(hlist).get::<30>(), &130u32);
+    }
+}
@@ -0,0 +1,285 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+
+/// Typestate marker for a field of a partial record that has been set.  Fields that
+/// have not been set are instead marked by a type named after the field, which is
+/// generated by the `PartialSupport` derive.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Set;
+
+/// Implemented only by the `Set` typestate marker, so that `finalize` can require every
+/// field of a partial record to have been set.
+///
+/// The `PartialSupport` derive generates a `<Name>Partial` struct with a `set_<field>`
+/// setter and a `<field>` getter for each field, and a `partial()` constructor on the
+/// struct itself.  It requires the struct to also derive `HListSupport`.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(Debug, PartialEq, HListSupport, PartialSupport)]
+/// struct Endpoint {
+///     host: &'static str,
+///     port: u16,
+/// }
+///
+/// let partial = Endpoint::partial().set_port(8080);
+/// assert_eq!(partial.host(), None);
+/// let endpoint = partial.set_host("localhost").finalize();
+/// assert_eq!(endpoint, Endpoint { host: "localhost", port: 8080 });
+/// ```
+///
+/// Finalizing before every field has been set does not compile, and the error names
+/// the missing field (`endpoint_partial::host` here):
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// #[derive(HListSupport, PartialSupport)]
+/// struct Endpoint {
+///     host: &'static str,
+///     port: u16,
+/// }
+///
+/// let endpoint = Endpoint::partial().set_port(8080).finalize();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "the field `{Self}` has not been set",
+    label = "call the setter for this field before `finalize`"
+)]
+pub trait IsSet {}
+
+impl IsSet for Set {}
+
+/// Allows for converting a labelled `HList` of optional values into a labelled `HList`
+/// of values, if every value is present.
+pub trait UnwrapFields {
+    /// The labelled `HList` of values.
+    type Output: HList;
+
+    /// Returns the values, or `None` if any of them is missing.
+    fn unwrap_fields(self) -> Option<Self::Output>;
+}
+
+impl UnwrapFields for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn unwrap_fields(self) -> Option<HNil> {
+        Some(HNil)
+    }
+}
+
+impl<L, T, Tail> UnwrapFields for HCons<Field<L, Option<T>>, Tail>
+where
+    Tail: HList + UnwrapFields,
+{
+    type Output = HCons<Field<L, T>, Tail::Output>;
+
+    #[inline]
+    fn unwrap_fields(self) -> Option<Self::Output> {
+        Some(HCons(Field::new(self.0.value?), self.1.unwrap_fields()?))
+    }
+}
+
+/// Allows for converting an `HList` into its partial counterpart, in which each element
+/// `T` becomes an `Option<T>`.
+pub trait Lift: HList {
+    /// The partial `HList`, with an `Option` in place of each element.
+    type Partial: PartialHList<Full = Self>;
+
+    /// Returns the partial `HList` with every element present.
+    fn lift(self) -> Self::Partial;
+}
+
+/// Shorthand for the partial counterpart of the `HList` type `L`.
+pub type PartialOf<L> = <L as Lift>::Partial;
+
+/// An `HList` of `Option`s that records some subset of the elements of its `Full`
+/// counterpart, such as the changed fields of a PATCH-style partial update.
+///
+/// Partial lists are combined with `merge`, in which present elements on the right
+/// replace those on the left, and are converted back with `unwrap_all` once every element
+/// is present:
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(Clone, Debug, PartialEq, HListSupport)]
+/// struct Profile {
+///     name: &'static str,
+///     age: u8,
+/// }
+///
+/// let current = Profile { name: "kim", age: 30 }.into_hlist().lift();
+/// let mut update = PartialOf::<HCons<&str, HCons<u8, HNil>>>::empty();
+/// update.1 .0 = Some(31);
+///
+/// let merged = current.merge(update).unwrap_all().unwrap();
+/// assert_eq!(Profile::from_hlist(merged), Profile { name: "kim", age: 31 });
+/// ```
+pub trait PartialHList: HList + Sized {
+    /// The `HList` of the values held by this partial list.
+    type Full: Lift<Partial = Self>;
+
+    /// Returns the partial `HList` with every element missing.
+    fn empty() -> Self;
+
+    /// Combines this list with `other`, taking each element from `other` where it is
+    /// present and from `self` otherwise.
+    fn merge(self, other: Self) -> Self;
+
+    /// Returns the full `HList`, or `None` if any element is missing.
+    fn unwrap_all(self) -> Option<Self::Full>;
+}
+
+impl Lift for HNil {
+    type Partial = HNil;
+
+    #[inline(always)]
+    fn lift(self) -> HNil {
+        HNil
+    }
+}
+
+impl<H, T: Lift> Lift for HCons<H, T> {
+    type Partial = HCons<Option<H>, T::Partial>;
+
+    #[inline]
+    fn lift(self) -> Self::Partial {
+        HCons(Some(self.0), self.1.lift())
+    }
+}
+
+impl PartialHList for HNil {
+    type Full = HNil;
+
+    #[inline(always)]
+    fn empty() -> Self {
+        HNil
+    }
+
+    #[inline(always)]
+    fn merge(self, _other: Self) -> Self {
+        HNil
+    }
+
+    #[inline(always)]
+    fn unwrap_all(self) -> Option<HNil> {
+        Some(HNil)
+    }
+}
+
+impl<H, T: PartialHList> PartialHList for HCons<Option<H>, T> {
+    type Full = HCons<H, T::Full>;
+
+    #[inline]
+    fn empty() -> Self {
+        HCons(None, T::empty())
+    }
+
+    #[inline]
+    fn merge(self, other: Self) -> Self {
+        HCons(other.0.or(self.0), self.1.merge(other.1))
+    }
+
+    #[inline]
+    fn unwrap_all(self) -> Option<Self::Full> {
+        Some(HCons(self.0?, self.1.unwrap_all()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::{HListSupport, PartialSupport};
+
+    #[derive(Debug, PartialEq, HListSupport, PartialSupport)]
+    struct Endpoint {
+        host: &'static str,
+        port: u16,
+    }
+
+    #[test]
+    fn finalize_should_build_the_struct_once_every_field_is_set() {
+        let partial = Endpoint::partial().set_port(80);
+        assert_eq!(partial.port(), Some(&80));
+        assert_eq!(partial.host(), None);
+
+        let endpoint = partial.set_host("example.com").set_port(8080).finalize();
+        assert_eq!(
+            endpoint,
+            Endpoint {
+                host: "example.com",
+                port: 8080
+            }
+        );
+    }
+    #[derive(Clone, Debug, PartialEq, HListSupport, PartialSupport)]
+    struct Request<'a, B: Clone>
+    where
+        B: PartialEq,
+    {
+        path: &'a str,
+        body: B,
+        #[hlist(skip)]
+        attempts: u8,
+    }
+
+    #[test]
+    fn generic_structs_should_be_supported() {
+        let partial = Request::partial().set_body(vec![1u8]);
+        assert_eq!(partial.body(), Some(&vec![1u8]));
+        let path = String::from("/items");
+        let request = partial.set_path(&path).finalize();
+        assert_eq!(
+            request,
+            Request {
+                path: "/items",
+                body: vec![1u8],
+                attempts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn partial_hlists_should_merge_from_the_right() {
+        type Full = HCons<u8, HCons<&'static str, HCons<bool, HNil>>>;
+        let empty = PartialOf::<Full>::empty();
+        assert_eq!(empty, hlist!(None, None, None));
+        assert_eq!(empty.unwrap_all(), None);
+
+        let left = hlist!(Some(1u8), None, Some(true));
+        let right = hlist!(Some(2u8), Some("b"), None);
+        assert_eq!(left.merge(right), hlist!(Some(2u8), Some("b"), Some(true)));
+        assert_eq!(right.merge(left), hlist!(Some(1u8), Some("b"), Some(true)));
+        assert_eq!(left.merge(empty), left);
+
+        let full: Full = hlist!(1, "a", false);
+        assert_eq!(full.lift().unwrap_all(), Some(full));
+        assert_eq!(
+            empty.merge(right).merge(left).unwrap_all(),
+            Some(hlist!(1, "b", true))
+        );
+    }
+
+    #[test]
+    fn partial_hlists_should_update_structs() {
+        let endpoint = Endpoint {
+            host: "example.com",
+            port: 80,
+        };
+        let mut update = PartialOf::<HCons<&str, HCons<u16, HNil>>>::empty();
+        update.1 .0 = Some(443);
+        let updated = endpoint.into_hlist().lift().merge(update).unwrap_all();
+        assert_eq!(
+            updated.map(Endpoint::from_hlist),
+            Some(Endpoint {
+                host: "example.com",
+                port: 443
+            })
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for splitting an `HList` into a `Prefix` containing its first elements and an
+/// `HList` of the remaining elements.  The prefix is determined by the `Prefix` type,
+/// which must match the leading element types of `Self` exactly.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two", 3.0f32, 'f');
+/// let (prefix, rest): (HCons<u8, HCons<&str, HNil>>, _) = hlist.into_prefix();
+/// assert_eq!(prefix, hlist!(1u8, "two"));
+/// assert_eq!(rest, hlist!(3.0f32, 'f'));
+/// # }
+/// ```
+pub trait IntoPrefix<Prefix> {
+    /// The `HList` of elements following the prefix.
+    type Rest: HList;
+
+    /// Consumes this list, returning the prefix and the remaining elements separately.
+    fn into_prefix(self) -> (Prefix, Self::Rest);
+}
+
+impl<L: HList> IntoPrefix<HNil> for L {
+    type Rest = L;
+
+    #[inline]
+    fn into_prefix(self) -> (HNil, Self::Rest) {
+        (HNil, self)
+    }
+}
+
+impl<H, T, PrefixTail> IntoPrefix<HCons<H, PrefixTail>> for HCons<H, T>
+where
+    T: HList + IntoPrefix<PrefixTail>,
+    PrefixTail: HList,
+{
+    type Rest = T::Rest;
+
+    #[inline]
+    fn into_prefix(self) -> (HCons<H, PrefixTail>, Self::Rest) {
+        let (prefix, rest) = self.1.into_prefix();
+        (HCons(self.0, prefix), rest)
+    }
+}
+
+/// Allows for dropping the elements of an `HList` in order from last to first, which is
+/// the reverse of the order used when an `HList` is dropped normally.  This is useful
+/// when the elements are guards that must be released in the opposite order from which
+/// they were acquired.
+pub trait DropReversed {
+    /// Drops each element of this list, starting with the last.
+    fn drop_reversed(self);
+}
+
+impl DropReversed for HNil {
+    #[inline]
+    fn drop_reversed(self) {}
+}
+
+impl<H, T> DropReversed for HCons<H, T>
+where
+    T: HList + DropReversed,
+{
+    #[inline]
+    fn drop_reversed(self) {
+        let HCons(head, tail) = self;
+        tail.drop_reversed();
+        drop(head);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct Guard<'a>(&'static str, &'a RefCell<Vec<&'static str>>);
+
+    impl Drop for Guard<'_> {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    #[test]
+    fn hlist_should_drop_elements_in_order() {
+        let log = RefCell::new(Vec::new());
+        drop(hlist!(Guard("a", &log), Guard("b", &log), Guard("c", &log)));
+        assert_eq!(*log.borrow(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn drop_reversed_should_drop_elements_in_reverse_order() {
+        let log = RefCell::new(Vec::new());
+        hlist!(Guard("a", &log), Guard("b", &log), Guard("c", &log)).drop_reversed();
+        assert_eq!(*log.borrow(), vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn into_parts_should_allow_dropping_in_any_order() {
+        let log = RefCell::new(Vec::new());
+        let hlist = hlist!(Guard("a", &log), Guard("b", &log), Guard("c", &log));
+
+        let (a, rest) = hlist.into_parts();
+        let (b, rest) = rest.into_parts();
+        drop(b);
+        drop(rest);
+        drop(a);
+        assert_eq!(*log.borrow(), vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn into_prefix_should_split_off_leading_elements() {
+        let log = RefCell::new(Vec::new());
+        let hlist = hlist!(Guard("a", &log), Guard("b", &log), 3u8, Guard("d", &log));
+
+        let (prefix, rest): (HCons<Guard, HCons<Guard, HNil>>, _) = hlist.into_prefix();
+        assert_eq!(*rest.head(), 3u8);
+        drop(rest);
+        assert_eq!(*log.borrow(), vec!["d"]);
+        drop(prefix);
+        assert_eq!(*log.borrow(), vec!["d", "a", "b"]);
+
+        let (empty, all): (HNil, _) = hlist!(1u8, 2u8).into_prefix();
+        assert_eq!(empty, HNil);
+        assert_eq!(all, hlist!(1u8, 2u8));
+    }
+}
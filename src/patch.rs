@@ -0,0 +1,254 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use core::fmt;
+use core::marker::PhantomData;
+
+/// A set of field-level changes to a value of type `S`.
+///
+/// The changes are stored as a labelled `HList` with one `Field<L, Option<T>>` per field
+/// of `S`, where `Some` holds the new value of a changed field.  A patch is computed by
+/// `diff` and can be applied to any value of type `S` (not just the one it was computed
+/// from).  With the `toml` feature, a patch can be converted to and from a TOML table
+/// with one entry per changed field.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(Clone, Debug, PartialEq, HListSupport)]
+/// struct Settings {
+///     theme: &'static str,
+///     font_size: u8,
+/// }
+///
+/// let old = Settings { theme: "light", font_size: 12 };
+/// let new = Settings { theme: "dark", font_size: 12 };
+/// let patch = Patch::diff(&old, &new);
+/// assert_eq!(patch.changed_count(), 1);
+///
+/// let other = Settings { theme: "light", font_size: 16 };
+/// assert_eq!(patch.apply(other), Settings { theme: "dark", font_size: 16 });
+/// ```
+pub struct Patch<S, Fields> {
+    fields: Fields,
+    target: PhantomData<fn(S) -> S>,
+}
+
+impl<S, Fields: HList> Patch<S, Fields> {
+    /// Creates a patch from the given labelled `HList` of optional new values.
+    pub fn from_fields(fields: Fields) -> Self {
+        Patch {
+            fields,
+            target: PhantomData,
+        }
+    }
+
+    /// Computes the patch that changes `old` into `new`.
+    pub fn diff<L>(old: &S, new: &S) -> Self
+    where
+        S: ToLabelledHList<L>,
+        L: HList + DiffFields<Patch = Fields>,
+    {
+        Patch::from_fields(old.to_labelled_hlist().diff_fields(new.to_labelled_hlist()))
+    }
+
+    /// Applies the changes in this patch to the given value.
+    pub fn apply<L>(self, value: S) -> S
+    where
+        S: IntoLabelledHList<L> + FromLabelledHList<L>,
+        L: HList + ApplyFields<Fields>,
+    {
+        S::from_labelled_hlist(value.into_labelled_hlist().apply_fields(self.fields))
+    }
+
+    /// Returns the number of fields changed by this patch.
+    pub fn changed_count(&self) -> usize
+    where
+        Fields: PatchFields,
+    {
+        self.fields.changed_count()
+    }
+
+    /// Returns true if this patch changes no fields.
+    pub fn is_empty(&self) -> bool
+    where
+        Fields: PatchFields,
+    {
+        self.changed_count() == 0
+    }
+
+    /// Returns the labelled `HList` of optional new values.
+    pub fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    /// Consumes this patch, returning the labelled `HList` of optional new values.
+    pub fn into_fields(self) -> Fields {
+        self.fields
+    }
+}
+
+impl<S, Fields: Clone> Clone for Patch<S, Fields> {
+    fn clone(&self) -> Self {
+        Patch {
+            fields: self.fields.clone(),
+            target: PhantomData,
+        }
+    }
+}
+
+impl<S, Fields: PartialEq> PartialEq for Patch<S, Fields> {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields == other.fields
+    }
+}
+
+impl<S, Fields: fmt::Debug> fmt::Debug for Patch<S, Fields> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Patch").field(&self.fields).finish()
+    }
+}
+
+/// Allows for computing the changes between two labelled `HList`s of the same type.
+pub trait DiffFields {
+    /// The labelled `HList` of optional new values.
+    type Patch: HList;
+
+    /// Returns the value of each field of `new` that differs from that of `self`.
+    fn diff_fields(self, new: Self) -> Self::Patch;
+}
+
+impl DiffFields for HNil {
+    type Patch = HNil;
+
+    fn diff_fields(self, _new: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl<L, T, Tail> DiffFields for HCons<Field<L, T>, Tail>
+where
+    T: PartialEq,
+    Tail: HList + DiffFields,
+{
+    type Patch = HCons<Field<L, Option<T>>, Tail::Patch>;
+
+    fn diff_fields(self, new: Self) -> Self::Patch {
+        let changed = if self.0.value != new.0.value {
+            Some(new.0.value)
+        } else {
+            None
+        };
+        HCons(Field::new(changed), self.1.diff_fields(new.1))
+    }
+}
+
+/// Allows for applying a labelled `HList` of optional new values to a labelled `HList`.
+pub trait ApplyFields<Patch> {
+    /// Replaces the value of each field for which the patch holds a new value.
+    fn apply_fields(self, patch: Patch) -> Self;
+}
+
+impl ApplyFields<HNil> for HNil {
+    fn apply_fields(self, _patch: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl<L, T, Tail, PatchTail> ApplyFields<HCons<Field<L, Option<T>>, PatchTail>>
+    for HCons<Field<L, T>, Tail>
+where
+    Tail: HList + ApplyFields<PatchTail>,
+    PatchTail: HList,
+{
+    fn apply_fields(self, patch: HCons<Field<L, Option<T>>, PatchTail>) -> Self {
+        let head = match patch.0.value {
+            Some(value) => Field::new(value),
+            None => self.0,
+        };
+        HCons(head, self.1.apply_fields(patch.1))
+    }
+}
+
+/// Allows for inspecting a labelled `HList` of optional new values.
+pub trait PatchFields {
+    /// Returns the number of fields that hold a new value.
+    fn changed_count(&self) -> usize;
+
+    /// Calls `f` with the name of each field that holds a new value.
+    fn for_each_changed(&self, f: &mut dyn FnMut(&'static str));
+}
+
+impl PatchFields for HNil {
+    fn changed_count(&self) -> usize {
+        0
+    }
+
+    fn for_each_changed(&self, _f: &mut dyn FnMut(&'static str)) {}
+}
+
+impl<L, T, Tail> PatchFields for HCons<Field<L, Option<T>>, Tail>
+where
+    L: Label,
+    Tail: HList + PatchFields,
+{
+    fn changed_count(&self) -> usize {
+        usize::from(self.0.value.is_some()) + self.1.changed_count()
+    }
+
+    fn for_each_changed(&self, f: &mut dyn FnMut(&'static str)) {
+        if self.0.value.is_some() {
+            f(L::NAME);
+        }
+        self.1.for_each_changed(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Document {
+        title: String,
+        revision: u32,
+        draft: bool,
+    }
+
+    #[test]
+    fn diff_and_apply_should_only_touch_changed_fields() {
+        let old = Document {
+            title: "a".to_string(),
+            revision: 1,
+            draft: true,
+        };
+        let new = Document {
+            title: "a".to_string(),
+            revision: 2,
+            draft: false,
+        };
+
+        let patch = Patch::diff(&old, &new);
+        let mut changed = Vec::new();
+        patch
+            .fields()
+            .for_each_changed(&mut |name| changed.push(name));
+        assert_eq!(changed, ["revision", "draft"]);
+
+        let other = Document {
+            title: "b".to_string(),
+            revision: 1,
+            draft: true,
+        };
+        let patched = patch.clone().apply(other);
+        assert_eq!(patched.title, "b");
+        assert_eq!((patched.revision, patched.draft), (2, false));
+
+        assert!(Patch::diff(&new, &new).is_empty());
+        assert_eq!(patch.apply(old), new);
+    }
+}
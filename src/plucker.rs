@@ -0,0 +1,95 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+
+/// Allows for removing the element of type `Target` from an `HList`, returning
+/// it along with the remaining elements.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// `Target` within the list.  As with `Selector`, the list must contain exactly one
+/// element of type `Target`.
+///
+/// `HCons` also provides `pluck` as an inherent method, so the target type can be given
+/// with a turbofish:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, String::from("two"), 3u32);
+/// let (value, rest) = hlist.pluck::<String, _>();
+/// assert_eq!(value, "two");
+/// assert_eq!(rest, hlist!(1u8, 3u32));
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "type `{Target}` does not appear in this `HList`",
+    label = "no element of type `{Target}` to pluck",
+    note = "the full list type is named by the outermost `required for` note below",
+    note = "when sculpting, each element type appears as a `*mut` pointer to the element"
+)]
+pub trait Plucker<Target, Index> {
+    /// The `HList` that is left over after removing `Target`.
+    type Remainder: HList;
+
+    /// Removes the element of type `Target`, returning it along with the remaining elements.
+    fn pluck(self) -> (Target, Self::Remainder);
+}
+
+impl<H, T: HList> HCons<H, T> {
+    /// Removes the element of type `Target`, returning it along with the remaining elements.
+    #[inline(always)]
+    pub fn pluck<Target, Index>(self) -> (Target, <Self as Plucker<Target, Index>>::Remainder)
+    where
+        Self: Plucker<Target, Index>,
+    {
+        Plucker::pluck(self)
+    }
+}
+
+impl<T, Tail> Plucker<T, Here> for HCons<T, Tail>
+where
+    Tail: HList,
+{
+    type Remainder = Tail;
+
+    #[inline(always)]
+    fn pluck(self) -> (T, Self::Remainder) {
+        (self.0, self.1)
+    }
+}
+
+impl<H, Tail, T, TailIndex> Plucker<T, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + Plucker<T, TailIndex>,
+{
+    type Remainder = HCons<H, Tail::Remainder>;
+
+    #[inline(always)]
+    fn pluck(self) -> (T, Self::Remainder) {
+        let (target, remainder) = self.1.pluck();
+        (target, HCons(self.0, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pluck_should_remove_the_element_of_the_given_type() {
+        let hlist = hlist!(1u8, "two", 3u32, 'f');
+        let (value, remainder): (&str, _) = hlist.pluck();
+        assert_eq!(value, "two");
+        assert_eq!(remainder, hlist!(1u8, 3u32, 'f'));
+
+        let (value, remainder) = remainder.pluck::<char, _>();
+        assert_eq!(value, 'f');
+        let (value, remainder) = remainder.pluck::<u8, _>();
+        assert_eq!((value, remainder), (1u8, hlist!(3u32)));
+    }
+}
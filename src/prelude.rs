@@ -0,0 +1,226 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! A single import for working with `HList`s through method syntax.
+//!
+//! `use pl_hlist::prelude::*;` brings in the core types, macros, conversion traits, and
+//! derives, along with `HListOps`, which provides the common structural operations as
+//! methods on every `HList`, so that an expression using several of them does not need
+//! each of their traits to be imported:
+//!
+//! ```
+//! use pl_hlist::prelude::*;
+//!
+//! #[derive(Clone, Debug, PartialEq, HListSupport)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! let hlist = Point { x: 1, y: 2 }.into_hlist().append(hlist!("label"));
+//! let (label, rest) = hlist.pluck::<&str, _>();
+//! assert_eq!(label, "label");
+//! assert_eq!(rest.len(), 2);
+//! assert_eq!(Point::from_hlist(rest.reverse()), Point { x: 2, y: 1 });
+//! ```
+//!
+//! The methods of `HListOps` have the same names as those of the individual traits, so
+//! the prelude is an alternative to the crate-level glob import (`use pl_hlist::*;`)
+//! rather than an addition to it: importing both makes the method calls ambiguous.
+
+// `HList` names both the trait and the type macro
+pub use crate::{hlist, hlist_pat, HList};
+pub use crate::{label, path, Lens, PathAccess};
+pub use crate::{FromHList, HCons, HNil, IntoHList, ToHList};
+pub use crate::{FromLabelledHList, IntoLabelledHList, ToLabelledHList};
+pub use crate::{Func, HListView, HListViewMut, Poly};
+pub use pl_hlist_derive::HListSupport;
+
+/// Provides the common structural operations on `HList`s as methods, each delegating to
+/// the trait of the same name.
+pub trait HListOps: HList + Sized {
+    /// Appends `rhs` to the end of this list (see `Append`).
+    #[inline(always)]
+    fn append<RHS>(self, rhs: RHS) -> <Self as crate::Append<RHS>>::Output
+    where
+        Self: crate::Append<RHS>,
+    {
+        crate::Append::append(self, rhs)
+    }
+
+    /// Prepends `lhs` to the front of this list (see `Prepend`).
+    #[inline(always)]
+    fn prepend<LHS>(self, lhs: LHS) -> <Self as crate::Prepend<LHS>>::Output
+    where
+        Self: crate::Prepend<LHS>,
+    {
+        crate::Prepend::prepend(self, lhs)
+    }
+
+    /// Returns this list with its elements in the opposite order (see `Reverse`).
+    #[inline(always)]
+    fn reverse(self) -> <Self as crate::Reverse>::Output
+    where
+        Self: crate::Reverse,
+    {
+        crate::Reverse::reverse(self)
+    }
+
+    /// Concatenates the inner lists of this list of lists (see `Flatten`).
+    #[inline(always)]
+    fn flatten(self) -> <Self as crate::Flatten>::Output
+    where
+        Self: crate::Flatten,
+    {
+        crate::Flatten::flatten(self)
+    }
+
+    /// Returns the number of elements in this list (see `Len`).
+    #[inline(always)]
+    fn len(&self) -> usize
+    where
+        Self: crate::Len,
+    {
+        Self::LEN
+    }
+
+    /// Returns `true` if this list has no elements (see `Len`).
+    #[inline(always)]
+    fn is_empty(&self) -> bool
+    where
+        Self: crate::Len,
+    {
+        Self::LEN == 0
+    }
+
+    /// Returns a reference to the element of type `Target` (see `Selector`).
+    #[inline(always)]
+    fn get<Target, Index>(&self) -> &Target
+    where
+        Self: crate::Selector<Target, Index>,
+    {
+        crate::Selector::get(self)
+    }
+
+    /// Returns a mutable reference to the element of type `Target` (see `Selector`).
+    #[inline(always)]
+    fn get_mut<Target, Index>(&mut self) -> &mut Target
+    where
+        Self: crate::Selector<Target, Index>,
+    {
+        crate::Selector::get_mut(self)
+    }
+
+    /// Removes the element of type `Target`, returning it along with the remaining
+    /// elements (see `Plucker`).
+    #[inline(always)]
+    fn pluck<Target, Index>(self) -> (Target, <Self as crate::Plucker<Target, Index>>::Remainder)
+    where
+        Self: crate::Plucker<Target, Index>,
+    {
+        crate::Plucker::pluck(self)
+    }
+
+    /// Reshapes this list into `Target`, returning it along with the leftover elements
+    /// (see `Sculptor`).
+    #[inline(always)]
+    fn sculpt<Target, Indices>(
+        self,
+    ) -> (
+        Target,
+        <Self as crate::Sculptor<Target, Indices>>::Remainder,
+    )
+    where
+        Self: crate::Sculptor<Target, Indices>,
+    {
+        crate::Sculptor::sculpt(self)
+    }
+
+    /// Reorders this list into `Target`, which must use every element exactly once (see
+    /// `Permute`).
+    #[inline(always)]
+    fn permute<Target, Indices>(self) -> Target
+    where
+        Self: crate::Permute<Target, Indices>,
+    {
+        crate::Permute::permute(self)
+    }
+
+    /// Applies `mapper` to each element (see `HMappable`).
+    #[inline(always)]
+    fn map<Mapper>(self, mapper: Mapper) -> <Self as crate::HMappable<Mapper>>::Output
+    where
+        Self: crate::HMappable<Mapper>,
+    {
+        crate::HMappable::map(self, mapper)
+    }
+
+    /// Folds the elements from the left, starting with `init` (see `HFoldLeft`).
+    #[inline(always)]
+    fn foldl<Folder, Acc>(
+        self,
+        folder: Folder,
+        init: Acc,
+    ) -> <Self as crate::HFoldLeft<Folder, Acc>>::Output
+    where
+        Self: crate::HFoldLeft<Folder, Acc>,
+    {
+        crate::HFoldLeft::foldl(self, folder, init)
+    }
+
+    /// Folds the elements from the right, starting with `init` (see `HFoldRight`).
+    #[inline(always)]
+    fn foldr<Folder, Init>(
+        self,
+        folder: Folder,
+        init: Init,
+    ) -> <Self as crate::HFoldRight<Folder, Init>>::Output
+    where
+        Self: crate::HFoldRight<Folder, Init>,
+    {
+        crate::HFoldRight::foldr(self, folder, init)
+    }
+
+    /// Pairs each element with the element at the same position in `other` (see
+    /// `HZippable`).
+    #[inline(always)]
+    fn zip<Other>(self, other: Other) -> <Self as crate::HZippable<Other>>::Zipped
+    where
+        Self: crate::HZippable<Other>,
+    {
+        crate::HZippable::zip(self, other)
+    }
+}
+
+impl<L: HList> HListOps for L {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Double;
+
+    impl<T: core::ops::Add<Output = T> + Copy> Func<T> for Double {
+        type Output = T;
+
+        fn call(&mut self, x: T) -> T {
+            x + x
+        }
+    }
+
+    #[test]
+    fn methods_should_be_available_from_the_prelude() {
+        let hlist = hlist!(1u8, 2.5f32).append(hlist!(3u16));
+        assert_eq!(hlist.len(), 3);
+        assert!(!hlist.is_empty());
+        assert_eq!(*hlist.get::<u16, _>(), 3);
+        assert_eq!(hlist.map(Poly(Double)), hlist!(2u8, 5.0f32, 6u16));
+        assert_eq!(hlist.zip(hlist!('a', 'b', 'c')).1 .0, (2.5f32, 'b'));
+        assert_eq!(hlist.pluck::<f32, _>(), (2.5, hlist!(1u8, 3u16)));
+        assert_eq!(hlist!(hlist!(1u8), hlist!('a')).flatten(), hlist!(1u8, 'a'));
+        assert_eq!(HNil.prepend(hlist!(1)).reverse(), hlist!(1));
+        assert!(HNil.is_empty());
+    }
+}
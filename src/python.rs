@@ -0,0 +1,281 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use pyo3::exceptions::{PyKeyError, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyTuple};
+use pyo3::IntoPyObjectExt;
+use std::convert::Infallible;
+
+/// Converts each element of an `HList` into a Python object, in order.  This is used
+/// to build the Python tuple for an `HList`.
+pub trait IntoPyElements<'py> {
+    /// Converts each element and pushes it onto `elements`.
+    fn push_py_elements(
+        self,
+        py: Python<'py>,
+        elements: &mut Vec<Bound<'py, PyAny>>,
+    ) -> PyResult<()>;
+}
+
+impl<'py> IntoPyElements<'py> for HNil {
+    fn push_py_elements(self, _: Python<'py>, _: &mut Vec<Bound<'py, PyAny>>) -> PyResult<()> {
+        Ok(())
+    }
+}
+
+impl<'py, H, T> IntoPyElements<'py> for HCons<H, T>
+where
+    H: IntoPyObject<'py>,
+    T: HList + IntoPyElements<'py>,
+{
+    fn push_py_elements(
+        self,
+        py: Python<'py>,
+        elements: &mut Vec<Bound<'py, PyAny>>,
+    ) -> PyResult<()> {
+        elements.push(self.0.into_bound_py_any(py)?);
+        self.1.push_py_elements(py, elements)
+    }
+}
+
+/// An `HNil` converts to the empty Python tuple.
+impl<'py> IntoPyObject<'py> for HNil {
+    type Target = PyTuple;
+    type Output = Bound<'py, PyTuple>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(PyTuple::empty(py))
+    }
+}
+
+/// An `HCons` converts to a Python tuple with one item per element.
+impl<'py, H, T> IntoPyObject<'py> for HCons<H, T>
+where
+    H: IntoPyObject<'py>,
+    T: HList + IntoPyElements<'py>,
+{
+    type Target = PyTuple;
+    type Output = Bound<'py, PyTuple>;
+    type Error = PyErr;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        let mut elements = Vec::new();
+        self.push_py_elements(py, &mut elements)?;
+        PyTuple::new(py, elements)
+    }
+}
+
+/// A `Field` converts to the Python object for its value.
+impl<'py, L, V> IntoPyObject<'py> for Field<L, V>
+where
+    V: IntoPyObject<'py>,
+{
+    type Target = V::Target;
+    type Output = V::Output;
+    type Error = V::Error;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        self.value.into_pyobject(py)
+    }
+}
+
+/// Extracts the elements of an `HList` from consecutive items of a Python tuple.
+pub trait FromPyElements<'py>: Sized {
+    /// Extracts this list from the tuple items starting at `index`.
+    fn extract_py_elements(tuple: &Bound<'py, PyTuple>, index: usize) -> PyResult<Self>;
+}
+
+impl<'py> FromPyElements<'py> for HNil {
+    fn extract_py_elements(tuple: &Bound<'py, PyTuple>, index: usize) -> PyResult<Self> {
+        if tuple.len() == index {
+            Ok(HNil)
+        } else {
+            Err(PyTypeError::new_err(format!(
+                "expected a tuple of length {}, found length {}",
+                index,
+                tuple.len()
+            )))
+        }
+    }
+}
+
+impl<'py, H, T> FromPyElements<'py> for HCons<H, T>
+where
+    H: FromPyObject<'py>,
+    T: HList + FromPyElements<'py>,
+{
+    fn extract_py_elements(tuple: &Bound<'py, PyTuple>, index: usize) -> PyResult<Self> {
+        if index >= tuple.len() {
+            return Err(PyTypeError::new_err(format!(
+                "expected a tuple with an element at position {}, found length {}",
+                index,
+                tuple.len()
+            )));
+        }
+        let head = tuple.get_item(index)?.extract().map_err(|err: PyErr| {
+            PyTypeError::new_err(format!("failed to extract element {}: {}", index, err))
+        })?;
+        let tail = T::extract_py_elements(tuple, index + 1)?;
+        Ok(HCons(head, tail))
+    }
+}
+
+/// An `HNil` is extracted from the empty Python tuple.
+impl<'py> FromPyObject<'py> for HNil {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        HNil::extract_py_elements(ob.downcast::<PyTuple>()?, 0)
+    }
+}
+
+/// An `HCons` is extracted from a Python tuple with exactly one item per element.
+impl<'py, H, T> FromPyObject<'py> for HCons<H, T>
+where
+    H: FromPyObject<'py>,
+    T: HList + FromPyElements<'py>,
+{
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Self::extract_py_elements(ob.downcast::<PyTuple>()?, 0)
+    }
+}
+
+/// Allows for converting (and consuming) a labelled `HList` into a Python dict keyed
+/// by field name.
+pub trait IntoLabelledPyDict<'py>: Sized {
+    /// Sets one dict item for each field in this list.
+    fn set_py_items(self, dict: &Bound<'py, PyDict>) -> PyResult<()>;
+
+    /// Converts this list into a new Python dict.
+    fn into_labelled_py_dict(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        self.set_py_items(&dict)?;
+        Ok(dict)
+    }
+}
+
+impl<'py> IntoLabelledPyDict<'py> for HNil {
+    fn set_py_items(self, _: &Bound<'py, PyDict>) -> PyResult<()> {
+        Ok(())
+    }
+}
+
+impl<'py, L, V, T> IntoLabelledPyDict<'py> for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: IntoPyObject<'py>,
+    T: HList + IntoLabelledPyDict<'py>,
+{
+    fn set_py_items(self, dict: &Bound<'py, PyDict>) -> PyResult<()> {
+        dict.set_item(L::NAME, self.0.value)?;
+        self.1.set_py_items(dict)
+    }
+}
+
+/// Allows for extracting a labelled `HList` from a Python dict keyed by field name.
+/// Additional dict items are ignored.
+pub trait FromLabelledPyDict<'py>: Sized {
+    /// Extracts this list from the items of the given dict.
+    fn from_py_items(dict: &Bound<'py, PyDict>) -> PyResult<Self>;
+
+    /// Extracts this list from the given object, which must be a Python dict.
+    fn from_labelled_py_dict(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        Self::from_py_items(ob.downcast::<PyDict>()?)
+    }
+}
+
+impl<'py> FromLabelledPyDict<'py> for HNil {
+    fn from_py_items(_: &Bound<'py, PyDict>) -> PyResult<Self> {
+        Ok(HNil)
+    }
+}
+
+impl<'py, L, V, T> FromLabelledPyDict<'py> for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: FromPyObject<'py>,
+    T: HList + FromLabelledPyDict<'py>,
+{
+    fn from_py_items(dict: &Bound<'py, PyDict>) -> PyResult<Self> {
+        let item = dict
+            .get_item(L::NAME)?
+            .ok_or_else(|| PyKeyError::new_err(format!("missing field `{}`", L::NAME)))?;
+        let value = item.extract().map_err(|err: PyErr| {
+            PyTypeError::new_err(format!("failed to extract field `{}`: {}", L::NAME, err))
+        })?;
+        let tail = T::from_py_items(dict)?;
+        Ok(HCons(Field::new(value), tail))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Settings {
+        name: String,
+        retries: u32,
+    }
+
+    #[test]
+    fn hlist_should_round_trip_through_tuple() {
+        Python::initialize();
+        Python::attach(|py| {
+            let tuple = hlist!(1u8, "two".to_string(), true)
+                .into_pyobject(py)
+                .unwrap();
+            assert_eq!(tuple.repr().unwrap().to_string(), "(1, 'two', True)");
+
+            let hlist: HCons<u8, HCons<String, HCons<bool, HNil>>> = tuple.extract().unwrap();
+            assert_eq!(hlist, hlist!(1u8, "two".to_string(), true));
+
+            assert_eq!(HNil.into_pyobject(py).unwrap().len(), 0);
+        });
+    }
+
+    #[test]
+    fn extraction_errors_should_name_the_position() {
+        Python::initialize();
+        Python::attach(|py| {
+            let tuple = hlist!(1u8, "two").into_pyobject(py).unwrap();
+            let err = tuple.extract::<HCons<u8, HCons<u8, HNil>>>().unwrap_err();
+            assert!(err.to_string().contains("failed to extract element 1"));
+
+            let err = tuple.extract::<HCons<u8, HNil>>().unwrap_err();
+            assert!(err.to_string().contains("expected a tuple of length 1"));
+        });
+    }
+
+    #[test]
+    fn labelled_hlist_should_round_trip_through_dict() {
+        Python::initialize();
+        Python::attach(|py| {
+            let s = Settings {
+                name: "svc".to_string(),
+                retries: 3,
+            };
+            let dict = s.to_labelled_hlist().into_labelled_py_dict(py).unwrap();
+            assert_eq!(
+                dict.repr().unwrap().to_string(),
+                "{'name': 'svc', 'retries': 3}"
+            );
+
+            let hlist = FromLabelledPyDict::from_labelled_py_dict(dict.as_any()).unwrap();
+            assert_eq!(Settings::from_labelled_hlist(hlist), s);
+
+            dict.del_item("retries").unwrap();
+            let err = <HCons<
+                Field<label!(name), String>,
+                HCons<Field<label!(retries), u32>, HNil>,
+            >>::from_labelled_py_dict(dict.as_any())
+            .unwrap_err();
+            assert!(err.to_string().contains("missing field `retries`"));
+        });
+    }
+}
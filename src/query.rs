@@ -0,0 +1,258 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+use crate::labelled::*;
+use core::marker::PhantomData;
+
+/// Allows for removing the `Field` with label `L` from a labelled `HList`, returning it
+/// along with the remaining fields.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// the field within the list.
+pub trait LabelPlucker<L, Index> {
+    /// The type of the value held by the field.
+    type Value;
+
+    /// The `HList` that is left over after removing the field.
+    type Remainder: HList;
+
+    /// Removes the field with label `L`, returning it along with the remaining fields.
+    fn pluck_by_label(self) -> (Field<L, Self::Value>, Self::Remainder);
+}
+
+impl<L, V, Tail> LabelPlucker<L, Here> for HCons<Field<L, V>, Tail>
+where
+    Tail: HList,
+{
+    type Value = V;
+    type Remainder = Tail;
+
+    #[inline(always)]
+    fn pluck_by_label(self) -> (Field<L, V>, Self::Remainder) {
+        (self.0, self.1)
+    }
+}
+
+impl<H, Tail, L, TailIndex> LabelPlucker<L, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + LabelPlucker<L, TailIndex>,
+{
+    type Value = Tail::Value;
+    type Remainder = HCons<H, Tail::Remainder>;
+
+    #[inline(always)]
+    fn pluck_by_label(self) -> (Field<L, Self::Value>, Self::Remainder) {
+        let (field, remainder) = self.1.pluck_by_label();
+        (field, HCons(self.0, remainder))
+    }
+}
+
+/// Allows for projecting a labelled `HList` onto the fields named by the `HList` of
+/// labels `Labels`, in that order.  Each label may be selected at most once.
+pub trait Project<Labels, Indices> {
+    /// The labelled `HList` holding only the selected fields.
+    type Output: HList;
+
+    /// Returns the selected fields, discarding the rest.
+    fn project(self) -> Self::Output;
+}
+
+impl<S: HList> Project<HNil, HNil> for S {
+    type Output = HNil;
+
+    #[inline]
+    fn project(self) -> HNil {
+        HNil
+    }
+}
+
+impl<S, L, RestLabels, Index, RestIndices> Project<HCons<L, RestLabels>, HCons<Index, RestIndices>>
+    for S
+where
+    S: LabelPlucker<L, Index>,
+    S::Remainder: Project<RestLabels, RestIndices>,
+    RestLabels: HList,
+    RestIndices: HList,
+{
+    type Output =
+        HCons<Field<L, S::Value>, <S::Remainder as Project<RestLabels, RestIndices>>::Output>;
+
+    #[inline]
+    fn project(self) -> Self::Output {
+        let (field, remainder) = self.pluck_by_label();
+        HCons(field, remainder.project())
+    }
+}
+
+/// A query selecting columns (fields) from a "table" struct that supports conversion to
+/// a labelled `HList`, such as one using the `HListSupport` derive.
+///
+/// Each call to `select` adds a column, identified by its label type, and `fetch`
+/// produces a labelled `HList` row with just those fields.  The type of the row is
+/// inferred from the table, and selecting a column that does not exist (or selecting
+/// the same column twice) is a compile error.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(Clone, HListSupport)]
+/// struct User {
+///     id: u32,
+///     name: String,
+///     email: String,
+/// }
+///
+/// let user = User { id: 7, name: "kim".to_string(), email: "kim@example.com".to_string() };
+/// let query = TableQuery::<User>::new().select::<label!(email)>().select::<label!(id)>();
+/// let HCons(email, HCons(id, HNil)) = query.fetch(&user);
+/// assert_eq!((email.name(), email.value.as_str()), ("email", "kim@example.com"));
+/// assert_eq!((id.name(), id.value), ("id", 7));
+/// # }
+/// ```
+///
+/// Selecting a column that the table does not have is rejected:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(Clone, HListSupport)]
+/// struct User {
+///     id: u32,
+/// }
+///
+/// let user = User { id: 7 };
+/// TableQuery::<User>::new().select::<label!(name)>().fetch(&user);
+/// # }
+/// ```
+pub struct TableQuery<Table, Columns = HNil> {
+    columns: PhantomData<fn(&Table) -> Columns>,
+}
+
+impl<Table> TableQuery<Table> {
+    /// Creates a query that selects no columns.
+    pub fn new() -> Self {
+        TableQuery {
+            columns: PhantomData,
+        }
+    }
+}
+
+impl<Table> Default for TableQuery<Table> {
+    fn default() -> Self {
+        TableQuery::new()
+    }
+}
+
+impl<Table, Columns> Clone for TableQuery<Table, Columns> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Table, Columns> Copy for TableQuery<Table, Columns> {}
+
+impl<Table, Columns: HList> TableQuery<Table, Columns> {
+    /// Adds the column with label `L` to the end of the selected columns.
+    pub fn select<L>(self) -> TableQuery<Table, <Columns as Append<HCons<L, HNil>>>::Output>
+    where
+        Columns: Append<HCons<L, HNil>>,
+    {
+        TableQuery {
+            columns: PhantomData,
+        }
+    }
+
+    /// Returns the selected columns of the given row.
+    pub fn fetch<H, Indices>(&self, row: &Table) -> <H as Project<Columns, Indices>>::Output
+    where
+        Table: ToLabelledHList<H>,
+        H: HList + Project<Columns, Indices>,
+    {
+        row.to_labelled_hlist().project()
+    }
+
+    /// Consumes the given row, returning its selected columns.
+    pub fn fetch_owned<H, Indices>(&self, row: Table) -> <H as Project<Columns, Indices>>::Output
+    where
+        Table: IntoLabelledHList<H>,
+        H: HList + Project<Columns, Indices>,
+    {
+        row.into_labelled_hlist().project()
+    }
+
+    /// Returns an iterator over the selected columns of each of the given rows.
+    pub fn fetch_all<'a, H, Indices, I>(
+        self,
+        rows: I,
+    ) -> impl Iterator<Item = <H as Project<Columns, Indices>>::Output> + 'a
+    where
+        I: IntoIterator<Item = &'a Table>,
+        I::IntoIter: 'a,
+        Table: ToLabelledHList<H> + 'a,
+        H: HList + Project<Columns, Indices>,
+        Columns: 'a,
+    {
+        rows.into_iter().map(move |row| self.fetch(row))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, HListSupport)]
+    struct Order {
+        id: u32,
+        customer: &'static str,
+        total: f64,
+    }
+
+    #[test]
+    fn project_should_select_fields_by_label() {
+        let row = Order {
+            id: 1,
+            customer: "acme",
+            total: 9.5,
+        }
+        .into_labelled_hlist();
+        let HCons(total, HCons(id, HNil)) =
+            Project::<HCons<label!(total), HCons<label!(id), HNil>>, _>::project(row);
+        assert_eq!((total.value, id.value), (9.5, 1));
+    }
+
+    #[test]
+    fn query_should_fetch_selected_columns_from_each_row() {
+        let orders = [
+            Order {
+                id: 1,
+                customer: "acme",
+                total: 9.5,
+            },
+            Order {
+                id: 2,
+                customer: "globex",
+                total: 20.0,
+            },
+        ];
+        let query = TableQuery::<Order>::new()
+            .select::<label!(customer)>()
+            .select::<label!(total)>();
+        let rows: Vec<_> = query
+            .fetch_all(&orders)
+            .map(|row| row.strip_labels())
+            .collect();
+        assert_eq!(rows, vec![hlist!("acme", 9.5), hlist!("globex", 20.0)]);
+
+        let HCons(id, HNil) = TableQuery::<Order>::new()
+            .select::<label!(id)>()
+            .fetch_owned(orders[1].clone());
+        assert_eq!(id.value, 2);
+    }
+}
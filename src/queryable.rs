@@ -0,0 +1,38 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromStaticSqlRow, Queryable};
+
+/// Implements `Queryable` for the `HList` with the given elements, and then for each
+/// shorter `HList` formed by dropping elements from the front.  An `HList` is loaded
+/// from the same SQL types as the tuple with the same elements.
+macro_rules! impl_queryable {
+    () => {};
+    ($T1:ident $ST1:ident $(, $T:ident $ST:ident)*) => {
+        impl<$T1, $($T,)* $ST1, $($ST,)* __DB> Queryable<($ST1, $($ST,)*), __DB>
+            for hlist_type!($T1 $(, $T)*)
+        where
+            __DB: Backend,
+            ($T1, $($T,)*): FromStaticSqlRow<($ST1, $($ST,)*), __DB>,
+        {
+            type Row = ($T1, $($T,)*);
+
+            #[allow(non_snake_case)]
+            fn build(row: Self::Row) -> deserialize::Result<Self> {
+                let ($T1, $($T,)*) = row;
+                Ok(hlist!($T1 $(, $T)*))
+            }
+        }
+
+        impl_queryable!($($T $ST),*);
+    };
+}
+
+impl_queryable!(
+    A SA, B SB, C SC, D SD, E SE, F SF, G SG, H SH,
+    I SI, J SJ, K SK, L SL, M SM, N SN, O SO, P SP
+);
@@ -0,0 +1,100 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+impl Distribution<HNil> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, _: &mut R) -> HNil {
+        HNil
+    }
+}
+
+/// Samples each element in order using the `Standard` distribution.
+impl<H, T> Distribution<HCons<H, T>> for Standard
+where
+    T: HList,
+    Standard: Distribution<H> + Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> HCons<H, T> {
+        let head = rng.gen();
+        HCons(head, rng.gen())
+    }
+}
+
+/// Allows for generating random values of a type that can be built from an `HList`
+/// (such as any struct that derives `HListSupport`), by sampling each element of the
+/// `HList` from the `Standard` distribution.
+///
+/// ```
+/// # use pl_hlist::*;
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+///
+/// #[derive(Debug, PartialEq, HListSupport)]
+/// struct Packet {
+///     id: u16,
+///     urgent: bool,
+/// }
+///
+/// let packet = Packet::random(&mut StdRng::seed_from_u64(1));
+/// assert_eq!(packet, Packet::random(&mut StdRng::seed_from_u64(1)));
+/// ```
+pub trait RandomHList<H>: Sized {
+    /// Returns a value built from randomly generated elements.
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+}
+
+impl<S, H> RandomHList<H> for S
+where
+    S: FromHList<H>,
+    H: HList,
+    Standard: Distribution<H>,
+{
+    #[inline]
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        S::from_hlist(rng.gen())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[derive(HListSupport)]
+    struct Sample {
+        flag: bool,
+        value: u32,
+        ratio: f64,
+    }
+
+    #[test]
+    fn sampling_should_draw_elements_in_order() {
+        let hlist: HCons<u32, HCons<bool, HNil>> = StdRng::seed_from_u64(7).gen();
+        let mut rng = StdRng::seed_from_u64(7);
+        let value: u32 = rng.gen();
+        let flag: bool = rng.gen();
+        assert_eq!(hlist, hlist!(value, flag));
+    }
+
+    #[test]
+    fn sampled_hlist_should_convert_to_struct() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let s = Sample::from_hlist(rng.gen());
+        assert!((0.0..1.0).contains(&s.ratio));
+    }
+
+    #[test]
+    fn random_should_sample_each_field() {
+        let a = Sample::random(&mut StdRng::seed_from_u64(3));
+        let HCons(flag, HCons(value, HCons(ratio, HNil))) =
+            StdRng::seed_from_u64(3).gen::<HCons<bool, HCons<u32, HCons<f64, HNil>>>>();
+        assert_eq!((a.flag, a.value, a.ratio), (flag, value, ratio));
+    }
+}
@@ -0,0 +1,189 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::selector::*;
+
+/// A dependency-injection container whose services are stored in an `HList`.
+///
+/// Each `register` call adds a service and refines the registry's type, so resolving a
+/// service (or a whole list of constructor arguments) that was never registered is
+/// reported as a compile error rather than a runtime failure.  Each service type should
+/// be registered at most once; resolving a type that was registered more than once is
+/// ambiguous and also fails to compile.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # use std::sync::Arc;
+/// struct Config {
+///     url: &'static str,
+/// }
+///
+/// #[derive(Clone)]
+/// struct Database {
+///     url: &'static str,
+/// }
+///
+/// #[derive(HListSupport)]
+/// struct UserService {
+///     config: Arc<Config>,
+///     database: Database,
+/// }
+///
+/// # fn main() {
+/// let registry = Registry::new().register(Arc::new(Config { url: "db://" }));
+/// let database = Database { url: registry.resolve::<Arc<Config>, _>().url };
+/// let registry = registry.register(database);
+///
+/// let users: UserService = registry.construct();
+/// assert_eq!(users.database.url, "db://");
+/// # }
+/// ```
+///
+/// Resolving a service that was not registered does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// let registry = Registry::new().register(1u8);
+/// let _: &u32 = registry.resolve();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Registry<L: HList> {
+    services: L,
+}
+
+impl Registry<HNil> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Registry { services: HNil }
+    }
+}
+
+impl Default for Registry<HNil> {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
+
+impl<L: HList> Registry<L> {
+    /// Creates a registry containing the given services.
+    pub fn from_services(services: L) -> Self {
+        Registry { services }
+    }
+
+    /// Adds the given service, returning the refined registry.
+    pub fn register<S>(self, service: S) -> Registry<HCons<S, L>> {
+        Registry {
+            services: HCons(service, self.services),
+        }
+    }
+
+    /// Returns a reference to the service of type `T`.
+    pub fn resolve<T, Index>(&self) -> &T
+    where
+        L: Selector<T, Index>,
+    {
+        self.services.get()
+    }
+
+    /// Returns a mutable reference to the service of type `T`.
+    pub fn resolve_mut<T, Index>(&mut self) -> &mut T
+    where
+        L: Selector<T, Index>,
+    {
+        self.services.get_mut()
+    }
+
+    /// Returns a copy of each of the services in the `Deps` list, e.g. the arguments of a
+    /// constructor.  Services are cloned, so they are typically cheap handles such as
+    /// `Arc`s.
+    pub fn resolve_all<Deps, Indices>(&self) -> Deps
+    where
+        Deps: HList,
+        L: ResolveAll<Deps, Indices>,
+    {
+        self.services.resolve_all()
+    }
+
+    /// Constructs an `S` from copies of the services listed in its `HList`
+    /// representation.
+    pub fn construct<S, Deps, Indices>(&self) -> S
+    where
+        S: FromHList<Deps>,
+        Deps: HList,
+        L: ResolveAll<Deps, Indices>,
+    {
+        S::from_hlist(self.resolve_all())
+    }
+
+    /// Consumes this registry, returning its services.
+    pub fn into_services(self) -> L {
+        self.services
+    }
+}
+
+/// Allows for cloning each of the elements in the `Deps` list out of an `HList`, in the
+/// order given by `Deps`.
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position of
+/// each element of `Deps` within the list.
+pub trait ResolveAll<Deps, Indices> {
+    /// Returns a copy of each of the elements in `Deps`.
+    fn resolve_all(&self) -> Deps;
+}
+
+impl<L> ResolveAll<HNil, HNil> for L {
+    #[inline]
+    fn resolve_all(&self) -> HNil {
+        HNil
+    }
+}
+
+impl<L, D, DRest, Index, RestIndices> ResolveAll<HCons<D, DRest>, HCons<Index, RestIndices>> for L
+where
+    L: Selector<D, Index> + ResolveAll<DRest, RestIndices>,
+    D: Clone,
+    DRest: HList,
+    RestIndices: HList,
+{
+    #[inline]
+    fn resolve_all(&self) -> HCons<D, DRest> {
+        HCons(self.get().clone(), self.resolve_all())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq, HListSupport)]
+    struct Handler {
+        name: Rc<String>,
+        retries: u8,
+    }
+
+    #[test]
+    fn resolve_should_find_registered_services() {
+        let mut registry = Registry::new()
+            .register(Rc::new(String::from("svc")))
+            .register(3u8)
+            .register('x');
+        assert_eq!(**registry.resolve::<Rc<String>, _>(), "svc");
+        *registry.resolve_mut::<u8, _>() += 1;
+        assert_eq!(*registry.resolve::<u8, _>(), 4);
+
+        let deps: HCons<u8, HCons<char, HNil>> = registry.resolve_all();
+        assert_eq!(deps, hlist!(4u8, 'x'));
+
+        let handler: Handler = registry.construct();
+        assert_eq!(*handler.name, "svc");
+        assert_eq!(handler.retries, 4);
+
+        assert_eq!(Rc::strong_count(registry.resolve::<Rc<String>, _>()), 2);
+    }
+}
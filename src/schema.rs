@@ -0,0 +1,167 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde_json::{Map, Value};
+
+/// Collects the JSON Schema properties described by a labelled `HList`.
+///
+/// This is implemented for `HNil` and for any `HCons` whose head is a `Field` with a
+/// `JsonSchema` value, and is used to build the object schema for labelled `HList`s.
+pub trait JsonSchemaProperties {
+    /// Adds one property (and its `required` entry) per element of this list.
+    fn add_properties(
+        generator: &mut SchemaGenerator,
+        properties: &mut Map<String, Value>,
+        required: &mut Vec<Value>,
+    );
+}
+
+impl JsonSchemaProperties for HNil {
+    fn add_properties(_: &mut SchemaGenerator, _: &mut Map<String, Value>, _: &mut Vec<Value>) {}
+}
+
+impl<L, T, Tail> JsonSchemaProperties for HCons<Field<L, T>, Tail>
+where
+    L: Label,
+    T: JsonSchema,
+    Tail: HList + JsonSchemaProperties,
+{
+    fn add_properties(
+        generator: &mut SchemaGenerator,
+        properties: &mut Map<String, Value>,
+        required: &mut Vec<Value>,
+    ) {
+        properties.insert(L::NAME.into(), generator.subschema_for::<T>().to_value());
+        required.push(L::NAME.into());
+        Tail::add_properties(generator, properties, required);
+    }
+}
+
+/// A labelled `HList` is described as a JSON object with one required property per
+/// field.  The schema is always inlined into its parent.
+impl<L, T, Tail> JsonSchema for HCons<Field<L, T>, Tail>
+where
+    Self: JsonSchemaProperties,
+    Tail: HList,
+{
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("LabelledHList")
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed(core::any::type_name::<Self>())
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        Self::add_properties(generator, &mut properties, &mut required);
+
+        let mut schema = Map::new();
+        schema.insert("type".into(), "object".into());
+        schema.insert("properties".into(), Value::Object(properties));
+        schema.insert("required".into(), Value::Array(required));
+        Schema::from(schema)
+    }
+}
+
+/// Generates the JSON Schema for `S` using its labelled `HList` representation.  This
+/// makes it easy to implement `JsonSchema` for structs that use the `HListSupport`
+/// derive:
+///
+/// ```
+/// use pl_hlist::*;
+/// use schemars::{JsonSchema, Schema, SchemaGenerator};
+/// use std::borrow::Cow;
+///
+/// #[derive(HListSupport)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// impl JsonSchema for Point {
+///     fn schema_name() -> Cow<'static, str> {
+///         "Point".into()
+///     }
+///
+///     fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+///         labelled_json_schema::<Point, _>(generator)
+///     }
+/// }
+///
+/// # let schema = schemars::schema_for!(Point);
+/// # assert_eq!(schema.get("required").unwrap(), &serde_json::json!(["x", "y"]));
+/// ```
+pub fn labelled_json_schema<S, H>(generator: &mut SchemaGenerator) -> Schema
+where
+    S: ToLabelledHList<H>,
+    H: HList + JsonSchema,
+{
+    H::json_schema(generator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+    use serde_json::json;
+
+    #[derive(Clone, HListSupport)]
+    struct Inner {
+        flag: bool,
+    }
+
+    #[derive(HListSupport)]
+    struct Outer {
+        name: String,
+        count: u32,
+        inner: Inner,
+    }
+
+    impl JsonSchema for Inner {
+        fn schema_name() -> Cow<'static, str> {
+            "Inner".into()
+        }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            labelled_json_schema::<Inner, _>(generator)
+        }
+    }
+
+    #[test]
+    fn labelled_hlist_schema_should_describe_each_field() {
+        let schema = schemars::schema_for!(
+            HCons<Field<label!(name), String>, HCons<Field<label!(count), u32>, HNil>>
+        );
+        assert_eq!(schema.get("type"), Some(&json!("object")));
+        assert_eq!(schema.get("required"), Some(&json!(["name", "count"])));
+        assert_eq!(
+            schema.get("properties").unwrap()["name"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn struct_schema_should_reference_nested_structs() {
+        let mut generator = SchemaGenerator::default();
+        let schema = labelled_json_schema::<Outer, _>(&mut generator);
+        assert_eq!(
+            schema.get("properties").unwrap()["inner"],
+            json!({ "$ref": "#/$defs/Inner" })
+        );
+        assert!(generator.definitions().contains_key("Inner"));
+    }
+}
@@ -0,0 +1,386 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::plucker::*;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr;
+
+/// Allows for reshaping an `HList` into a `Target` `HList` containing any subset of its
+/// elements, in any order, returning the target along with the leftover elements.
+///
+/// When the remainder is `HNil`, the target is a permutation of the source.
+///
+/// The reshaping is performed on pointers to the elements rather than on the elements
+/// themselves: each element is copied exactly once, directly from its position in the
+/// source into its position in the (initially uninitialized) target or remainder.  This
+/// keeps the cost independent of how far an element moves, which matters for large
+/// elements such as inline buffers.
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position in
+/// the source of each element of the target.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two", 3.0f32, 'f');
+/// let (target, remainder): (HCons<f32, HCons<u8, HNil>>, _) = hlist.sculpt();
+/// assert_eq!(target, hlist!(3.0f32, 1u8));
+/// assert_eq!(remainder, hlist!("two", 'f'));
+/// # }
+/// ```
+///
+/// `HCons` also provides `sculpt` as an inherent method, so the target type can be given
+/// with a turbofish.  Combined with the `HListSupport` conversions, this converts between
+/// structs whose fields have the same types in a different order:
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(HListSupport)]
+/// struct Row {
+///     id: u32,
+///     name: &'static str,
+///     score: f32,
+/// }
+///
+/// #[derive(HListSupport)]
+/// struct Summary {
+///     name: &'static str,
+///     score: f32,
+/// }
+///
+/// let row = Row { id: 7, name: "kim", score: 9.5 };
+/// let (fields, HCons(id, HNil)) = row.into_hlist().sculpt::<HCons<&str, HCons<f32, HNil>>, _>();
+/// let summary = Summary::from_hlist(fields);
+/// assert_eq!((summary.name, summary.score, id), ("kim", 9.5, 7));
+/// ```
+pub trait Sculptor<Target, Indices> {
+    /// The `HList` of leftover elements that are not part of `Target`.
+    type Remainder: HList;
+
+    /// Reshapes `self` into `Target`, returning it along with the leftover elements.
+    fn sculpt(self) -> (Target, Self::Remainder);
+}
+
+/// Allows for reordering an `HList` into a `Target` `HList` with the same element types in
+/// any order.  Unlike `Sculptor`, every element must be used exactly once, so a `Target`
+/// that omits an element (or names one twice) is rejected at compile time.
+///
+/// This is implemented for every source whose `Sculptor` remainder is `HNil`, and so
+/// shares its pointer-based reshaping.  Combined with the `HListSupport` conversions, it
+/// adapts between structs with the same fields in a different order:
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(HListSupport)]
+/// struct Wire {
+///     id: u32,
+///     name: &'static str,
+///     active: bool,
+/// }
+///
+/// #[derive(HListSupport)]
+/// struct Model {
+///     active: bool,
+///     id: u32,
+///     name: &'static str,
+/// }
+///
+/// let wire = Wire { id: 3, name: "kim", active: true };
+/// let model = Model::from_hlist(wire.into_hlist().permute());
+/// assert_eq!((model.active, model.id, model.name), (true, 3, "kim"));
+/// ```
+///
+/// A target that leaves elements unused is rejected, and the error names the leftovers:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// // error[E0277]: the target of `permute` does not use every element
+/// let target: HCons<u8, HNil> = hlist!(1u8, "two").permute();
+/// # }
+/// ```
+pub trait Permute<Target, Indices> {
+    /// Reorders `self` into `Target`.
+    fn permute(self) -> Target;
+}
+
+impl<Source, Target, Indices> Permute<Target, Indices> for Source
+where
+    Source: Sculptor<Target, Indices>,
+    Source::Remainder: NoLeftovers,
+{
+    #[inline(always)]
+    fn permute(self) -> Target {
+        self.sculpt().0
+    }
+}
+
+/// Implemented only by `HNil`, so that `Permute` can report the elements that a target
+/// leaves unused (rather than a type mismatch with `HNil`).
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "the target of `permute` does not use every element",
+    label = "the elements `{Self}` would be left over",
+    note = "use `sculpt` to keep the leftover elements"
+)]
+pub trait NoLeftovers: HList {}
+
+impl NoLeftovers for HNil {}
+
+/// An `HList` whose elements can be addressed individually by raw pointer.
+///
+/// This is an implementation detail of `Sculptor`.  It is sealed because `Sculptor` relies
+/// on the pointers produced here being valid, distinct, and covering every element.
+pub trait ElementPointers: HList + Sized + sealed::Sealed {
+    /// An `HList` with a `*mut` pointer to each element of `Self`.
+    type Pointers: PointedTo<Values = Self>;
+
+    /// Returns a pointer to each element of the `HList` at `this`.
+    ///
+    /// # Safety
+    ///
+    /// `this` must be valid for reads and writes.
+    unsafe fn element_pointers(this: *mut Self) -> Self::Pointers;
+
+    /// Initializes each element of the (possibly uninitialized) `HList` at `dst` by copying
+    /// from the corresponding pointer in `src`.
+    ///
+    /// # Safety
+    ///
+    /// `dst` must be valid for writes, and each pointer in `src` must be valid for reads
+    /// and must not overlap `dst`.  The values behind `src` are moved out, and must not be
+    /// used or dropped afterwards.
+    unsafe fn write_from_pointers(dst: *mut Self, src: Self::Pointers);
+}
+
+/// An `HList` of `*mut` pointers, mapped back to the `HList` of values they point to.
+///
+/// This is an implementation detail of `Sculptor`.
+pub trait PointedTo: HList + sealed::Sealed {
+    /// The `HList` of values pointed to by `Self`.
+    type Values: ElementPointers<Pointers = Self>;
+}
+
+/// Reshapes an `HList` of pointers; the pointer-level counterpart to `Sculptor`.
+///
+/// This is an implementation detail of `Sculptor`.
+pub trait SculptPointers<Target, Indices> {
+    /// The `HList` of leftover pointers that are not part of `Target`.
+    type Remainder: PointedTo;
+
+    /// Reshapes `self` into `Target`, returning it along with the leftover pointers.
+    fn sculpt_pointers(self) -> (Target, Self::Remainder);
+}
+
+mod sealed {
+    use crate::hlist::*;
+
+    pub trait Sealed {}
+
+    impl Sealed for HNil {}
+    impl<H, T: HList> Sealed for HCons<H, T> {}
+}
+
+impl ElementPointers for HNil {
+    type Pointers = HNil;
+
+    #[inline(always)]
+    unsafe fn element_pointers(_this: *mut Self) -> Self::Pointers {
+        HNil
+    }
+
+    #[inline(always)]
+    unsafe fn write_from_pointers(_dst: *mut Self, _src: Self::Pointers) {}
+}
+
+impl<H, T> ElementPointers for HCons<H, T>
+where
+    T: ElementPointers,
+{
+    type Pointers = HCons<*mut H, T::Pointers>;
+
+    #[inline(always)]
+    unsafe fn element_pointers(this: *mut Self) -> Self::Pointers {
+        HCons(
+            ptr::addr_of_mut!((*this).0),
+            T::element_pointers(ptr::addr_of_mut!((*this).1)),
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn write_from_pointers(dst: *mut Self, src: Self::Pointers) {
+        ptr::copy_nonoverlapping(src.0, ptr::addr_of_mut!((*dst).0), 1);
+        T::write_from_pointers(ptr::addr_of_mut!((*dst).1), src.1);
+    }
+}
+
+impl PointedTo for HNil {
+    type Values = HNil;
+}
+
+impl<H, T> PointedTo for HCons<*mut H, T>
+where
+    T: PointedTo,
+{
+    type Values = HCons<H, T::Values>;
+}
+
+impl<Source> SculptPointers<HNil, HNil> for Source
+where
+    Source: PointedTo,
+{
+    type Remainder = Source;
+
+    #[inline(always)]
+    fn sculpt_pointers(self) -> (HNil, Self::Remainder) {
+        (HNil, self)
+    }
+}
+
+impl<Source, TargetHead, TargetTail, IndexHead, IndexTail>
+    SculptPointers<HCons<*mut TargetHead, TargetTail>, HCons<IndexHead, IndexTail>> for Source
+where
+    Source: Plucker<*mut TargetHead, IndexHead>,
+    Source::Remainder: SculptPointers<TargetTail, IndexTail>,
+    TargetTail: HList,
+    IndexTail: HList,
+{
+    type Remainder = <Source::Remainder as SculptPointers<TargetTail, IndexTail>>::Remainder;
+
+    #[inline(always)]
+    fn sculpt_pointers(self) -> (HCons<*mut TargetHead, TargetTail>, Self::Remainder) {
+        let (head, rest) = self.pluck();
+        let (tail, remainder) = rest.sculpt_pointers();
+        (HCons(head, tail), remainder)
+    }
+}
+
+impl<Source, Target, Indices> Sculptor<Target, Indices> for Source
+where
+    Source: ElementPointers,
+    Target: ElementPointers,
+    Source::Pointers: SculptPointers<Target::Pointers, Indices>,
+{
+    type Remainder =
+        <<Source::Pointers as SculptPointers<Target::Pointers, Indices>>::Remainder as PointedTo>::Values;
+
+    #[inline]
+    fn sculpt(self) -> (Target, Self::Remainder) {
+        let mut source = ManuallyDrop::new(self);
+        let mut target = MaybeUninit::<Target>::uninit();
+        let mut remainder = MaybeUninit::<Self::Remainder>::uninit();
+
+        // SAFETY: `element_pointers` yields one distinct pointer per element of `source`, and
+        // `sculpt_pointers` partitions those pointers between the target and the remainder,
+        // so every element of `target` and `remainder` is initialized, and every element of
+        // `source` is moved out exactly once.  `source` is never dropped.
+        unsafe {
+            let pointers = Source::element_pointers(&mut *source);
+            let (target_pointers, remainder_pointers) = pointers.sculpt_pointers();
+            Target::write_from_pointers(target.as_mut_ptr(), target_pointers);
+            Self::Remainder::write_from_pointers(remainder.as_mut_ptr(), remainder_pointers);
+            (target.assume_init(), remainder.assume_init())
+        }
+    }
+}
+
+impl<H, T: HList> HCons<H, T> {
+    /// Reshapes this list into `Target`, returning it along with the leftover elements.
+    #[inline(always)]
+    pub fn sculpt<Target, Indices>(self) -> (Target, <Self as Sculptor<Target, Indices>>::Remainder)
+    where
+        Self: Sculptor<Target, Indices>,
+    {
+        Sculptor::sculpt(self)
+    }
+
+    /// Reorders this list into `Target`, which must use every element exactly once.
+    #[inline(always)]
+    pub fn permute<Target, Indices>(self) -> Target
+    where
+        Self: Permute<Target, Indices>,
+    {
+        Permute::permute(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn sculpt_should_select_and_reorder_elements() {
+        let hlist = hlist!(1u8, "two", 3u32, 'f', 5.0f64);
+        type Target = HCons<f64, HCons<u8, HCons<char, HNil>>>;
+        let (target, remainder): (Target, _) = hlist.sculpt();
+        assert_eq!(target, hlist!(5.0f64, 1u8, 'f'));
+        assert_eq!(remainder, hlist!("two", 3u32));
+    }
+
+    #[test]
+    fn inherent_sculpt_should_accept_a_turbofish() {
+        let (target, remainder) =
+            hlist!(1u8, "two", 3u32).sculpt::<HCons<u32, HCons<u8, HNil>>, _>();
+        assert_eq!(target, hlist!(3u32, 1u8));
+        assert_eq!(remainder, hlist!("two"));
+    }
+
+    #[test]
+    fn permute_should_use_every_element() {
+        let hlist = hlist!(1u8, "two", 3u32, 'f');
+        type Reversed = hlist_type!(char, u32, &'static str, u8);
+        let permuted: Reversed = hlist.permute();
+        assert_eq!(permuted, hlist!('f', 3u32, "two", 1u8));
+        assert_eq!(
+            permuted.permute::<hlist_type!(u8, &str, u32, char), _>(),
+            hlist
+        );
+        assert_eq!(Permute::<HNil, HNil>::permute(HNil), HNil);
+    }
+
+    #[test]
+    fn sculpt_should_permute_large_elements() {
+        let hlist = hlist!([1u8; 4096], [2u16; 2048], [3u32; 1024]);
+        type Target = HCons<[u32; 1024], HCons<[u8; 4096], HCons<[u16; 2048], HNil>>>;
+        let (target, remainder): (Target, _) = hlist.sculpt();
+        assert_eq!(remainder, HNil);
+        assert!(target.0.iter().all(|&x| x == 3));
+        assert!(target.1 .0.iter().all(|&x| x == 1));
+        assert!(target.1 .1 .0.iter().all(|&x| x == 2));
+    }
+
+    #[test]
+    fn sculpt_should_drop_each_element_exactly_once() {
+        struct Noisy(&'static str, Rc<RefCell<Vec<&'static str>>>);
+
+        impl Drop for Noisy {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push(self.0);
+            }
+        }
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        {
+            let hlist = hlist!(
+                Noisy("a", log.clone()),
+                1u8,
+                String::from("b"),
+                Box::new(Noisy("c", log.clone()))
+            );
+            let (target, remainder): (HCons<Box<Noisy>, HCons<String, HNil>>, _) = hlist.sculpt();
+            assert_eq!(target.1 .0, "b");
+            assert!(log.borrow().is_empty());
+            drop(target);
+            assert_eq!(*log.borrow(), vec!["c"]);
+            drop(remainder);
+        }
+        assert_eq!(*log.borrow(), vec!["c", "a"]);
+    }
+}
@@ -0,0 +1,113 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::indices::*;
+
+/// Allows for borrowing the element of type `Target` from an `HList`.
+///
+/// The `Index` type parameter is inferred by the compiler and records the position of
+/// `Target` within the list.  The list must contain exactly one element of type `Target`:
+/// selecting a type that is absent fails to compile, and selecting a type that appears
+/// more than once is ambiguous (so the compiler asks for the index to be spelled out).
+///
+/// `HCons` also provides `get` and `get_mut` as inherent methods, so the target type can
+/// be given with a turbofish:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let mut hlist = hlist!(1u8, "two", 3u32);
+/// assert_eq!(*hlist.get::<u32, _>(), 3);
+/// *hlist.get_mut::<u8, _>() += 1;
+/// assert_eq!(hlist, hlist!(2u8, "two", 3u32));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two");
+/// hlist.get::<u32, _>();
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "type `{Target}` does not appear in this `HList`",
+    label = "no element of type `{Target}`",
+    note = "the full list type is named by the outermost `required for` note below"
+)]
+pub trait Selector<Target, Index> {
+    /// Returns a reference to the element of type `Target`.
+    fn get(&self) -> &Target;
+
+    /// Returns a mutable reference to the element of type `Target`.
+    fn get_mut(&mut self) -> &mut Target;
+}
+
+impl<H, T: HList> HCons<H, T> {
+    /// Returns a reference to the element of type `Target`.
+    #[inline(always)]
+    pub fn get<Target, Index>(&self) -> &Target
+    where
+        Self: Selector<Target, Index>,
+    {
+        Selector::get(self)
+    }
+
+    /// Returns a mutable reference to the element of type `Target`.
+    #[inline(always)]
+    pub fn get_mut<Target, Index>(&mut self) -> &mut Target
+    where
+        Self: Selector<Target, Index>,
+    {
+        Selector::get_mut(self)
+    }
+}
+
+impl<T, Tail> Selector<T, Here> for HCons<T, Tail>
+where
+    Tail: HList,
+{
+    #[inline(always)]
+    fn get(&self) -> &T {
+        &self.0
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<H, Tail, T, TailIndex> Selector<T, There<TailIndex>> for HCons<H, Tail>
+where
+    Tail: HList + Selector<T, TailIndex>,
+{
+    #[inline(always)]
+    fn get(&self) -> &T {
+        self.1.get()
+    }
+
+    #[inline(always)]
+    fn get_mut(&mut self) -> &mut T {
+        self.1.get_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_should_borrow_the_element_of_the_given_type() {
+        let mut hlist = hlist!(1u8, String::from("two"), 3.0f64);
+        assert_eq!(hlist.get::<String, _>(), "two");
+        *hlist.get_mut::<f64, _>() *= 2.0;
+        assert_eq!(*hlist.get::<f64, _>(), 6.0);
+        assert_eq!(*Selector::<u8, _>::get(&hlist), 1);
+    }
+}
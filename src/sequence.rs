@@ -0,0 +1,113 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Allows for collapsing an `HList` of `Result`s that share the error type `E` into a
+/// `Result` of an `HList` of the success values.
+///
+/// `sequence` stops at the first error, while `sequence_all` (with the `alloc` feature)
+/// examines every element and reports all of the errors:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let parsed = hlist!("1".parse::<u8>(), "two".parse::<u8>(), "3".parse::<u32>());
+/// assert_eq!(parsed.clone().sequence().unwrap_err().to_string(), "invalid digit found in string");
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(parsed.sequence_all().unwrap_err().len(), 1);
+///
+/// let parsed = hlist!("1".parse::<u8>(), "3".parse::<u32>());
+/// assert_eq!(parsed.sequence(), Ok(hlist!(1u8, 3u32)));
+/// # }
+/// ```
+pub trait SequenceResults<E> {
+    /// The `HList` of success values.
+    type Output: HList;
+
+    /// Returns the success values if every element is `Ok`, or else the first error.
+    fn sequence(self) -> Result<Self::Output, E>;
+
+    /// Returns the success values if every element is `Ok`, or else all of the errors,
+    /// in order.
+    #[cfg(feature = "alloc")]
+    fn sequence_all(self) -> Result<Self::Output, Vec<E>>
+    where
+        Self: Sized,
+    {
+        let mut errors = Vec::new();
+        match self.sequence_into(&mut errors) {
+            Some(output) if errors.is_empty() => Ok(output),
+            _ => Err(errors),
+        }
+    }
+
+    /// Returns the success values if every element is `Ok`, after appending each error
+    /// to `errors`.
+    #[doc(hidden)]
+    #[cfg(feature = "alloc")]
+    fn sequence_into(self, errors: &mut Vec<E>) -> Option<Self::Output>;
+}
+
+impl<E> SequenceResults<E> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn sequence(self) -> Result<HNil, E> {
+        Ok(HNil)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn sequence_into(self, _: &mut Vec<E>) -> Option<HNil> {
+        Some(HNil)
+    }
+}
+
+impl<H, E, T> SequenceResults<E> for HCons<Result<H, E>, T>
+where
+    T: HList + SequenceResults<E>,
+{
+    type Output = HCons<H, T::Output>;
+
+    #[inline]
+    fn sequence(self) -> Result<Self::Output, E> {
+        let head = self.0?;
+        Ok(HCons(head, self.1.sequence()?))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn sequence_into(self, errors: &mut Vec<E>) -> Option<Self::Output> {
+        let head = self.0.map_err(|error| errors.push(error)).ok();
+        let tail = self.1.sequence_into(errors);
+        Some(HCons(head?, tail?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_should_stop_at_the_first_error() {
+        assert_eq!(SequenceResults::<()>::sequence(HNil), Ok(HNil));
+        let ok: HCons<Result<u8, &str>, _> = hlist!(Ok(1u8), Ok("two"));
+        assert_eq!(ok.sequence(), Ok(hlist!(1u8, "two")));
+        let failed = hlist!(Ok(1u8), Err::<i32, _>("bad"), Err::<bool, _>("worse"));
+        assert_eq!(failed.sequence(), Err("bad"));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sequence_all_should_accumulate_errors() {
+        let ok: HCons<Result<u8, &str>, _> = hlist!(Ok(1u8), Ok("two"));
+        assert_eq!(ok.sequence_all(), Ok(hlist!(1u8, "two")));
+        let failed = hlist!(Err::<u8, _>("bad"), Ok(2i32), Err::<bool, _>("worse"));
+        assert_eq!(failed.sequence_all(), Err(vec!["bad", "worse"]));
+    }
+}
@@ -0,0 +1,158 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::len::*;
+use core::fmt;
+use core::marker::PhantomData;
+use serde::de::{self, Deserialize, Deserializer, Expected, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+/// Serializes an `HList` as a tuple of its elements, in order, so that it has the same
+/// representation as the equivalent tuple (for example, `[1,"a",true]` in JSON).  `HNil`
+/// serializes as an empty tuple.
+impl Serialize for HNil {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_tuple(0)?.end()
+    }
+}
+
+impl<H, T> Serialize for HCons<H, T>
+where
+    H: Serialize,
+    T: Len + SerializeElements,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(<Self as Len>::LEN)?;
+        self.serialize_elements(&mut tuple)?;
+        tuple.end()
+    }
+}
+
+/// Helper for `Serialize` that serializes each element of an `HList` into a tuple.
+#[doc(hidden)]
+pub trait SerializeElements {
+    fn serialize_elements<S: SerializeTuple>(&self, tuple: &mut S) -> Result<(), S::Error>;
+}
+
+impl SerializeElements for HNil {
+    fn serialize_elements<S: SerializeTuple>(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<H, T> SerializeElements for HCons<H, T>
+where
+    H: Serialize,
+    T: HList + SerializeElements,
+{
+    fn serialize_elements<S: SerializeTuple>(&self, tuple: &mut S) -> Result<(), S::Error> {
+        tuple.serialize_element(&self.0)?;
+        self.1.serialize_elements(tuple)
+    }
+}
+
+/// Deserializes an `HList` from a tuple of its elements, in order.  A tuple with too few
+/// elements is reported as an invalid length.
+impl<'de> Deserialize<'de> for HNil {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(0, HListVisitor(PhantomData))
+    }
+}
+
+impl<'de, H, T> Deserialize<'de> for HCons<H, T>
+where
+    H: Deserialize<'de>,
+    T: Len + DeserializeElements<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(<Self as Len>::LEN, HListVisitor(PhantomData))
+    }
+}
+
+/// Helper for `Deserialize` that deserializes each element of an `HList` from a sequence,
+/// starting at the given index.
+#[doc(hidden)]
+pub trait DeserializeElements<'de>: Sized {
+    fn deserialize_elements<A: SeqAccess<'de>>(
+        seq: &mut A,
+        index: usize,
+        expected: &dyn Expected,
+    ) -> Result<Self, A::Error>;
+}
+
+impl<'de> DeserializeElements<'de> for HNil {
+    fn deserialize_elements<A: SeqAccess<'de>>(
+        _: &mut A,
+        _: usize,
+        _: &dyn Expected,
+    ) -> Result<Self, A::Error> {
+        Ok(HNil)
+    }
+}
+
+impl<'de, H, T> DeserializeElements<'de> for HCons<H, T>
+where
+    H: Deserialize<'de>,
+    T: HList + DeserializeElements<'de>,
+{
+    fn deserialize_elements<A: SeqAccess<'de>>(
+        seq: &mut A,
+        index: usize,
+        expected: &dyn Expected,
+    ) -> Result<Self, A::Error> {
+        let head = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(index, expected))?;
+        let tail = T::deserialize_elements(seq, index + 1, expected)?;
+        Ok(HCons(head, tail))
+    }
+}
+
+/// Visits a tuple of the elements of the `HList` `L`.
+struct HListVisitor<L>(PhantomData<L>);
+
+impl<'de, L> Visitor<'de> for HListVisitor<L>
+where
+    L: Len + DeserializeElements<'de>,
+{
+    type Value = L;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a tuple of {} elements", L::LEN)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<L, A::Error> {
+        L::deserialize_elements(&mut seq, 0, &self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Row = HCons<u32, HCons<String, HCons<bool, HNil>>>;
+
+    #[test]
+    fn hlists_should_serialize_like_tuples() {
+        let row: Row = hlist!(1, String::from("a"), true);
+        let json = serde_json::to_string(&row).unwrap();
+        assert_eq!(json, serde_json::to_string(&(1, "a", true)).unwrap());
+        assert_eq!(json, "[1,\"a\",true]");
+        assert_eq!(serde_json::from_str::<Row>(&json).unwrap(), row);
+
+        assert_eq!(serde_json::to_string(&HNil).unwrap(), "[]");
+        assert_eq!(serde_json::from_str::<HNil>("[]").unwrap(), HNil);
+    }
+
+    #[test]
+    fn deserializing_too_few_elements_should_fail() {
+        let err = serde_json::from_str::<Row>("[1,\"a\"]").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid length 2, expected a tuple of 3 elements at line 1 column 7"
+        );
+    }
+}
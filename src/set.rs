@@ -0,0 +1,253 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::sculptor::*;
+use crate::selector::*;
+
+/// Marker trait for `HList`s in which no type appears more than once, so that the list
+/// can be treated as a set of types (for example, the resources held by a typestate
+/// API, or the capabilities in a `Capabilities` set).
+///
+/// The `Indices` type parameter is inferred by the compiler and records the position of
+/// each element type within the list.  Locating a type that appears more than once is
+/// ambiguous, so requiring `Distinct` of a list with a duplicate type fails to compile
+/// (with a "type annotations needed" error):
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// fn as_set<L: Distinct<I>, I>(list: L) -> L {
+///     list
+/// }
+///
+/// # fn main() {
+/// as_set(hlist!(1u8, "two", 3.0f32));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// fn as_set<L: Distinct<I>, I>(list: L) -> L {
+///     list
+/// }
+///
+/// # fn main() {
+/// as_set(hlist!(1u8, "two", 3u8));
+/// # }
+/// ```
+pub trait Distinct<Indices>: HList {}
+
+impl<L, Indices> Distinct<Indices> for L where L: HList + DistinctIn<L, Indices> {}
+
+/// Helper for `Distinct` that locates each element type of `Self` within the whole list
+/// `L`.
+#[doc(hidden)]
+pub trait DistinctIn<L, Indices> {}
+
+impl<L> DistinctIn<L, HNil> for HNil {}
+
+impl<L, H, T, I, Is> DistinctIn<L, HCons<I, Is>> for HCons<H, T>
+where
+    L: Selector<H, I>,
+    T: HList + DistinctIn<L, Is>,
+    Is: HList,
+{
+}
+
+/// Marker trait for `HList`s that contain an element of type `T`, for use in bounds
+/// such as `where L: Contains<Db, I1> + Contains<Logger, I2>`.
+///
+/// As with `Selector` (which this is implemented in terms of), the `Index` type
+/// parameter is inferred by the compiler, and the borrowed element can be obtained with
+/// `get`:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// struct Db(&'static str);
+/// struct Logger;
+///
+/// fn connect<Env, I1, I2>(env: &Env) -> &'static str
+/// where
+///     Env: Contains<Db, I1> + Contains<Logger, I2>,
+/// {
+///     Selector::<Db, I1>::get(env).0
+/// }
+///
+/// # fn main() {
+/// assert_eq!(connect(&hlist!(Logger, 7u32, Db("postgres://"))), "postgres://");
+/// # }
+/// ```
+pub trait Contains<T, Index>: Selector<T, Index> {}
+
+impl<L, T, Index> Contains<T, Index> for L where L: Selector<T, Index> {}
+
+/// Marker trait for `HList`s that contain an element of each type in the `HList`
+/// `Required` (in any order), for use in bounds such as `where Env: SupersetOf<Required,
+/// I>`.  The `Indices` type parameter is inferred by the compiler.
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// fn run<Env: SupersetOf<HCons<u8, HCons<bool, HNil>>, I>, I>(_: &Env) {}
+///
+/// # fn main() {
+/// run(&hlist!(1u8, "two"));
+/// # }
+/// ```
+pub trait SupersetOf<Required, Indices> {}
+
+impl<L> SupersetOf<HNil, HNil> for L {}
+
+impl<L, H, T, I, Is> SupersetOf<HCons<H, T>, HCons<I, Is>> for L
+where
+    L: Selector<H, I> + SupersetOf<T, Is>,
+    T: HList,
+    Is: HList,
+{
+}
+
+/// Marker trait for `HList`s whose element types all appear in `Super`; the converse of
+/// `SupersetOf`.  The `Indices` type parameter is inferred by the compiler.
+pub trait SubsetOf<Super, Indices> {}
+
+impl<L, Super, Indices> SubsetOf<Super, Indices> for L where Super: SupersetOf<L, Indices> {}
+
+/// Allows for forming the union of two `HList`s that have no types in common, producing
+/// the elements of `Self` followed by the elements of `Other`.
+///
+/// The output is required to be `Distinct`, so taking the union of sets that share a
+/// type fails to compile.  (Computing the union of *overlapping* sets would require
+/// deciding that two types are different, which trait resolution cannot do on stable
+/// Rust.)
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Db;
+/// struct Logger;
+/// struct Cache;
+///
+/// let resources = hlist!(Db, Logger).disjoint_union(hlist!(Cache));
+/// let HCons(_, HCons(_, HCons(Cache, HNil))) = resources;
+/// # }
+/// ```
+pub trait DisjointUnion<Other, Indices> {
+    /// The combined set.
+    type Output: HList;
+
+    /// Combines this set with `other`.
+    fn disjoint_union(self, other: Other) -> Self::Output;
+}
+
+impl<L, Other, Indices> DisjointUnion<Other, Indices> for L
+where
+    L: Append<Other>,
+    L::Output: Distinct<Indices>,
+{
+    type Output = L::Output;
+
+    #[inline(always)]
+    fn disjoint_union(self, other: Other) -> Self::Output {
+        self.append(other)
+    }
+}
+
+/// Allows for forming the intersection of two `HList` sets, producing the elements of
+/// `Self` whose types also appear in `Other`.
+///
+/// Trait resolution cannot decide that two types are different on stable Rust, so the
+/// intersection cannot be computed from the two sets alone.  Instead the caller names
+/// the `Output` set (usually through a type annotation), and it is checked at compile
+/// time: each of its types must appear in both `Self` and `Other`, and no type may
+/// appear in it more than once.  The `Indices` type parameter is inferred by the
+/// compiler.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// struct Db(&'static str);
+/// struct Logger;
+/// struct Cache;
+///
+/// let granted = hlist!(Db("postgres://"), Logger, Cache);
+/// let requested = hlist!(Cache, 7u32, Db("sqlite://"));
+/// let HCons(db, HCons(Cache, HNil)): HCons<Db, HCons<Cache, HNil>> =
+///     granted.intersection(&requested);
+/// assert_eq!(db.0, "postgres://");
+/// # }
+/// ```
+///
+/// Naming a type that is missing from either set fails to compile:
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let common: HCons<bool, HNil> = hlist!(1u8, true).intersection(&hlist!(2u8, "two"));
+/// # }
+/// ```
+pub trait Intersection<Other, Output, Indices> {
+    /// Extracts the elements of `Output` from this set, checking that each of their
+    /// types also appears in `other`.
+    fn intersection(self, other: &Other) -> Output;
+}
+
+impl<L, Other, Output, I, J, K> Intersection<Other, Output, (I, J, K)> for L
+where
+    L: Sculptor<Output, I>,
+    Other: SupersetOf<Output, J>,
+    Output: Distinct<K>,
+{
+    #[inline(always)]
+    fn intersection(self, _other: &Other) -> Output {
+        self.sculpt().0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_distinct<L: Distinct<I>, I>(_: &L) {}
+
+    #[test]
+    fn distinct_lists_should_be_accepted() {
+        assert_distinct(&HNil);
+        assert_distinct(&hlist!(1u8, "two", 3.0f32, 'c'));
+    }
+
+    fn assert_superset<L: SupersetOf<R, I>, R, I>(_: &L) {}
+    fn assert_subset<L: SubsetOf<S, I>, S, I>(_: &L) {}
+
+    #[test]
+    fn superset_and_subset_should_accept_any_order() {
+        let env = hlist!(1u8, "two", 3.0f32);
+        assert_superset::<_, HNil, _>(&env);
+        assert_superset::<_, HCons<f32, HCons<u8, HNil>>, _>(&env);
+        assert_subset::<_, HCons<bool, HCons<&str, HCons<f32, HCons<u8, HNil>>>>, _>(&env);
+    }
+
+    #[test]
+    fn disjoint_union_should_append_the_sets() {
+        let union = hlist!(1u8, "two").disjoint_union(hlist!(3.0f32));
+        assert_eq!(union, hlist!(1u8, "two", 3.0f32));
+        assert_distinct(&union);
+    }
+
+    #[test]
+    fn intersection_should_keep_the_elements_of_self() {
+        let common: HCons<&str, HCons<u8, HNil>> =
+            hlist!(1u8, "one", 1.0f32).intersection(&hlist!('c', 2u8, "two"));
+        assert_eq!(common, hlist!("one", 1u8));
+
+        let empty: HNil = hlist!(1u8).intersection(&hlist!("two"));
+        assert_eq!(empty, HNil);
+    }
+}
@@ -0,0 +1,189 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for splitting an `HList` into its first `N` elements and the remaining
+/// elements; the inverse of `Append::append`.
+///
+/// Splitting is checked at compile time: splitting at a position past the end of the list
+/// fails to compile.  This is implemented for positions up to 32.
+///
+/// `HNil` and `HCons` also provide `split_at`, `take`, and `skip` as inherent methods, so
+/// the position can be given with a turbofish:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two", 3u32, 'c');
+/// assert_eq!(hlist.split_at::<1>(), (hlist!(1u8), hlist!("two", 3u32, 'c')));
+/// assert_eq!(hlist.take::<2>(), hlist!(1u8, "two"));
+/// assert_eq!(hlist.skip::<2>(), hlist!(3u32, 'c'));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two");
+/// hlist.split_at::<3>();
+/// # }
+/// ```
+pub trait SplitAt<const N: usize> {
+    /// The `HList` of the first `N` elements.
+    type Prefix: HList;
+
+    /// The `HList` of the elements after the first `N`.
+    type Suffix: HList;
+
+    /// Splits this list into its first `N` elements and the remaining elements.
+    fn split_at(self) -> (Self::Prefix, Self::Suffix);
+}
+
+/// Allows for keeping only the first `N` elements of an `HList`.
+pub trait Take<const N: usize> {
+    /// The `HList` of the first `N` elements.
+    type Output: HList;
+
+    /// Returns the first `N` elements of this list.
+    fn take(self) -> Self::Output;
+}
+
+/// Allows for dropping the first `N` elements of an `HList`.  (This is named `Skip`, after
+/// `Iterator::skip`, so that it does not shadow `core::ops::Drop`.)
+pub trait Skip<const N: usize> {
+    /// The `HList` of the elements after the first `N`.
+    type Output: HList;
+
+    /// Returns the elements of this list after the first `N`.
+    fn skip(self) -> Self::Output;
+}
+
+impl<L: SplitAt<N>, const N: usize> Take<N> for L {
+    type Output = L::Prefix;
+
+    #[inline(always)]
+    fn take(self) -> Self::Output {
+        self.split_at().0
+    }
+}
+
+impl<L: SplitAt<N>, const N: usize> Skip<N> for L {
+    type Output = L::Suffix;
+
+    #[inline(always)]
+    fn skip(self) -> Self::Output {
+        self.split_at().1
+    }
+}
+
+/// Implements the inherent `split_at`, `take`, and `skip` methods for the given `HList`
+/// type, which delegate to the traits.
+macro_rules! impl_split_methods {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> $ty {
+            /// Splits this list into its first `N` elements and the remaining elements.
+            #[inline(always)]
+            pub fn split_at<const N: usize>(
+                self,
+            ) -> (<Self as SplitAt<N>>::Prefix, <Self as SplitAt<N>>::Suffix)
+            where
+                Self: SplitAt<N>,
+            {
+                SplitAt::<N>::split_at(self)
+            }
+
+            /// Returns the first `N` elements of this list.
+            #[inline(always)]
+            pub fn take<const N: usize>(self) -> <Self as Take<N>>::Output
+            where
+                Self: Take<N>,
+            {
+                Take::<N>::take(self)
+            }
+
+            /// Returns the elements of this list after the first `N`.
+            #[inline(always)]
+            pub fn skip<const N: usize>(self) -> <Self as Skip<N>>::Output
+            where
+                Self: Skip<N>,
+            {
+                Skip::<N>::skip(self)
+            }
+        }
+    };
+}
+
+impl_split_methods!([] HNil);
+impl_split_methods!([H, T: HList] HCons<H, T>);
+
+impl<L: HList> SplitAt<0> for L {
+    type Prefix = HNil;
+    type Suffix = L;
+
+    #[inline(always)]
+    fn split_at(self) -> (HNil, L) {
+        (HNil, self)
+    }
+}
+
+/// Implements `SplitAt` for each of the given positions, by splitting the tail at the
+/// preceding position.
+macro_rules! impl_split_at {
+    ($($prev:literal => $n:literal),*) => {
+        $(
+            impl<H, T> SplitAt<$n> for HCons<H, T>
+            where
+                T: HList + SplitAt<$prev>,
+            {
+                type Prefix = HCons<H, T::Prefix>;
+                type Suffix = T::Suffix;
+
+                #[inline(always)]
+                fn split_at(self) -> (Self::Prefix, Self::Suffix) {
+                    let HCons(head, tail) = self;
+                    let (prefix, suffix) = SplitAt::<$prev>::split_at(tail);
+                    (HCons(head, prefix), suffix)
+                }
+            }
+        )*
+    };
+}
+
+impl_split_at!(
+    0 => 1, 1 => 2, 2 => 3, 3 => 4, 4 => 5, 5 => 6, 6 => 7, 7 => 8,
+    8 => 9, 9 => 10, 10 => 11, 11 => 12, 12 => 13, 13 => 14, 14 => 15, 15 => 16,
+    16 => 17, 17 => 18, 18 => 19, 19 => 20, 20 => 21, 21 => 22, 22 => 23, 23 => 24,
+    24 => 25, 25 => 26, 26 => 27, 27 => 28, 28 => 29, 29 => 30, 30 => 31, 31 => 32
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_should_be_the_inverse_of_append() {
+        let hlist = hlist!(1u8, String::from("two"), 3.0f64);
+        assert_eq!(HNil.split_at::<0>(), (HNil, HNil));
+        assert_eq!(hlist.clone().split_at::<0>(), (HNil, hlist.clone()));
+        assert_eq!(hlist.clone().split_at::<3>(), (hlist.clone(), HNil));
+
+        let (prefix, suffix) = hlist.clone().split_at::<2>();
+        assert_eq!(prefix, hlist!(1u8, String::from("two")));
+        assert_eq!(suffix, hlist!(3.0f64));
+        assert_eq!(prefix.append(suffix), hlist);
+    }
+
+    #[test]
+    fn take_and_skip_should_keep_each_side() {
+        let hlist = hlist!(1u8, "two", 3u32);
+        assert_eq!(hlist.take::<1>(), hlist!(1u8));
+        assert_eq!(hlist.skip::<1>(), hlist!("two", 3u32));
+        assert_eq!(Take::<2>::take(hlist), hlist!(1u8, "two"));
+        assert_eq!(Skip::<3>::skip(hlist), HNil);
+    }
+}
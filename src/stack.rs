@@ -0,0 +1,139 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// A typed stack backed by an `HList`, whose head is the top of the stack.
+///
+/// Every operation returns the stack with its new type, so popping from an empty stack
+/// or applying an operation to operands of the wrong type is a compile error.  This
+/// makes `Stack` a convenient basis for statically checked stack machines.
+///
+/// ```
+/// # use pl_hlist::*;
+/// let stack = Stack::new().push(2u32).push(3u32).push(4u32);
+/// let stack = stack.binary(|a: u32, b: u32| a * b).binary(|a: u32, b: u32| a + b);
+/// let stack = stack.push(true).binary(|n: u32, flag: bool| if flag { n } else { 0 });
+/// let (result, _) = stack.pop();
+/// assert_eq!(result, 14);
+/// ```
+///
+/// Popping from an empty stack does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// let (value, stack) = Stack::new().push(1u8).pop();
+/// stack.pop();
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Stack<L: HList> {
+    elements: L,
+}
+
+impl Stack<HNil> {
+    /// Creates an empty stack.
+    pub fn new() -> Self {
+        Stack { elements: HNil }
+    }
+}
+
+impl<L: HList> Stack<L> {
+    /// Creates a stack with the given elements, where the head of the list is the top of
+    /// the stack.
+    pub fn from_hlist(elements: L) -> Self {
+        Stack { elements }
+    }
+
+    /// Pushes the given value onto the top of the stack.
+    pub fn push<V>(self, value: V) -> Stack<HCons<V, L>> {
+        Stack::from_hlist(HCons(value, self.elements))
+    }
+
+    /// Returns the elements of this stack, with the top of the stack at the head.
+    pub fn as_hlist(&self) -> &L {
+        &self.elements
+    }
+
+    /// Consumes this stack, returning its elements with the top of the stack at the head.
+    pub fn into_hlist(self) -> L {
+        self.elements
+    }
+}
+
+impl<V, Rest: HList> Stack<HCons<V, Rest>> {
+    /// Removes the top value, returning it along with the rest of the stack.
+    pub fn pop(self) -> (V, Stack<Rest>) {
+        let HCons(value, rest) = self.elements;
+        (value, Stack::from_hlist(rest))
+    }
+
+    /// Returns a reference to the top value.
+    pub fn peek(&self) -> &V {
+        &self.elements.0
+    }
+
+    /// Removes the top value.
+    pub fn drop_top(self) -> Stack<Rest> {
+        self.pop().1
+    }
+
+    /// Pushes a copy of the top value.
+    pub fn dup(self) -> Stack<HCons<V, HCons<V, Rest>>>
+    where
+        V: Clone,
+    {
+        let value = self.peek().clone();
+        self.push(value)
+    }
+
+    /// Replaces the top value with the result of applying `f` to it.
+    pub fn unary<R, F>(self, f: F) -> Stack<HCons<R, Rest>>
+    where
+        F: FnOnce(V) -> R,
+    {
+        let (value, rest) = self.pop();
+        rest.push(f(value))
+    }
+}
+
+impl<A, B, Rest: HList> Stack<HCons<B, HCons<A, Rest>>> {
+    /// Swaps the top two values.
+    pub fn swap(self) -> Stack<HCons<A, HCons<B, Rest>>> {
+        let HCons(b, HCons(a, rest)) = self.elements;
+        Stack::from_hlist(HCons(a, HCons(b, rest)))
+    }
+
+    /// Replaces the top two values with the result of applying `f` to them, where the
+    /// top value is the second operand (so `push(a).push(b).binary(f)` computes `f(a, b)`).
+    pub fn binary<R, F>(self, f: F) -> Stack<HCons<R, Rest>>
+    where
+        F: FnOnce(A, B) -> R,
+    {
+        let HCons(b, HCons(a, rest)) = self.elements;
+        Stack::from_hlist(HCons(f(a, b), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stack_operations_should_track_types() {
+        let stack = Stack::new().push("x").push(10i64).push(3i64);
+        assert_eq!(*stack.peek(), 3);
+
+        let stack = stack.swap().binary(|a: i64, b: i64| a - b);
+        assert_eq!(stack.as_hlist(), &hlist!(-7i64, "x"));
+
+        let stack = stack
+            .dup()
+            .binary(|a: i64, b: i64| a * b)
+            .unary(|n| n.to_string());
+        let (top, stack) = stack.pop();
+        assert_eq!(top, "49");
+        assert_eq!(stack.drop_top().into_hlist(), HNil);
+    }
+}
@@ -0,0 +1,187 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::coproduct::*;
+
+/// A transition from a state of type `From` to a state of type `To`, implemented by the
+/// event that triggers it.
+pub trait Transition<From, To> {
+    /// Consumes the current state, returning the next one.
+    fn transition(self, from: From) -> To;
+}
+
+/// A state machine whose set of states is the coproduct `States`, and whose current
+/// state is one of them.
+///
+/// Transitions are described by `Transition` impls, and `fire` only accepts transitions
+/// whose source and target are both members of `States`, so a machine can never enter
+/// a state it does not declare.
+///
+/// ```
+/// # use pl_hlist::*;
+/// struct Idle;
+/// struct Running(u32);
+/// struct Done(u32);
+///
+/// struct Start;
+/// struct Finish;
+///
+/// impl Transition<Idle, Running> for Start {
+///     fn transition(self, _: Idle) -> Running {
+///         Running(0)
+///     }
+/// }
+///
+/// impl Transition<Running, Done> for Finish {
+///     fn transition(self, from: Running) -> Done {
+///         Done(from.0)
+///     }
+/// }
+///
+/// type States = Coproduct<Idle, Coproduct<Running, Coproduct<Done, CNil>>>;
+///
+/// let mut machine = StateMachine::<States>::new(Idle);
+/// assert!(machine.fire(Finish).is_err());
+/// assert!(machine.fire(Start).is_ok());
+/// machine.get_mut::<Running, _>().unwrap().0 += 1;
+/// assert!(machine.fire(Finish).is_ok());
+/// assert_eq!(machine.get::<Done, _>().unwrap().0, 1);
+/// ```
+///
+/// A transition to a state that is not a member of `States` does not compile:
+///
+/// ```compile_fail
+/// # use pl_hlist::*;
+/// struct Idle;
+/// struct Running;
+/// struct Start;
+///
+/// impl Transition<Idle, Running> for Start {
+///     fn transition(self, _: Idle) -> Running {
+///         Running
+///     }
+/// }
+///
+/// let mut machine = StateMachine::<Coproduct<Idle, CNil>>::new(Idle);
+/// machine.fire(Start);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StateMachine<States> {
+    // This is only `None` if a transition panicked
+    state: Option<States>,
+}
+
+impl<States> StateMachine<States> {
+    /// Creates a state machine in the given initial state.
+    pub fn new<S, Index>(initial: S) -> Self
+    where
+        States: CoprodInjector<S, Index>,
+    {
+        StateMachine {
+            state: Some(States::inject(initial)),
+        }
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> &States {
+        self.state.as_ref().expect(POISONED)
+    }
+
+    /// Returns a reference to the current state if it is of type `S`.
+    pub fn get<S, Index>(&self) -> Option<&S>
+    where
+        States: CoprodSelector<S, Index>,
+    {
+        self.state().get()
+    }
+
+    /// Returns a mutable reference to the current state if it is of type `S`.
+    pub fn get_mut<S, Index>(&mut self) -> Option<&mut S>
+    where
+        States: CoprodSelector<S, Index>,
+    {
+        self.state.as_mut().expect(POISONED).get_mut()
+    }
+
+    /// Returns true if the current state is of type `S`.
+    pub fn is_in<S, Index>(&self) -> bool
+    where
+        States: CoprodSelector<S, Index>,
+    {
+        self.get::<S, Index>().is_some()
+    }
+
+    /// Applies the transition triggered by the given event, if the current state is its
+    /// `From` state.  Otherwise the event is returned and the state is unchanged.
+    ///
+    /// If the event triggers transitions from more than one state, the `From` and `To`
+    /// types must be specified explicitly.
+    pub fn fire<E, From, To, FromIndex, ToIndex>(&mut self, event: E) -> Result<(), E>
+    where
+        E: Transition<From, To>,
+        States: CoprodSelector<From, FromIndex>
+            + CoprodUninjector<From, FromIndex>
+            + CoprodInjector<To, ToIndex>,
+    {
+        if !self.is_in::<From, FromIndex>() {
+            return Err(event);
+        }
+        let from = match self.state.take().expect(POISONED).uninject() {
+            Ok(from) => from,
+            Err(_) => unreachable!(),
+        };
+        self.state = Some(States::inject(event.transition(from)));
+        Ok(())
+    }
+
+    /// Consumes this state machine, returning the current state.
+    pub fn into_state(self) -> States {
+        self.state.expect(POISONED)
+    }
+}
+
+const POISONED: &str = "state machine was poisoned by a panicking transition";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Locked;
+
+    #[derive(Debug, PartialEq)]
+    struct Unlocked(u8);
+
+    struct Coin(u8);
+    struct Push;
+
+    impl Transition<Locked, Unlocked> for Coin {
+        fn transition(self, _: Locked) -> Unlocked {
+            Unlocked(self.0)
+        }
+    }
+
+    impl Transition<Unlocked, Locked> for Push {
+        fn transition(self, _: Unlocked) -> Locked {
+            Locked
+        }
+    }
+
+    type States = Coproduct<Locked, Coproduct<Unlocked, CNil>>;
+
+    #[test]
+    fn fire_should_only_apply_transitions_from_the_current_state() {
+        let mut machine = StateMachine::<States>::new(Locked);
+        assert!(machine.fire(Push).is_err());
+        assert!(machine.is_in::<Locked, _>());
+
+        assert!(machine.fire(Coin(5)).is_ok());
+        assert_eq!(machine.get::<Unlocked, _>(), Some(&Unlocked(5)));
+        assert!(machine.fire(Coin(6)).is_err());
+
+        assert!(machine.fire(Push).is_ok());
+        assert_eq!(machine.into_state(), States::inject(Locked));
+    }
+}
@@ -0,0 +1,85 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::fmt::Debug;
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::{Just, Map, Strategy};
+
+impl Arbitrary for HNil {
+    type Parameters = ();
+    type Strategy = Just<HNil>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        Just(HNil)
+    }
+}
+
+/// Generates each element independently, with the strategy for each element type.
+/// Shrinking shrinks one element at a time while holding the others fixed, so a
+/// failing case is reduced element by element.
+impl<H, T> Arbitrary for HCons<H, T>
+where
+    H: Arbitrary,
+    T: HList + Arbitrary,
+{
+    type Parameters = (H::Parameters, T::Parameters);
+    type Strategy = Map<(H::Strategy, T::Strategy), fn((H, T)) -> Self>;
+
+    fn arbitrary_with((head, tail): Self::Parameters) -> Self::Strategy {
+        (H::arbitrary_with(head), T::arbitrary_with(tail)).prop_map(|(h, t)| HCons(h, t))
+    }
+}
+
+/// Returns a proptest strategy for values of type `S` (such as a struct that derives
+/// `HListSupport`), built from arbitrary `HList`s.
+///
+/// ```
+/// # use pl_hlist::*;
+/// use proptest::prelude::*;
+///
+/// #[derive(Clone, Debug, PartialEq, HListSupport)]
+/// struct Range {
+///     start: u16,
+///     len: u8,
+/// }
+///
+/// proptest!(|(range in arbitrary_from_hlist::<Range, _>())| {
+///     prop_assert_eq!(Range::from_hlist(range.to_hlist()), range);
+/// });
+/// ```
+pub fn arbitrary_from_hlist<S, H>() -> Map<H::Strategy, fn(H) -> S>
+where
+    S: FromHList<H> + Debug,
+    H: HList + Arbitrary,
+{
+    H::arbitrary().prop_map(S::from_hlist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::strategy::ValueTree;
+    use proptest::test_runner::TestRunner;
+
+    #[test]
+    fn shrinking_should_shrink_each_element_independently() {
+        let mut runner = TestRunner::deterministic();
+        let mut tree = any::<HCons<u8, HCons<bool, HNil>>>()
+            .new_tree(&mut runner)
+            .unwrap();
+        while tree.simplify() {}
+        assert_eq!(tree.current(), hlist!(0u8, false));
+    }
+
+    proptest! {
+        #[test]
+        fn append_should_preserve_elements(a in any::<HCons<u8, HCons<String, HNil>>>(), b in any::<HCons<bool, HNil>>()) {
+            let HCons(x, HCons(y, HNil)) = a.clone();
+            prop_assert_eq!(a.append(b), HCons(x, HCons(y, b)));
+        }
+    }
+}
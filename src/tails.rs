@@ -0,0 +1,25 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for converting an `HList` whose tails are stored indirectly (such as an
+/// `HConsBox` or an `ArenaHCons`) back into a plain `HList`, with all elements stored
+/// inline.
+pub trait IntoInline {
+    /// The plain form of this list.
+    type Output: HList;
+
+    /// Moves the elements of this list out of their indirect storage.
+    fn into_inline(self) -> Self::Output;
+}
+
+impl IntoInline for HNil {
+    type Output = HNil;
+
+    fn into_inline(self) -> Self::Output {
+        HNil
+    }
+}
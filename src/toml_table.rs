@@ -0,0 +1,283 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use crate::patch::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use toml::{Table, Value};
+
+/// Error returned when a labelled `HList` cannot be converted to or from a TOML table.
+/// Each variant names the field that caused the problem.
+#[derive(Debug)]
+pub enum TomlFieldError {
+    /// The table did not contain a value for the field.
+    Missing { field: &'static str },
+    /// The field's value could not be converted into a TOML value.
+    Serialize {
+        field: &'static str,
+        error: toml::ser::Error,
+    },
+    /// The table's value for the field could not be converted into the field's type.
+    Deserialize {
+        field: &'static str,
+        error: toml::de::Error,
+    },
+}
+
+impl TomlFieldError {
+    /// Returns the name of the field that caused this error.
+    pub fn field(&self) -> &'static str {
+        match self {
+            TomlFieldError::Missing { field }
+            | TomlFieldError::Serialize { field, .. }
+            | TomlFieldError::Deserialize { field, .. } => field,
+        }
+    }
+}
+
+impl fmt::Display for TomlFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TomlFieldError::Missing { field } => write!(f, "missing field `{}`", field),
+            TomlFieldError::Serialize { field, error } => {
+                write!(f, "failed to serialize field `{}`: {}", field, error)
+            }
+            TomlFieldError::Deserialize { field, error } => {
+                write!(f, "failed to deserialize field `{}`: {}", field, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TomlFieldError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TomlFieldError::Missing { .. } => None,
+            TomlFieldError::Serialize { error, .. } => Some(error),
+            TomlFieldError::Deserialize { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Allows for copying the contents of a labelled `HList` into a TOML table, with one
+/// entry per field.
+pub trait ToTomlTable {
+    /// Inserts one entry into `table` for each field in this list.
+    fn insert_into_toml_table(&self, table: &mut Table) -> Result<(), TomlFieldError>;
+
+    /// Converts this list into a new TOML table.
+    fn to_toml_table(&self) -> Result<Table, TomlFieldError> {
+        let mut table = Table::new();
+        self.insert_into_toml_table(&mut table)?;
+        Ok(table)
+    }
+
+    /// Converts this list into a `toml::Value::Table`.
+    fn to_toml_value(&self) -> Result<Value, TomlFieldError> {
+        self.to_toml_table().map(Value::Table)
+    }
+}
+
+impl ToTomlTable for HNil {
+    fn insert_into_toml_table(&self, _: &mut Table) -> Result<(), TomlFieldError> {
+        Ok(())
+    }
+}
+
+impl<L, V, T> ToTomlTable for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: Serialize,
+    T: HList + ToTomlTable,
+{
+    fn insert_into_toml_table(&self, table: &mut Table) -> Result<(), TomlFieldError> {
+        let value = Value::try_from(&self.0.value).map_err(|error| TomlFieldError::Serialize {
+            field: L::NAME,
+            error,
+        })?;
+        table.insert(L::NAME.to_string(), value);
+        self.1.insert_into_toml_table(table)
+    }
+}
+
+/// Allows for converting a TOML table into a labelled `HList`, reading one entry per
+/// field.  Entries that do not correspond to a field are ignored.
+pub trait FromTomlTable: Sized {
+    /// Converts the entries of the given table into this list.
+    fn from_toml_table(table: &Table) -> Result<Self, TomlFieldError>;
+}
+
+impl FromTomlTable for HNil {
+    fn from_toml_table(_: &Table) -> Result<Self, TomlFieldError> {
+        Ok(HNil)
+    }
+}
+
+impl<L, V, T> FromTomlTable for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: DeserializeOwned,
+    T: HList + FromTomlTable,
+{
+    fn from_toml_table(table: &Table) -> Result<Self, TomlFieldError> {
+        let value = table
+            .get(L::NAME)
+            .ok_or(TomlFieldError::Missing { field: L::NAME })?
+            .clone()
+            .try_into()
+            .map_err(|error| TomlFieldError::Deserialize {
+                field: L::NAME,
+                error,
+            })?;
+        let tail = T::from_toml_table(table)?;
+        Ok(HCons(Field::new(value), tail))
+    }
+}
+
+/// Allows for converting the changes in a `Patch` to and from a TOML table, with one
+/// entry per changed field.
+pub trait TomlPatchFields: Sized {
+    /// Inserts one entry into `table` for each field that holds a new value.
+    fn insert_changes_into_toml_table(&self, table: &mut Table) -> Result<(), TomlFieldError>;
+
+    /// Reads the new value of each field that has an entry in `table`.
+    fn changes_from_toml_table(table: &Table) -> Result<Self, TomlFieldError>;
+}
+
+impl TomlPatchFields for HNil {
+    fn insert_changes_into_toml_table(&self, _: &mut Table) -> Result<(), TomlFieldError> {
+        Ok(())
+    }
+
+    fn changes_from_toml_table(_: &Table) -> Result<Self, TomlFieldError> {
+        Ok(HNil)
+    }
+}
+
+impl<L, V, T> TomlPatchFields for HCons<Field<L, Option<V>>, T>
+where
+    L: Label,
+    V: Serialize + DeserializeOwned,
+    T: HList + TomlPatchFields,
+{
+    fn insert_changes_into_toml_table(&self, table: &mut Table) -> Result<(), TomlFieldError> {
+        if let Some(value) = &self.0.value {
+            let value = Value::try_from(value).map_err(|error| TomlFieldError::Serialize {
+                field: L::NAME,
+                error,
+            })?;
+            table.insert(L::NAME.to_string(), value);
+        }
+        self.1.insert_changes_into_toml_table(table)
+    }
+
+    fn changes_from_toml_table(table: &Table) -> Result<Self, TomlFieldError> {
+        let value = match table.get(L::NAME) {
+            Some(value) => {
+                Some(
+                    value
+                        .clone()
+                        .try_into()
+                        .map_err(|error| TomlFieldError::Deserialize {
+                            field: L::NAME,
+                            error,
+                        })?,
+                )
+            }
+            None => None,
+        };
+        let tail = T::changes_from_toml_table(table)?;
+        Ok(HCons(Field::new(value), tail))
+    }
+}
+
+impl<S, F> ToTomlTable for Patch<S, F>
+where
+    F: HList + TomlPatchFields,
+{
+    fn insert_into_toml_table(&self, table: &mut Table) -> Result<(), TomlFieldError> {
+        self.fields().insert_changes_into_toml_table(table)
+    }
+}
+
+impl<S, F> FromTomlTable for Patch<S, F>
+where
+    F: HList + TomlPatchFields,
+{
+    fn from_toml_table(table: &Table) -> Result<Self, TomlFieldError> {
+        F::changes_from_toml_table(table).map(Patch::from_fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct ServerConfig {
+        host: String,
+        port: u16,
+        tags: Vec<String>,
+    }
+
+    type ServerConfigHList = HCons<
+        Field<label!(host), String>,
+        HCons<Field<label!(port), u16>, HCons<Field<label!(tags), Vec<String>>, HNil>>,
+    >;
+
+    #[test]
+    fn struct_should_round_trip_through_toml() {
+        let config = ServerConfig {
+            host: "localhost".to_string(),
+            port: 8080,
+            tags: vec!["a".to_string()],
+        };
+        let table = config.to_labelled_hlist().to_toml_table().unwrap();
+        assert_eq!(
+            toml::to_string(&table).unwrap(),
+            "host = \"localhost\"\nport = 8080\ntags = [\"a\"]\n"
+        );
+
+        let hlist = ServerConfigHList::from_toml_table(&table).unwrap();
+        assert_eq!(ServerConfig::from_labelled_hlist(hlist), config);
+    }
+
+    #[test]
+    fn patch_should_round_trip_through_toml() {
+        let old = ServerConfig {
+            host: "localhost".to_string(),
+            port: 8080,
+            tags: vec![],
+        };
+        let new = ServerConfig {
+            port: 9090,
+            ..old.clone()
+        };
+        let table = Patch::diff(&old, &new).to_toml_table().unwrap();
+        assert_eq!(toml::to_string(&table).unwrap(), "port = 9090\n");
+
+        let patch = Patch::<ServerConfig, _>::from_toml_table(&table).unwrap();
+        assert_eq!(patch.apply(old), new);
+    }
+
+    #[test]
+    fn errors_should_name_the_field() {
+        let table: Table = toml::from_str("host = \"localhost\"\ntags = []").unwrap();
+        let err = ServerConfigHList::from_toml_table(&table).unwrap_err();
+        assert_eq!(err.field(), "port");
+        assert_eq!(err.to_string(), "missing field `port`");
+
+        let table: Table = toml::from_str("host = 1\nport = 80\ntags = []").unwrap();
+        let err = ServerConfigHList::from_toml_table(&table).unwrap_err();
+        assert_eq!(err.field(), "host");
+        assert!(err
+            .to_string()
+            .starts_with("failed to deserialize field `host`"));
+    }
+}
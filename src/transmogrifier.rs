@@ -0,0 +1,210 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use crate::query::*;
+use core::marker::PhantomData;
+
+/// Allows for converting `Self` into a structurally similar `Target`, matching fields by
+/// name and recursing into fields whose types differ.
+///
+/// A value converts into a `Target` of the same type as-is.  Otherwise both types must
+/// support labelled `HList` conversion (as with the `HListSupport` derive), and each
+/// field of `Target` is taken from the source field with the same name, transmogrifying
+/// its value in turn.  Source fields that the target lacks are dropped.
+///
+/// The `Indices` type parameter is inferred by the compiler and records how each field
+/// is found and converted.  A nested field whose type is the same in both structs, and
+/// which itself supports labelled conversion, can be converted either as-is or field by
+/// field; since the compiler cannot choose between them, such fields should be moved
+/// with `transform_from` or by hand instead.
+///
+/// ```
+/// # use pl_hlist::*;
+/// #[derive(Clone, HListSupport)]
+/// struct AddressDto {
+///     city: String,
+///     zip: String,
+///     country: String,
+/// }
+///
+/// #[derive(Clone, HListSupport)]
+/// struct UserDto {
+///     name: String,
+///     address: AddressDto,
+///     etag: u64,
+/// }
+///
+/// #[derive(Clone, HListSupport)]
+/// struct Address {
+///     zip: String,
+///     city: String,
+/// }
+///
+/// #[derive(Clone, HListSupport)]
+/// struct User {
+///     address: Address,
+///     name: String,
+/// }
+///
+/// let dto = UserDto {
+///     name: "kim".to_string(),
+///     address: AddressDto {
+///         city: "Oslo".to_string(),
+///         zip: "0150".to_string(),
+///         country: "NO".to_string(),
+///     },
+///     etag: 1,
+/// };
+/// let user: User = dto.transmogrify();
+/// assert_eq!((user.name.as_str(), user.address.city.as_str()), ("kim", "Oslo"));
+/// ```
+pub trait Transmogrifier<Target, Indices> {
+    /// Converts `self` into `Target`.
+    fn transmogrify(self) -> Target;
+}
+
+/// The `Transmogrifier` index for a value that is converted as-is.
+pub enum IdentityTransmog {}
+
+/// The `Transmogrifier` index for a value that is converted field by field, via the
+/// labelled `HList` representations `SourceFields` and `TargetFields`.
+pub struct DoTransmog<SourceFields, TargetFields, Indices>(
+    PhantomData<(SourceFields, TargetFields, Indices)>,
+);
+
+impl<T> Transmogrifier<T, IdentityTransmog> for T {
+    #[inline(always)]
+    fn transmogrify(self) -> T {
+        self
+    }
+}
+
+impl<Source, Target, SourceFields, TargetFields, Indices>
+    Transmogrifier<Target, DoTransmog<SourceFields, TargetFields, Indices>> for Source
+where
+    Source: IntoLabelledHList<SourceFields>,
+    Target: FromLabelledHList<TargetFields>,
+    SourceFields: HList + TransmogrifyFields<TargetFields, Indices>,
+    TargetFields: HList,
+{
+    #[inline]
+    fn transmogrify(self) -> Target {
+        Target::from_labelled_hlist(self.into_labelled_hlist().transmogrify_fields())
+    }
+}
+
+/// Allows for converting a labelled `HList` into the labelled `HList` `TargetFields`,
+/// taking each target field from the source field with the same name and
+/// transmogrifying its value.
+///
+/// This is the field-level counterpart to `Transmogrifier`.
+pub trait TransmogrifyFields<TargetFields, Indices> {
+    /// Converts the fields of `self` into `TargetFields`.
+    fn transmogrify_fields(self) -> TargetFields;
+}
+
+impl<Source: HList> TransmogrifyFields<HNil, HNil> for Source {
+    #[inline(always)]
+    fn transmogrify_fields(self) -> HNil {
+        HNil
+    }
+}
+
+impl<Source, L, Value, TargetTail, PluckIndex, ValueIndices, TailIndices>
+    TransmogrifyFields<
+        HCons<Field<L, Value>, TargetTail>,
+        HCons<(PluckIndex, ValueIndices), TailIndices>,
+    > for Source
+where
+    Source: LabelPlucker<L, PluckIndex>,
+    Source::Value: Transmogrifier<Value, ValueIndices>,
+    Source::Remainder: TransmogrifyFields<TargetTail, TailIndices>,
+    TargetTail: HList,
+    TailIndices: HList,
+{
+    #[inline]
+    fn transmogrify_fields(self) -> HCons<Field<L, Value>, TargetTail> {
+        let (field, remainder) = self.pluck_by_label();
+        HCons(
+            Field::new(field.into_value().transmogrify()),
+            remainder.transmogrify_fields(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, HListSupport)]
+    struct ApiPoint {
+        y: i32,
+        x: i32,
+        label: &'static str,
+    }
+
+    #[derive(Clone, HListSupport)]
+    struct ApiShape {
+        name: &'static str,
+        origin: ApiPoint,
+        extent: ApiPoint,
+    }
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Shape {
+        extent: Point,
+        origin: Point,
+    }
+
+    #[test]
+    fn transmogrify_should_convert_nested_structs_by_name() {
+        let api = ApiShape {
+            name: "box",
+            origin: ApiPoint {
+                y: 2,
+                x: 1,
+                label: "o",
+            },
+            extent: ApiPoint {
+                y: 20,
+                x: 10,
+                label: "e",
+            },
+        };
+        assert_eq!(
+            (api.name, api.origin.label, api.extent.label),
+            ("box", "o", "e")
+        );
+
+        let shape: Shape = api.transmogrify();
+        assert_eq!(
+            shape,
+            Shape {
+                extent: Point { x: 10, y: 20 },
+                origin: Point { x: 1, y: 2 },
+            }
+        );
+    }
+
+    #[test]
+    fn transmogrify_fields_should_convert_labelled_hlists() {
+        type Target = HCons<Field<label!(b), u8>, HNil>;
+        let source = hlist!(
+            Field::<label!(a), _>::new("a"),
+            Field::<label!(b), _>::new(2u8)
+        );
+        let target: Target = source.transmogrify_fields();
+        assert_eq!(target.head().value, 2);
+    }
+}
@@ -0,0 +1,125 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for converting an `HList` into the tuple with the same elements, in order.
+///
+/// This is implemented for `HList`s of up to 16 elements, which can also be converted
+/// to and from tuples with `From`/`Into`:
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// assert_eq!(hlist!(1u8, "a").into_tuple(), (1u8, "a"));
+///
+/// let hlist = HCons::from((1u8, "a", 'c'));
+/// assert_eq!(hlist, hlist!(1u8, "a", 'c'));
+/// let tuple: (u8, &str, char) = hlist.into();
+/// assert_eq!(tuple, (1u8, "a", 'c'));
+/// # }
+/// ```
+pub trait IntoTuple: HList {
+    /// The tuple with the same elements as this list.
+    type Tuple;
+
+    /// Converts this list into the tuple with the same elements.
+    fn into_tuple(self) -> Self::Tuple;
+}
+
+impl IntoTuple for HNil {
+    type Tuple = ();
+
+    #[inline(always)]
+    fn into_tuple(self) {}
+}
+
+impl From<()> for HNil {
+    #[inline(always)]
+    fn from(_: ()) -> Self {
+        HNil
+    }
+}
+
+impl From<HNil> for () {
+    #[inline(always)]
+    fn from(_: HNil) -> Self {}
+}
+
+/// Implements the tuple conversions for the `HList` with each of the given lists of
+/// elements.
+macro_rules! impl_tuple_conversions {
+    ($(($($H:ident $h:ident),+)),+ $(,)?) => {
+        $(
+            impl<$($H),+> IntoTuple for hlist_type!($($H),+) {
+                type Tuple = ($($H,)+);
+
+                #[inline(always)]
+                fn into_tuple(self) -> Self::Tuple {
+                    let hlist_pattern!($($h),+) = self;
+                    ($($h,)+)
+                }
+            }
+
+            impl<$($H),+> From<($($H,)+)> for hlist_type!($($H),+) {
+                #[inline(always)]
+                fn from(tuple: ($($H,)+)) -> Self {
+                    let ($($h,)+) = tuple;
+                    hlist!($($h),+)
+                }
+            }
+
+            impl<$($H),+> From<hlist_type!($($H),+)> for ($($H,)+) {
+                #[inline(always)]
+                fn from(hlist: hlist_type!($($H),+)) -> Self {
+                    hlist.into_tuple()
+                }
+            }
+        )+
+    };
+}
+
+#[rustfmt::skip]
+impl_tuple_conversions!(
+    (A1 a1),
+    (A1 a1, A2 a2),
+    (A1 a1, A2 a2, A3 a3),
+    (A1 a1, A2 a2, A3 a3, A4 a4),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12, A13 a13),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14, A15 a15),
+    (A1 a1, A2 a2, A3 a3, A4 a4, A5 a5, A6 a6, A7 a7, A8 a8, A9 a9, A10 a10, A11 a11, A12 a12, A13 a13, A14 a14, A15 a15, A16 a16)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tuples_should_round_trip_through_hlists() {
+        assert_eq!(HNil.into_tuple(), ());
+        assert_eq!(HNil::from(()), HNil);
+        assert_eq!(hlist!("one").into_tuple(), ("one",));
+
+        // Tuples only implement `PartialEq` up to arity 12, so compare the longest
+        // conversions as `HList`s
+        let hlist = hlist!(
+            1u8, 2u16, 3u32, 4u64, 5i8, 6i16, 7i32, 8i64, 'a', 'b', 'c', 'd', "e", "f", 1.5f32,
+            true
+        );
+        let tuple = hlist.into_tuple();
+        assert!(tuple.15);
+        assert_eq!(HCons::from(tuple), hlist);
+    }
+}
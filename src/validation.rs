@@ -0,0 +1,207 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Checks values of type `T`, returning a message describing the problem for invalid
+/// values.
+///
+/// This is implemented for closures, and for `Unchecked`, which accepts every value.
+pub trait Validator<T> {
+    /// Returns an error message if the given value is invalid.
+    fn validate(&self, value: &T) -> Result<(), String>;
+}
+
+impl<T, F> Validator<T> for F
+where
+    F: Fn(&T) -> Result<(), String>,
+{
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self(value)
+    }
+}
+
+/// A `Validator` that accepts every value, for fields that need no validation.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Unchecked;
+
+impl<T> Validator<T> for Unchecked {
+    fn validate(&self, _value: &T) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A problem with the value of a single field.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldError {
+    /// The name of the field.
+    pub field: &'static str,
+    /// The message returned by the field's validator.
+    pub message: String,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid `{}`: {}", self.field, self.message)
+    }
+}
+
+/// The aggregated set of problems encountered while validating a labelled `HList`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    /// Returns the individual problems, in field order.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationErrors {}
+
+/// Allows for checking each field of a labelled `HList` against the corresponding
+/// element of an `HList` of `Validator`s.
+pub trait ValidateFields<Validators> {
+    /// Checks each field, recording problems in `errors`.
+    fn check_fields(&self, validators: &Validators, errors: &mut Vec<FieldError>);
+
+    /// Checks each field, returning this list if every field is valid, or every problem
+    /// encountered otherwise.
+    fn validate_fields(self, validators: &Validators) -> Result<Self, ValidationErrors>
+    where
+        Self: Sized,
+    {
+        let mut errors = Vec::new();
+        self.check_fields(validators, &mut errors);
+        if errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(ValidationErrors { errors })
+        }
+    }
+}
+
+impl ValidateFields<HNil> for HNil {
+    fn check_fields(&self, _validators: &HNil, _errors: &mut Vec<FieldError>) {}
+}
+
+impl<L, T, Tail, V, VTail> ValidateFields<HCons<V, VTail>> for HCons<Field<L, T>, Tail>
+where
+    L: Label,
+    V: Validator<T>,
+    Tail: HList + ValidateFields<VTail>,
+    VTail: HList,
+{
+    fn check_fields(&self, validators: &HCons<V, VTail>, errors: &mut Vec<FieldError>) {
+        if let Err(message) = validators.0.validate(&self.0.value) {
+            errors.push(FieldError {
+                field: L::NAME,
+                message,
+            });
+        }
+        self.1.check_fields(&validators.1, errors)
+    }
+}
+
+/// Checks each field of the given value against the corresponding element of an
+/// `HList` of `Validator`s, returning the value if every field is valid, or every
+/// problem encountered (keyed by field name) otherwise.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// #[derive(Debug, HListSupport)]
+/// struct Signup {
+///     name: String,
+///     age: u8,
+///     newsletter: bool,
+/// }
+///
+/// # fn main() {
+/// let validators = hlist!(
+///     |name: &String| if name.is_empty() { Err("must not be empty".into()) } else { Ok(()) },
+///     |age: &u8| if *age < 13 { Err(format!("{} is too young", age)) } else { Ok(()) },
+///     Unchecked
+/// );
+///
+/// let signup = Signup { name: "".into(), age: 9, newsletter: true };
+/// let errors = validate(signup, &validators).unwrap_err();
+/// assert_eq!(errors.errors()[0].field, "name");
+/// assert_eq!(errors.to_string(), "invalid `name`: must not be empty\ninvalid `age`: 9 is too young");
+/// # }
+/// ```
+pub fn validate<S, L, Validators>(value: S, validators: &Validators) -> Result<S, ValidationErrors>
+where
+    S: IntoLabelledHList<L> + FromLabelledHList<L>,
+    L: HList + ValidateFields<Validators>,
+{
+    value
+        .into_labelled_hlist()
+        .validate_fields(validators)
+        .map(S::from_labelled_hlist)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(Clone, Debug, PartialEq, HListSupport)]
+    struct Range {
+        start: u32,
+        end: u32,
+        label: &'static str,
+    }
+
+    fn at_most_100(value: &u32) -> Result<(), String> {
+        if *value <= 100 {
+            Ok(())
+        } else {
+            Err("must be at most 100".to_string())
+        }
+    }
+
+    #[test]
+    fn validate_should_report_each_invalid_field() {
+        let validators = hlist!(at_most_100, at_most_100, Unchecked);
+
+        let range = Range {
+            start: 1,
+            end: 50,
+            label: "ok",
+        };
+        assert_eq!(validate(range.clone(), &validators), Ok(range));
+
+        let range = Range {
+            start: 200,
+            end: 300,
+            label: "bad",
+        };
+        let errors = validate(range, &validators).unwrap_err();
+        let fields: Vec<_> = errors.errors().iter().map(|e| e.field).collect();
+        assert_eq!(fields, ["start", "end"]);
+        assert_eq!(errors.errors()[1].message, "must be at most 100");
+    }
+}
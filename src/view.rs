@@ -0,0 +1,316 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+
+/// Allows for borrowing the contents of `Self` as an `HList` of references, without
+/// copying or moving anything.
+///
+/// This is implemented for all `HList`s, and the `HListSupport` derive implements it for
+/// structs marked with `#[hlist(views)]` (exposing a reference to each field, in
+/// declaration order), so read-only structural algorithms can operate directly on the
+/// original memory.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(HListSupport)]
+/// #[hlist(views)]
+/// struct Config {
+///     name: String,
+///     retries: u8,
+/// }
+///
+/// let config = Config { name: String::from("server"), retries: 3 };
+/// let HCons(name, HCons(retries, HNil)) = config.hlist_view();
+/// assert!(std::ptr::eq(name, &config.name));
+/// assert_eq!(*retries, 3);
+/// # }
+/// ```
+pub trait HListView {
+    /// An `HList` with a reference to each element of `Self`.
+    type View<'a>: HList
+    where
+        Self: 'a;
+
+    /// Returns an `HList` with a reference to each element of `self`.
+    fn hlist_view(&self) -> Self::View<'_>;
+
+    /// Returns an `HList` with a reference to each element of `self`; the borrowing
+    /// counterpart of `ToHList::to_hlist`, which clones each element instead.
+    #[inline(always)]
+    fn to_hlist_ref(&self) -> Self::View<'_> {
+        self.hlist_view()
+    }
+}
+
+impl HListView for HNil {
+    type View<'a> = HNil;
+
+    fn hlist_view(&self) -> Self::View<'_> {
+        HNil
+    }
+}
+
+impl<H, T> HListView for HCons<H, T>
+where
+    T: HList + HListView,
+{
+    type View<'a>
+        = HCons<&'a H, T::View<'a>>
+    where
+        Self: 'a;
+
+    fn hlist_view(&self) -> Self::View<'_> {
+        HCons(&self.0, self.1.hlist_view())
+    }
+}
+
+/// Allows for mutably borrowing the contents of `Self` as an `HList` of mutable
+/// references, so that the elements can be updated in place.
+///
+/// Like `HListView`, this is implemented for all `HList`s and by the `HListSupport`
+/// derive for structs marked with `#[hlist(views)]`.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// #[derive(HListSupport)]
+/// #[hlist(views)]
+/// struct Counter {
+///     name: String,
+///     count: u64,
+/// }
+///
+/// let mut counter = Counter { name: String::from("hits"), count: 3 };
+/// let HCons(name, HCons(count, HNil)) = counter.to_hlist_mut();
+/// name.push('!');
+/// *count += 1;
+/// assert_eq!((counter.name.as_str(), counter.count), ("hits!", 4));
+/// # }
+/// ```
+pub trait HListViewMut {
+    /// An `HList` with a mutable reference to each element of `Self`.
+    type ViewMut<'a>: HList
+    where
+        Self: 'a;
+
+    /// Returns an `HList` with a mutable reference to each element of `self`.
+    fn hlist_view_mut(&mut self) -> Self::ViewMut<'_>;
+
+    /// Returns an `HList` with a mutable reference to each element of `self`.
+    #[inline(always)]
+    fn to_hlist_mut(&mut self) -> Self::ViewMut<'_> {
+        self.hlist_view_mut()
+    }
+}
+
+impl HListViewMut for HNil {
+    type ViewMut<'a> = HNil;
+
+    fn hlist_view_mut(&mut self) -> Self::ViewMut<'_> {
+        HNil
+    }
+}
+
+impl<H, T> HListViewMut for HCons<H, T>
+where
+    T: HList + HListViewMut,
+{
+    type ViewMut<'a>
+        = HCons<&'a mut H, T::ViewMut<'a>>
+    where
+        Self: 'a;
+
+    fn hlist_view_mut(&mut self) -> Self::ViewMut<'_> {
+        HCons(&mut self.0, self.1.hlist_view_mut())
+    }
+}
+
+/// Allows for borrowing each element of an `HList`, producing an `HList` of shared
+/// references that can be mapped or folded over without consuming the original.
+///
+/// This is equivalent to `HListView::hlist_view` for `HList`s, but names the lifetime of
+/// the borrow as a trait parameter, which makes it easier to combine with other bounds.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(7u32, String::from("four"));
+/// let lens = hlist.to_ref().map(hlist!(|n: &u32| *n as usize, |s: &String| s.len()));
+/// assert_eq!(lens, hlist!(7usize, 4usize));
+/// assert_eq!(hlist, hlist!(7u32, String::from("four")));
+/// # }
+/// ```
+pub trait ToRef<'a> {
+    /// An `HList` with a shared reference to each element of `Self`.
+    type Output: HList;
+
+    /// Returns an `HList` with a shared reference to each element of `self`.
+    fn to_ref(&'a self) -> Self::Output;
+}
+
+impl<'a> ToRef<'a> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn to_ref(&'a self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<'a, H, T> ToRef<'a> for HCons<H, T>
+where
+    H: 'a,
+    T: HList + ToRef<'a>,
+{
+    type Output = HCons<&'a H, T::Output>;
+
+    #[inline]
+    fn to_ref(&'a self) -> Self::Output {
+        HCons(&self.0, self.1.to_ref())
+    }
+}
+
+/// Allows for mutably borrowing each element of an `HList`, producing an `HList` of
+/// mutable references through which the elements can be updated in place.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let mut hlist = hlist!(7u32, String::from("four"));
+/// let HCons(n, HCons(s, HNil)) = hlist.to_mut();
+/// *n += 1;
+/// s.push('!');
+/// assert_eq!(hlist, hlist!(8u32, String::from("four!")));
+/// # }
+/// ```
+pub trait ToMut<'a> {
+    /// An `HList` with a mutable reference to each element of `Self`.
+    type Output: HList;
+
+    /// Returns an `HList` with a mutable reference to each element of `self`.
+    fn to_mut(&'a mut self) -> Self::Output;
+}
+
+impl<'a> ToMut<'a> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn to_mut(&'a mut self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<'a, H, T> ToMut<'a> for HCons<H, T>
+where
+    H: 'a,
+    T: HList + ToMut<'a>,
+{
+    type Output = HCons<&'a mut H, T::Output>;
+
+    #[inline]
+    fn to_mut(&'a mut self) -> Self::Output {
+        HCons(&mut self.0, self.1.to_mut())
+    }
+}
+
+/// Allows for cloning each element of an `HList` of references (such as an `HListView`),
+/// producing an `HList` of owned values.
+pub trait ClonedElements {
+    type Output: HList;
+    fn cloned_elements(self) -> Self::Output;
+}
+
+impl ClonedElements for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn cloned_elements(self) -> Self::Output {
+        HNil
+    }
+}
+
+impl<H, T> ClonedElements for HCons<&H, T>
+where
+    H: Clone,
+    T: HList + ClonedElements,
+{
+    type Output = HCons<H, T::Output>;
+
+    #[inline]
+    fn cloned_elements(self) -> Self::Output {
+        HCons(self.0.clone(), self.1.cloned_elements())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pl_hlist_derive::HListSupport;
+
+    #[derive(HListSupport)]
+    #[hlist(views)]
+    struct Buffer {
+        id: u32,
+        data: [u8; 1024],
+    }
+
+    #[test]
+    fn hlist_view_should_borrow_each_element() {
+        let hlist = hlist!(1u8, String::from("two"));
+        let view = hlist.hlist_view();
+        assert_eq!(view, hlist!(&1u8, &String::from("two")));
+        assert!(core::ptr::eq(view.1 .0, &hlist.1 .0));
+        assert_eq!(view.cloned_elements(), hlist);
+    }
+
+    #[test]
+    fn to_ref_and_to_mut_should_borrow_each_element() {
+        let mut hlist = hlist!(1u8, String::from("two"));
+        let refs = hlist.to_ref();
+        assert!(core::ptr::eq(refs.1 .0, &hlist.1 .0));
+        assert_eq!(refs.cloned_elements(), hlist);
+
+        let HCons(n, HCons(s, HNil)) = hlist.to_mut();
+        *n = 3;
+        s.push_str("four");
+        assert_eq!(hlist, hlist!(3u8, String::from("twofour")));
+        assert_eq!(HNil.to_mut(), HNil);
+    }
+
+    #[test]
+    fn hlist_view_should_borrow_each_struct_field() {
+        let buffer = Buffer {
+            id: 7,
+            data: [0u8; 1024],
+        };
+        let HCons(id, HCons(data, HNil)) = buffer.hlist_view();
+        assert_eq!(*id, 7);
+        assert!(core::ptr::eq(data, &buffer.data));
+    }
+
+    #[test]
+    fn to_hlist_ref_and_mut_should_borrow_each_struct_field() {
+        let mut buffer = Buffer {
+            id: 7,
+            data: [0u8; 1024],
+        };
+        assert!(core::ptr::eq(buffer.to_hlist_ref().1 .0, &buffer.data));
+
+        let HCons(id, HCons(data, HNil)) = buffer.to_hlist_mut();
+        *id += 1;
+        data[0] = 0xff;
+        assert_eq!((buffer.id, buffer.data[0]), (8, 0xff));
+
+        let mut hlist = hlist!(1u8, 'a');
+        *hlist.hlist_view_mut().1 .0 = 'b';
+        assert_eq!(hlist, hlist!(1u8, 'b'));
+    }
+}
@@ -0,0 +1,109 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use core::any::Any;
+use core::fmt::Debug;
+
+/// Allows for visiting each element of an `HList` at runtime, as a `&dyn Any`.
+///
+/// This is an escape hatch for when the elements only need to be inspected (for
+/// example, counted by type or downcast to a few known types), without defining a
+/// polymorphic function.  Every element must be `'static`.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let hlist = hlist!(1u8, "two", 3u8);
+/// let mut bytes = 0;
+/// hlist.for_each_dyn(&mut |element| {
+///     if element.is::<u8>() {
+///         bytes += 1;
+///     }
+/// });
+/// assert_eq!(bytes, 2);
+/// # }
+/// ```
+pub trait ForEachDyn {
+    /// Calls `f` with each element, in order.
+    fn for_each_dyn(&self, f: &mut dyn FnMut(&dyn Any));
+}
+
+impl ForEachDyn for HNil {
+    #[inline]
+    fn for_each_dyn(&self, _f: &mut dyn FnMut(&dyn Any)) {}
+}
+
+impl<H: Any, T: HList + ForEachDyn> ForEachDyn for HCons<H, T> {
+    #[inline]
+    fn for_each_dyn(&self, f: &mut dyn FnMut(&dyn Any)) {
+        f(&self.0);
+        self.1.for_each_dyn(f);
+    }
+}
+
+/// Allows for visiting each element of an `HList` at runtime, as a `&dyn Debug`.
+///
+/// Unlike `ForEachDyn`, the elements need not be `'static`, so this also works for
+/// `HList`s of references.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let name = String::from("kim");
+/// let hlist = hlist!(&name, 30u8);
+/// let mut lines = Vec::new();
+/// hlist.for_each_debug(&mut |element| lines.push(format!("{:?}", element)));
+/// assert_eq!(lines, ["\"kim\"", "30"]);
+/// # }
+/// ```
+pub trait ForEachDebug {
+    /// Calls `f` with each element, in order.
+    fn for_each_debug(&self, f: &mut dyn FnMut(&dyn Debug));
+}
+
+impl ForEachDebug for HNil {
+    #[inline]
+    fn for_each_debug(&self, _f: &mut dyn FnMut(&dyn Debug)) {}
+}
+
+impl<H: Debug, T: HList + ForEachDebug> ForEachDebug for HCons<H, T> {
+    #[inline]
+    fn for_each_debug(&self, f: &mut dyn FnMut(&dyn Debug)) {
+        f(&self.0);
+        self.1.for_each_debug(f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn for_each_dyn_should_visit_elements_in_order() {
+        let hlist = hlist!(1u8, "two", 3.0f32, 4u8);
+        let mut sum = 0;
+        let mut count = 0;
+        hlist.for_each_dyn(&mut |element| {
+            count += 1;
+            if let Some(x) = element.downcast_ref::<u8>() {
+                sum += x;
+            }
+        });
+        assert_eq!((count, sum), (4, 5));
+
+        HNil.for_each_dyn(&mut |_| panic!("HNil has no elements"));
+    }
+
+    #[test]
+    fn for_each_debug_should_visit_elements_in_order() {
+        let hlist = hlist!(1u8, "two", Some('c'));
+        let mut out = String::new();
+        hlist.for_each_debug(&mut |element| out.push_str(&format!("{:?};", element)));
+        assert_eq!(out, "1;\"two\";Some('c');");
+    }
+}
@@ -0,0 +1,197 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::labelled::*;
+use core::convert::TryFrom;
+use core::fmt;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+
+/// Error returned when a `JsValue` cannot be converted into an `HList`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromJsError {
+    /// The value was not a JS array.
+    NotAnArray,
+    /// The value was not a JS object.
+    NotAnObject,
+    /// The array length did not match the length of the `HList`.
+    LengthMismatch { actual: u32 },
+    /// The array element at the given index could not be converted.
+    InvalidElement { index: u32 },
+    /// The object property with the given name was missing or could not be converted.
+    InvalidField { name: &'static str },
+}
+
+impl fmt::Display for FromJsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromJsError::NotAnArray => write!(f, "value is not an array"),
+            FromJsError::NotAnObject => write!(f, "value is not an object"),
+            FromJsError::LengthMismatch { actual } => {
+                write!(f, "array has unexpected length {}", actual)
+            }
+            FromJsError::InvalidElement { index } => {
+                write!(f, "array element {} could not be converted", index)
+            }
+            FromJsError::InvalidField { name } => {
+                write!(
+                    f,
+                    "property `{}` is missing or could not be converted",
+                    name
+                )
+            }
+        }
+    }
+}
+
+/// Allows for converting (and consuming) an `HList` into a JS array, one array
+/// element per `HList` element.
+pub trait IntoJsArray {
+    /// Pushes each element of this list onto the end of the given array.
+    fn push_into(self, array: &Array);
+
+    /// Converts this list into a new JS array.
+    fn into_js_array(self) -> Array
+    where
+        Self: Sized,
+    {
+        let array = Array::new();
+        self.push_into(&array);
+        array
+    }
+}
+
+impl IntoJsArray for HNil {
+    fn push_into(self, _: &Array) {}
+}
+
+impl<H, T> IntoJsArray for HCons<H, T>
+where
+    H: Into<JsValue>,
+    T: HList + IntoJsArray,
+{
+    fn push_into(self, array: &Array) {
+        array.push(&self.0.into());
+        self.1.push_into(array);
+    }
+}
+
+/// Allows for converting a JS array into an `HList`.  The array must have exactly
+/// one element per `HList` element.
+pub trait FromJsArray: Sized {
+    /// Converts the array elements starting at `index` into this list.
+    fn from_js_array_at(array: &Array, index: u32) -> Result<Self, FromJsError>;
+
+    /// Converts the given value, which must be a JS array, into this list.
+    fn from_js_array(value: &JsValue) -> Result<Self, FromJsError> {
+        if !Array::is_array(value) {
+            return Err(FromJsError::NotAnArray);
+        }
+        Self::from_js_array_at(&Array::from(value), 0)
+    }
+}
+
+impl FromJsArray for HNil {
+    fn from_js_array_at(array: &Array, index: u32) -> Result<Self, FromJsError> {
+        if array.length() == index {
+            Ok(HNil)
+        } else {
+            Err(FromJsError::LengthMismatch {
+                actual: array.length(),
+            })
+        }
+    }
+}
+
+impl<H, T> FromJsArray for HCons<H, T>
+where
+    H: TryFrom<JsValue>,
+    T: HList + FromJsArray,
+{
+    fn from_js_array_at(array: &Array, index: u32) -> Result<Self, FromJsError> {
+        if index >= array.length() {
+            return Err(FromJsError::LengthMismatch {
+                actual: array.length(),
+            });
+        }
+        let head =
+            H::try_from(array.get(index)).map_err(|_| FromJsError::InvalidElement { index })?;
+        let tail = T::from_js_array_at(array, index + 1)?;
+        Ok(HCons(head, tail))
+    }
+}
+
+/// Allows for converting (and consuming) a labelled `HList` into a JS object, one
+/// property per `Field`.
+pub trait IntoJsObject {
+    /// Sets one property on the given object for each field in this list.
+    fn set_properties(self, object: &Object);
+
+    /// Converts this list into a new JS object.
+    fn into_js_object(self) -> Object
+    where
+        Self: Sized,
+    {
+        let object = Object::new();
+        self.set_properties(&object);
+        object
+    }
+}
+
+impl IntoJsObject for HNil {
+    fn set_properties(self, _: &Object) {}
+}
+
+impl<L, V, T> IntoJsObject for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: Into<JsValue>,
+    T: HList + IntoJsObject,
+{
+    fn set_properties(self, object: &Object) {
+        Reflect::set(object, &L::NAME.into(), &self.0.value.into()).unwrap_throw();
+        self.1.set_properties(object);
+    }
+}
+
+/// Allows for converting a JS object into a labelled `HList` by reading one property
+/// per `Field`.  Additional properties are ignored.
+pub trait FromJsObject: Sized {
+    /// Converts the properties of the given object into this list.
+    fn from_js_object_properties(object: &Object) -> Result<Self, FromJsError>;
+
+    /// Converts the given value, which must be a JS object, into this list.
+    fn from_js_object(value: &JsValue) -> Result<Self, FromJsError> {
+        if !value.is_object() {
+            return Err(FromJsError::NotAnObject);
+        }
+        Self::from_js_object_properties(value.unchecked_ref())
+    }
+}
+
+impl FromJsObject for HNil {
+    fn from_js_object_properties(_: &Object) -> Result<Self, FromJsError> {
+        Ok(HNil)
+    }
+}
+
+impl<L, V, T> FromJsObject for HCons<Field<L, V>, T>
+where
+    L: Label,
+    V: TryFrom<JsValue>,
+    T: HList + FromJsObject,
+{
+    fn from_js_object_properties(object: &Object) -> Result<Self, FromJsError> {
+        let invalid = || FromJsError::InvalidField { name: L::NAME };
+        let value = Reflect::get(object, &L::NAME.into()).map_err(|_| invalid())?;
+        if value.is_undefined() {
+            return Err(invalid());
+        }
+        let value = V::try_from(value).map_err(|_| invalid())?;
+        let tail = T::from_js_object_properties(object)?;
+        Ok(HCons(Field::new(value), tail))
+    }
+}
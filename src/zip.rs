@@ -0,0 +1,323 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+use crate::hlist::*;
+use crate::len::*;
+use crate::map::*;
+
+/// Allows for pairing up the elements of two `HList`s of the same length, producing an
+/// `HList` of tuples.  Zipping lists of different lengths fails to compile.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let values = hlist!(1u8, "two", 3.0f32);
+/// let units = hlist!("count", "label", "meters");
+/// let zipped = values.zip(units);
+/// assert_eq!(zipped, hlist!((1u8, "count"), ("two", "label"), (3.0f32, "meters")));
+/// assert_eq!(zipped.unzip(), (values, units));
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "cannot zip `{Self}` with `{Other}`",
+    note = "both lists must have the same length (see `SameLength`)"
+)]
+pub trait HZippable<Other> {
+    /// The `HList` of pairs.
+    type Zipped: HList;
+
+    /// Pairs each element of this list with the element at the same position in `other`.
+    fn zip(self, other: Other) -> Self::Zipped;
+}
+
+impl HZippable<HNil> for HNil {
+    type Zipped = HNil;
+
+    #[inline]
+    fn zip(self, _: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl<H1, T1, H2, T2> HZippable<HCons<H2, T2>> for HCons<H1, T1>
+where
+    T1: HList + HZippable<T2>,
+    T2: HList,
+{
+    type Zipped = HCons<(H1, H2), T1::Zipped>;
+
+    #[inline]
+    fn zip(self, other: HCons<H2, T2>) -> Self::Zipped {
+        HCons((self.0, other.0), self.1.zip(other.1))
+    }
+}
+
+/// Allows for splitting an `HList` of pairs into an `HList` of the first elements and an
+/// `HList` of the second elements; the inverse of `HZippable::zip`.
+pub trait HUnzippable {
+    /// The `HList` of the first element of each pair.
+    type Left: HList;
+
+    /// The `HList` of the second element of each pair.
+    type Right: HList;
+
+    /// Splits this list of pairs into two lists.
+    fn unzip(self) -> (Self::Left, Self::Right);
+}
+
+impl HUnzippable for HNil {
+    type Left = HNil;
+    type Right = HNil;
+
+    #[inline]
+    fn unzip(self) -> (HNil, HNil) {
+        (HNil, HNil)
+    }
+}
+
+impl<A, B, T> HUnzippable for HCons<(A, B), T>
+where
+    T: HList + HUnzippable,
+{
+    type Left = HCons<A, T::Left>;
+    type Right = HCons<B, T::Right>;
+
+    #[inline]
+    fn unzip(self) -> (Self::Left, Self::Right) {
+        let HCons((a, b), tail) = self;
+        let (left, right) = tail.unzip();
+        (HCons(a, left), HCons(b, right))
+    }
+}
+
+/// Allows for combining the elements of two `HList`s of the same length pairwise, using
+/// a polymorphic binary function (a type that implements `Func<(A, B)>` for each pair of
+/// element types, wrapped in `Poly`).  This is equivalent to zipping the lists and then
+/// mapping over the pairs.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # use core::ops::Sub;
+/// # fn main() {
+/// struct Diff;
+///
+/// impl<A: Sub> Func<(A, A)> for Diff {
+///     type Output = A::Output;
+///     fn call(&mut self, (a, b): (A, A)) -> A::Output { a - b }
+/// }
+///
+/// let before = hlist!(10u32, 2.5f64, -3i8);
+/// let after = hlist!(15u32, 1.0f64, 4i8);
+/// assert_eq!(after.zip_with(before, Poly(Diff)), hlist!(5u32, -1.5f64, 7i8));
+/// # }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "cannot zip `{Self}` with `{Other}`",
+    note = "both lists must have the same length (see `SameLength`)"
+)]
+pub trait HZipWith<Other, Mapper> {
+    /// The `HList` of results.
+    type Output: HList;
+
+    /// Combines each element of this list with the element at the same position in
+    /// `other`, using the given function.
+    fn zip_with(self, other: Other, mapper: Mapper) -> Self::Output;
+}
+
+impl<F> HZipWith<HNil, Poly<F>> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn zip_with(self, _: HNil, _: Poly<F>) -> HNil {
+        HNil
+    }
+}
+
+impl<F, H1, T1, H2, T2> HZipWith<HCons<H2, T2>, Poly<F>> for HCons<H1, T1>
+where
+    F: Func<(H1, H2)>,
+    T1: HList + HZipWith<T2, Poly<F>>,
+    T2: HList,
+{
+    type Output = HCons<F::Output, T1::Output>;
+
+    #[inline]
+    fn zip_with(self, other: HCons<H2, T2>, mapper: Poly<F>) -> Self::Output {
+        let Poly(mut f) = mapper;
+        let head = f.call((self.0, other.0));
+        HCons(head, self.1.zip_with(other.1, Poly(f)))
+    }
+}
+
+/// Allows for transposing an `HList` of rows (each an `HList` of the same length) into
+/// the `HList` of columns, so that the `i`th column holds the `i`th element of each row.
+///
+/// An empty list of rows transposes to an empty list of columns.  Rows of different
+/// lengths fail to compile, with an error naming the mismatched rows.
+///
+/// ```
+/// # #[macro_use] extern crate pl_hlist;
+/// # use pl_hlist::*;
+/// # fn main() {
+/// let rows = hlist!(hlist!(1u8, "a"), hlist!(2u8, "b"), hlist!(3u8, "c"));
+/// let columns = rows.transpose();
+/// assert_eq!(columns, hlist!(hlist!(1u8, 2u8, 3u8), hlist!("a", "b", "c")));
+/// assert_eq!(columns.transpose(), rows);
+/// # }
+/// ```
+pub trait Transpose {
+    /// The `HList` of columns.
+    type Output: HList;
+
+    /// Converts this list of rows into the list of columns.
+    fn transpose(self) -> Self::Output;
+}
+
+impl Transpose for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn transpose(self) -> HNil {
+        HNil
+    }
+}
+
+impl<Row> Transpose for HCons<Row, HNil>
+where
+    Row: WrapColumns,
+{
+    type Output = Row::Output;
+
+    #[inline]
+    fn transpose(self) -> Self::Output {
+        self.0.wrap_columns()
+    }
+}
+
+impl<Row, Next, Rest> Transpose for HCons<Row, HCons<Next, Rest>>
+where
+    Row: SameLength<Next> + ConsColumns<<HCons<Next, Rest> as Transpose>::Output>,
+    Rest: HList,
+    HCons<Next, Rest>: Transpose,
+{
+    type Output = Row::Output;
+
+    #[inline]
+    fn transpose(self) -> Self::Output {
+        let HCons(row, rest) = self;
+        row.cons_columns(rest.transpose())
+    }
+}
+
+/// Helper for `Transpose` that wraps each element of a row in a single-element column.
+#[doc(hidden)]
+pub trait WrapColumns {
+    type Output: HList;
+    fn wrap_columns(self) -> Self::Output;
+}
+
+impl WrapColumns for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn wrap_columns(self) -> HNil {
+        HNil
+    }
+}
+
+impl<H, T: HList + WrapColumns> WrapColumns for HCons<H, T> {
+    type Output = HCons<HCons<H, HNil>, T::Output>;
+
+    #[inline]
+    fn wrap_columns(self) -> Self::Output {
+        HCons(HCons(self.0, HNil), self.1.wrap_columns())
+    }
+}
+
+/// Helper for `Transpose` that moves each element of a row onto the front of the
+/// corresponding column.
+#[doc(hidden)]
+pub trait ConsColumns<Columns> {
+    type Output: HList;
+    fn cons_columns(self, columns: Columns) -> Self::Output;
+}
+
+impl ConsColumns<HNil> for HNil {
+    type Output = HNil;
+
+    #[inline]
+    fn cons_columns(self, _: HNil) -> HNil {
+        HNil
+    }
+}
+
+impl<H, T, C, Cs> ConsColumns<HCons<C, Cs>> for HCons<H, T>
+where
+    T: HList + ConsColumns<Cs>,
+    C: HList,
+    Cs: HList,
+{
+    type Output = HCons<HCons<H, C>, T::Output>;
+
+    #[inline]
+    fn cons_columns(self, columns: HCons<C, Cs>) -> Self::Output {
+        HCons(HCons(self.0, columns.0), self.1.cons_columns(columns.1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zip_and_unzip_should_round_trip() {
+        assert_eq!(HNil.zip(HNil), HNil);
+        assert_eq!(HNil.unzip(), (HNil, HNil));
+
+        let zipped = hlist!(1u8, "two").zip(hlist!('a', vec![2u8]));
+        assert_eq!(zipped, hlist!((1u8, 'a'), ("two", vec![2u8])));
+
+        let (left, right) = zipped.unzip();
+        assert_eq!(left, hlist!(1u8, "two"));
+        assert_eq!(right, hlist!('a', vec![2u8]));
+    }
+
+    struct Sum;
+
+    impl<A: core::ops::Add> Func<(A, A)> for Sum {
+        type Output = A::Output;
+
+        fn call(&mut self, (a, b): (A, A)) -> A::Output {
+            a + b
+        }
+    }
+
+    #[test]
+    fn zip_with_should_combine_elements_pairwise() {
+        assert_eq!(HNil.zip_with(HNil, Poly(Sum)), HNil);
+        let sums = hlist!(1u8, 2.5f32).zip_with(hlist!(2u8, 0.5f32), Poly(Sum));
+        assert_eq!(sums, hlist!(3u8, 3.0f32));
+    }
+
+    #[test]
+    fn transpose_should_swap_rows_and_columns() {
+        assert_eq!(HNil.transpose(), HNil);
+        assert_eq!(hlist!(HNil).transpose(), HNil);
+        assert_eq!(
+            hlist!(hlist!(1u8, 'a')).transpose(),
+            hlist!(hlist!(1u8), hlist!('a'))
+        );
+
+        let rows = hlist!(hlist!(1u8, "a", 1.5f32), hlist!(2u8, "b", 2.5f32));
+        let columns = rows.transpose();
+        assert_eq!(
+            columns,
+            hlist!(hlist!(1u8, 2u8), hlist!("a", "b"), hlist!(1.5f32, 2.5f32))
+        );
+        assert_eq!(columns.transpose(), rows);
+    }
+}
@@ -0,0 +1,61 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Regression tests for `HList`s with 128 elements.  Every recursive trait (including the
+//! derived `Clone`, `Copy`, and `PartialEq` impls) recurses once per element, so lists of
+//! this length require a raised recursion limit, as documented in the README; without it,
+//! `clone`, `pluck`, and `sculpt` all fail with an overflow evaluating their bounds.
+
+#![recursion_limit = "256"]
+
+use pl_hlist::*;
+
+macro_rules! wide_prefix {
+    () => {
+        hlist!(
+            0u64, 1u64, 2u64, 3u64, 4u64, 5u64, 6u64, 7u64, 8u64, 9u64, 10u64, 11u64, 12u64, 13u64,
+            14u64, 15u64, 16u64, 17u64, 18u64, 19u64, 20u64, 21u64, 22u64, 23u64, 24u64, 25u64,
+            26u64, 27u64, 28u64, 29u64, 30u64, 31u64, 32u64, 33u64, 34u64, 35u64, 36u64, 37u64,
+            38u64, 39u64, 40u64, 41u64, 42u64, 43u64, 44u64, 45u64, 46u64, 47u64, 48u64, 49u64,
+            50u64, 51u64, 52u64, 53u64, 54u64, 55u64, 56u64, 57u64, 58u64, 59u64, 60u64, 61u64,
+            62u64, 63u64, 64u64, 65u64, 66u64, 67u64, 68u64, 69u64, 70u64, 71u64, 72u64, 73u64,
+            74u64, 75u64, 76u64, 77u64, 78u64, 79u64, 80u64, 81u64, 82u64, 83u64, 84u64, 85u64,
+            86u64, 87u64, 88u64, 89u64, 90u64, 91u64, 92u64, 93u64, 94u64, 95u64, 96u64, 97u64,
+            98u64, 99u64, 100u64, 101u64, 102u64, 103u64, 104u64, 105u64, 106u64, 107u64, 108u64,
+            109u64, 110u64, 111u64, 112u64, 113u64, 114u64, 115u64, 116u64, 117u64, 118u64, 119u64,
+            120u64, 121u64, 122u64, 123u64, 124u64
+        )
+    };
+}
+
+macro_rules! wide {
+    () => {
+        wide_prefix!().append(hlist!('z', "str", -1i8))
+    };
+}
+
+#[test]
+fn append_should_work_with_128_elements() {
+    let hlist = wide!();
+    assert_eq!(hlist.head(), &0u64);
+    assert_eq!(hlist, hlist.clone());
+    assert_eq!(hlist.append(HNil), hlist);
+    assert!(format!("{:?}", hlist).contains("HCons('z', HCons(\"str\", HCons(-1, HNil)))"));
+}
+
+#[test]
+fn pluck_should_work_with_128_elements() {
+    let (last, remainder): (i8, _) = wide!().pluck();
+    assert_eq!(last, -1);
+    assert_eq!(remainder, wide_prefix!().append(hlist!('z', "str")));
+}
+
+#[test]
+fn sculpt_should_work_with_128_elements() {
+    type Target = HCons<i8, HCons<&'static str, HCons<char, HNil>>>;
+    let (target, remainder): (Target, _) = wide!().sculpt();
+    assert_eq!(target, hlist!(-1i8, "str", 'z'));
+    assert_eq!(remainder, wide_prefix!());
+}
@@ -0,0 +1,49 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Checks that the code generated by the derives names everything it uses by path, so
+//! that it compiles with only the derives imported, and is not confused by local items
+//! that share a name with items of the runtime crate.
+
+#![allow(dead_code)]
+
+use pl_hlist::{CoproductSupport, HListDecode, HListEncode, HListSupport, PartialSupport};
+
+struct Field;
+struct HCons;
+struct HNil;
+trait FromHList {}
+
+#[derive(Clone, Debug, PartialEq, HListSupport, PartialSupport, HListEncode, HListDecode)]
+#[hlist(tuple)]
+struct Reading {
+    sensor: u8,
+    value: i32,
+    #[hlist(skip)]
+    scratch: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, HListSupport)]
+struct Sample(u8, f32);
+
+#[derive(Clone, Debug, PartialEq, CoproductSupport)]
+enum Packet {
+    Reading(Reading),
+    Ack,
+}
+
+#[test]
+fn derived_code_should_not_depend_on_imports() {
+    use pl_hlist::{FromHList as _, HListDecode as _, IntoHList as _};
+
+    let reading = Reading::partial().set_sensor(1).set_value(-2).finalize();
+    let decoded = Reading::decode_from_slice(&[1, 0xfe, 0xff, 0xff, 0xff]).unwrap();
+    assert_eq!(decoded, reading);
+    assert_eq!(Reading::from((1u8, -2i32)), reading);
+    assert_eq!(
+        Sample::from_hlist(Sample(3, 4.0).into_hlist()),
+        Sample(3, 4.0)
+    );
+}
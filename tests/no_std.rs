@@ -0,0 +1,42 @@
+//
+// Copyright (c) 2015-2019 Plausible Labs Cooperative, Inc.
+// All rights reserved.
+//
+
+//! Checks that the code generated by the derives refers only to `core`, so that it
+//! compiles in `no_std` crates.  (The test harness itself still links `std`, but nothing
+//! from the `std` prelude is in scope here.)
+
+#![no_std]
+
+use pl_hlist::*;
+
+#[derive(Clone, Debug, PartialEq, HListSupport)]
+struct Reading {
+    sensor: u8,
+    value: i32,
+    #[hlist(skip)]
+    scratch: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, HListSupport)]
+struct Sample(u8, f32);
+
+#[derive(Clone, Debug, PartialEq, CoproductSupport)]
+enum Packet {
+    Reading(Reading),
+    Ack,
+}
+
+#[test]
+fn derived_conversions_should_work_without_std() {
+    let reading = Reading::from_hlist(hlist!(1u8, -2i32));
+    assert_eq!(reading.scratch, 0);
+    assert_eq!(reading.to_labelled_hlist().tail().head().name(), "value");
+    assert_eq!(reading.clone().into_hlist(), hlist!(1u8, -2i32));
+
+    assert_eq!(Sample(3, 0.5).into_hlist().into_tuple(), (3u8, 0.5f32));
+
+    let packet = Packet::Reading(reading).into_coproduct();
+    assert!(Packet::from_coproduct(packet) != Packet::Ack);
+}